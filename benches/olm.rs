@@ -0,0 +1,479 @@
+// Copyright 2020 Johannes Hayeß
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Benchmarks of the hot paths of a Matrix client: decrypting group and
+//! one-to-one messages, setting up sessions and (un)pickling accounts.
+//!
+//! All inputs are derived from libolm's deterministic test randomness, so
+//! results stay comparable across updates of the vendored `libolm`.
+
+#[cfg(not(feature = "runtime-dlopen"))]
+#[path = "../tests/common/mod.rs"]
+mod common;
+
+#[cfg(not(feature = "runtime-dlopen"))]
+mod benches {
+    use crate::common::{
+        self, alloc, create_account, create_inbound_session, create_outbound_session, decrypt,
+        encrypt, generate_one_time_keys, identity_keys, key_at, memory, one_time_keys, MockRandom,
+    };
+
+    use criterion::{BatchSize, BenchmarkId, Criterion, Throughput};
+    use olm_sys::*;
+
+    use std::ffi::c_void;
+
+    const PICKLE_KEY: &[u8] = b"secret_key";
+
+    /// Private key of the decryption test in `olm/tests/test_pk.cpp`.
+    const PK_PRIVATE_KEY: [u8; 32] = [
+        0x77, 0x07, 0x6D, 0x0A, 0x73, 0x18, 0xA5, 0x7D, 0x3C, 0x16, 0xC1, 0x72, 0x51, 0xB2, 0x66,
+        0x45, 0xDF, 0x4C, 0x2F, 0x87, 0xEB, 0xC0, 0x99, 0x2A, 0xB1, 0x77, 0xFB, 0xA5, 0x1D, 0xB9,
+        0x2C, 0x2A,
+    ];
+
+    fn check(result: usize) -> usize {
+        assert_ne!(result, unsafe { olm_error() });
+        result
+    }
+
+    fn pickle_account(account: *mut OlmAccount) -> Vec<u8> {
+        let mut pickle = vec![0; unsafe { olm_pickle_account_length(account) }];
+        check(unsafe {
+            olm_pickle_account(
+                account,
+                PICKLE_KEY.as_ptr() as *const c_void,
+                PICKLE_KEY.len(),
+                pickle.as_mut_ptr() as *mut c_void,
+                pickle.len(),
+            )
+        });
+        pickle
+    }
+
+    /// Unpickles `pickle`, which libolm destroys.
+    fn unpickle_account(memory: &mut [u64], pickle: &mut [u8]) -> *mut OlmAccount {
+        let account = unsafe { olm_account(common::memory(memory)) };
+        check(unsafe {
+            olm_unpickle_account(
+                account,
+                PICKLE_KEY.as_ptr() as *const c_void,
+                PICKLE_KEY.len(),
+                pickle.as_mut_ptr() as *mut c_void,
+                pickle.len(),
+            )
+        });
+        account
+    }
+
+    fn pickle_session(session: *mut OlmSession) -> Vec<u8> {
+        let mut pickle = vec![0; unsafe { olm_pickle_session_length(session) }];
+        check(unsafe {
+            olm_pickle_session(
+                session,
+                PICKLE_KEY.as_ptr() as *const c_void,
+                PICKLE_KEY.len(),
+                pickle.as_mut_ptr() as *mut c_void,
+                pickle.len(),
+            )
+        });
+        pickle
+    }
+
+    /// Unpickles `pickle`, which libolm destroys.
+    fn unpickle_session(memory: &mut [u64], pickle: &mut [u8]) -> *mut OlmSession {
+        let session = unsafe { olm_session(common::memory(memory)) };
+        check(unsafe {
+            olm_unpickle_session(
+                session,
+                PICKLE_KEY.as_ptr() as *const c_void,
+                PICKLE_KEY.len(),
+                pickle.as_mut_ptr() as *mut c_void,
+                pickle.len(),
+            )
+        });
+        session
+    }
+
+    /// Decrypts `message` in place, which libolm destroys. Unlike
+    /// `common::decrypt` it copies nothing, so that only libolm is timed.
+    fn decrypt_in_place(
+        session: *mut OlmSession,
+        message_type: usize,
+        message: &mut [u8],
+    ) -> usize {
+        let mut plaintext = [0u8; 256];
+        check(unsafe {
+            olm_decrypt(
+                session,
+                message_type,
+                message.as_mut_ptr() as *mut c_void,
+                message.len(),
+                plaintext.as_mut_ptr() as *mut c_void,
+                plaintext.len(),
+            )
+        })
+    }
+
+    /// Creates a session for Bob from Alice's pre-key `message`, which
+    /// libolm destroys. Unlike `common::create_inbound_session` it copies
+    /// nothing, so that only libolm is timed.
+    fn create_inbound_session_in_place(
+        memory: &mut [u64],
+        account: *mut OlmAccount,
+        message: &mut [u8],
+    ) -> *mut OlmSession {
+        let session = unsafe { olm_session(common::memory(memory)) };
+        check(unsafe {
+            olm_create_inbound_session(
+                session,
+                account,
+                message.as_mut_ptr() as *mut c_void,
+                message.len(),
+            )
+        });
+        session
+    }
+
+    /// Bob's side of the loopback test of `olm/tests/test_olm.cpp`, pickled
+    /// at the points the benchmarks start from.
+    struct Conversation {
+        /// Bob's account, before Alice's pre-key message arrived.
+        account_pickle: Vec<u8>,
+        /// Alice's pre-key message which starts the session.
+        pre_key_message: Vec<u8>,
+        /// Bob's session, before decrypting the pre-key message.
+        new_session_pickle: Vec<u8>,
+        /// Bob's session, after Bob has replied to Alice.
+        session_pickle: Vec<u8>,
+        /// Alice's answer to Bob's reply.
+        message: Vec<u8>,
+    }
+
+    impl Conversation {
+        fn new() -> Self {
+            let mut random = MockRandom::new(b'A', 0);
+            let mut alice_account_memory = alloc(unsafe { olm_account_size() });
+            let alice_account = create_account(&mut alice_account_memory, &mut random);
+            let mut bob_account_memory = alloc(unsafe { olm_account_size() });
+            let bob_account = create_account(&mut bob_account_memory, &mut random);
+            generate_one_time_keys(bob_account, 1, &mut random);
+            let account_pickle = pickle_account(bob_account);
+
+            let mut alice_session_memory = alloc(unsafe { olm_session_size() });
+            let (alice_session, result) = create_outbound_session(
+                &mut alice_session_memory,
+                alice_account,
+                key_at(&identity_keys(bob_account), 15),
+                key_at(&one_time_keys(bob_account), 25),
+                &mut random,
+            );
+            check(result);
+            let (_, pre_key_message) = encrypt(alice_session, b"Hello, Bob", &mut random);
+
+            let mut bob_session_memory = alloc(unsafe { olm_session_size() });
+            let (bob_session, result) =
+                create_inbound_session(&mut bob_session_memory, bob_account, &pre_key_message);
+            check(result);
+            let new_session_pickle = pickle_session(bob_session);
+            decrypt(bob_session, OLM_MESSAGE_TYPE_PRE_KEY, &pre_key_message).unwrap();
+
+            let (_, reply) = encrypt(bob_session, b"Hello, Alice", &mut random);
+            decrypt(alice_session, OLM_MESSAGE_TYPE_MESSAGE, &reply).unwrap();
+            let session_pickle = pickle_session(bob_session);
+            let (_, message) = encrypt(alice_session, b"Bye, Bob", &mut random);
+
+            unsafe {
+                olm_clear_session(alice_session);
+                olm_clear_session(bob_session);
+                olm_clear_account(alice_account);
+                olm_clear_account(bob_account);
+            }
+
+            Conversation {
+                account_pickle,
+                pre_key_message,
+                new_session_pickle,
+                session_pickle,
+                message,
+            }
+        }
+    }
+
+    pub fn group_decrypt(c: &mut Criterion) {
+        let mut outbound_memory = alloc(unsafe { olm_outbound_group_session_size() });
+        let outbound = unsafe { olm_outbound_group_session(memory(&mut outbound_memory)) };
+        let mut bytes = MockRandom::new(b'G', 0)
+            .bytes(unsafe { olm_init_outbound_group_session_random_length(outbound) });
+        check(unsafe {
+            olm_init_outbound_group_session(outbound, bytes.as_mut_ptr(), bytes.len())
+        });
+
+        let mut session_key = vec![0; unsafe { olm_outbound_group_session_key_length(outbound) }];
+        check(unsafe {
+            olm_outbound_group_session_key(outbound, session_key.as_mut_ptr(), session_key.len())
+        });
+
+        let mut inbound_memory = alloc(unsafe { olm_inbound_group_session_size() });
+        let inbound = unsafe { olm_inbound_group_session(memory(&mut inbound_memory)) };
+        check(unsafe {
+            olm_init_inbound_group_session(inbound, session_key.as_ptr(), session_key.len())
+        });
+
+        let plaintext = [0x42u8; 256];
+        let mut message = Vec::new();
+
+        // Decrypting doesn't advance the inbound session, so every message
+        // is decrypted from the session key's index 0
+        let mut group = c.benchmark_group("group_decrypt");
+        let mut index = 0;
+        for target in [0, 1, 255, 256, 65_536] {
+            while index <= target {
+                // The message index is varint encoded, so the length grows
+                message.resize(
+                    unsafe { olm_group_encrypt_message_length(outbound, plaintext.len()) },
+                    0,
+                );
+                check(unsafe {
+                    olm_group_encrypt(
+                        outbound,
+                        plaintext.as_ptr(),
+                        plaintext.len(),
+                        message.as_mut_ptr(),
+                        message.len(),
+                    )
+                });
+                index += 1;
+            }
+
+            group.bench_with_input(
+                BenchmarkId::from_parameter(target),
+                &message,
+                |b, message| {
+                    let mut copy = message.clone();
+                    let max_length = check(unsafe {
+                        olm_group_decrypt_max_plaintext_length(
+                            inbound,
+                            copy.as_mut_ptr(),
+                            copy.len(),
+                        )
+                    });
+                    let mut decrypted = vec![0; max_length];
+                    let mut message_index = 0;
+                    b.iter_batched_ref(
+                        || message.clone(),
+                        |message| {
+                            check(unsafe {
+                                olm_group_decrypt(
+                                    inbound,
+                                    message.as_mut_ptr(),
+                                    message.len(),
+                                    decrypted.as_mut_ptr(),
+                                    decrypted.len(),
+                                    &mut message_index,
+                                )
+                            })
+                        },
+                        BatchSize::SmallInput,
+                    )
+                },
+            );
+        }
+        group.finish();
+
+        unsafe {
+            olm_clear_inbound_group_session(inbound);
+            olm_clear_outbound_group_session(outbound);
+        }
+    }
+
+    pub fn decrypt_messages(c: &mut Criterion) {
+        let conversation = Conversation::new();
+
+        let mut group = c.benchmark_group("decrypt");
+        for (name, message_type, session_pickle, message) in [
+            (
+                "pre_key",
+                OLM_MESSAGE_TYPE_PRE_KEY,
+                &conversation.new_session_pickle,
+                &conversation.pre_key_message,
+            ),
+            (
+                "message",
+                OLM_MESSAGE_TYPE_MESSAGE,
+                &conversation.session_pickle,
+                &conversation.message,
+            ),
+        ] {
+            // Decrypting advances the ratchet, so every iteration starts
+            // from a freshly unpickled session in its own memory
+            group.bench_function(name, |b| {
+                b.iter_batched_ref(
+                    || {
+                        let mut memory = alloc(unsafe { olm_session_size() });
+                        let session = unpickle_session(&mut memory, &mut session_pickle.clone());
+                        (memory, session, message.clone())
+                    },
+                    |(_, session, message)| decrypt_in_place(*session, message_type, message),
+                    BatchSize::SmallInput,
+                )
+            });
+        }
+        group.finish();
+    }
+
+    pub fn create_inbound(c: &mut Criterion) {
+        let conversation = Conversation::new();
+        let mut account_memory = alloc(unsafe { olm_account_size() });
+        let account = unpickle_account(
+            &mut account_memory,
+            &mut conversation.account_pickle.clone(),
+        );
+        let mut session_memory = alloc(unsafe { olm_session_size() });
+
+        c.bench_function("create_inbound_session", |b| {
+            b.iter_batched_ref(
+                || conversation.pre_key_message.clone(),
+                |message| create_inbound_session_in_place(&mut session_memory, account, message),
+                BatchSize::SmallInput,
+            )
+        });
+
+        unsafe { olm_clear_account(account) };
+    }
+
+    pub fn account_pickle(c: &mut Criterion) {
+        let mut random = MockRandom::new(b'P', 0);
+        let mut account_memory = alloc(unsafe { olm_account_size() });
+        let account = create_account(&mut account_memory, &mut random);
+        // A full account, as a client keeps it
+        generate_one_time_keys(
+            account,
+            unsafe { olm_account_max_number_of_one_time_keys(account) },
+            &mut random,
+        );
+        let pickle = pickle_account(account);
+
+        let mut group = c.benchmark_group("account");
+        group.bench_function("pickle", |b| b.iter(|| pickle_account(account)));
+        group.bench_function("unpickle", |b| {
+            let mut memory = alloc(unsafe { olm_account_size() });
+            b.iter_batched_ref(
+                || pickle.clone(),
+                |pickle| unpickle_account(&mut memory, pickle),
+                BatchSize::SmallInput,
+            )
+        });
+        group.finish();
+
+        unsafe { olm_clear_account(account) };
+    }
+
+    pub fn pk_decrypt(c: &mut Criterion) {
+        let mut decryption_memory = alloc(unsafe { olm_pk_decryption_size() });
+        let decryption = unsafe { olm_pk_decryption(memory(&mut decryption_memory)) };
+        let mut public_key = vec![0u8; unsafe { olm_pk_key_length() }];
+        check(unsafe {
+            olm_pk_key_from_private(
+                decryption,
+                public_key.as_mut_ptr() as *mut c_void,
+                public_key.len(),
+                PK_PRIVATE_KEY.as_ptr() as *const c_void,
+                PK_PRIVATE_KEY.len(),
+            )
+        });
+
+        let mut encryption_memory = alloc(unsafe { olm_pk_encryption_size() });
+        let encryption = unsafe { olm_pk_encryption(memory(&mut encryption_memory)) };
+        check(unsafe {
+            olm_pk_encryption_set_recipient_key(
+                encryption,
+                public_key.as_ptr() as *const c_void,
+                public_key.len(),
+            )
+        });
+
+        let mut random = MockRandom::new(b'K', 0);
+        let mut group = c.benchmark_group("pk_decrypt");
+        for length in [32, 1024, 16 * 1024] {
+            let plaintext = vec![0x42u8; length];
+            let mut ciphertext =
+                vec![0u8; unsafe { olm_pk_ciphertext_length(encryption, plaintext.len()) }];
+            let mut mac = vec![0u8; unsafe { olm_pk_mac_length(encryption) }];
+            let mut ephemeral_key = vec![0u8; unsafe { olm_pk_key_length() }];
+            let mut bytes = random.bytes(unsafe { olm_pk_encrypt_random_length(encryption) });
+            check(unsafe {
+                olm_pk_encrypt(
+                    encryption,
+                    plaintext.as_ptr() as *const c_void,
+                    plaintext.len(),
+                    ciphertext.as_mut_ptr() as *mut c_void,
+                    ciphertext.len(),
+                    mac.as_mut_ptr() as *mut c_void,
+                    mac.len(),
+                    ephemeral_key.as_mut_ptr() as *mut c_void,
+                    ephemeral_key.len(),
+                    bytes.as_mut_ptr() as *mut c_void,
+                    bytes.len(),
+                )
+            });
+
+            let mut decrypted =
+                vec![0u8; unsafe { olm_pk_max_plaintext_length(decryption, ciphertext.len()) }];
+            group.throughput(Throughput::Bytes(length as u64));
+            group.bench_function(BenchmarkId::from_parameter(length), |b| {
+                b.iter_batched_ref(
+                    || ciphertext.clone(),
+                    |ciphertext| {
+                        check(unsafe {
+                            olm_pk_decrypt(
+                                decryption,
+                                ephemeral_key.as_ptr() as *const c_void,
+                                ephemeral_key.len(),
+                                mac.as_ptr() as *const c_void,
+                                mac.len(),
+                                ciphertext.as_mut_ptr() as *mut c_void,
+                                ciphertext.len(),
+                                decrypted.as_mut_ptr() as *mut c_void,
+                                decrypted.len(),
+                            )
+                        })
+                    },
+                    BatchSize::SmallInput,
+                )
+            });
+        }
+        group.finish();
+
+        unsafe {
+            olm_clear_pk_encryption(encryption);
+            olm_clear_pk_decryption(decryption);
+        }
+    }
+}
+
+#[cfg(not(feature = "runtime-dlopen"))]
+criterion::criterion_group!(
+    benches,
+    benches::group_decrypt,
+    benches::decrypt_messages,
+    benches::create_inbound,
+    benches::account_pickle,
+    benches::pk_decrypt
+);
+#[cfg(not(feature = "runtime-dlopen"))]
+criterion::criterion_main!(benches);
+
+#[cfg(feature = "runtime-dlopen")]
+fn main() {}
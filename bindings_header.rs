@@ -1,4 +1,49 @@
+#![cfg_attr(not(feature = "std"), no_std)]
 #![allow(non_upper_case_globals)]
 #![allow(non_camel_case_types)]
 #![allow(non_snake_case)]
 
+extern crate alloc;
+
+pub mod account;
+pub mod backend;
+#[cfg(feature = "matrix")]
+pub mod backup;
+#[cfg(feature = "matrix")]
+mod base58;
+mod base64;
+#[cfg(feature = "matrix")]
+pub mod cross_signing;
+#[cfg(feature = "matrix")]
+mod crypto;
+#[cfg(olm_sys_no_cxx_stdlib)]
+mod cxxrt;
+#[cfg(feature = "runtime-dlopen")]
+#[allow(clippy::all)]
+mod dynamic;
+pub mod error;
+#[cfg(feature = "matrix")]
+pub mod fallback_keys;
+mod glibc;
+pub mod group_session;
+#[cfg(feature = "matrix")]
+pub mod key_export;
+pub mod megolm;
+mod memory;
+pub mod message;
+#[cfg(feature = "matrix")]
+pub mod one_time_keys;
+pub mod pk;
+#[cfg(feature = "matrix")]
+mod pickle;
+pub mod random;
+#[cfg(feature = "matrix")]
+pub mod secret_storage;
+#[cfg(feature = "matrix")]
+pub mod signing;
+pub mod version;
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+mod wasm;
+
+pub use glibc::*;
+
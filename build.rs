@@ -20,11 +20,38 @@ use std::process::{Command, Stdio};
 use std::{env, path::PathBuf};
 
 const OLM_LINK_VARIANT_ENV: &str = "OLM_LINK_VARIANT";
+const OLM_CXX_STDLIB_ENV: &str = "OLM_CXX_STDLIB";
+const OLM_CMAKE_ARGS_ENV: &str = "OLM_CMAKE_ARGS";
+const OLM_CFLAGS_ENV: &str = "OLM_CFLAGS";
+const OLM_SANITIZER_ENV: &str = "OLM_SANITIZER";
+const OLM_SYMBOL_PREFIX: &str = "olmsys_";
 
 fn main() {
+    println!("cargo:rustc-check-cfg=cfg(olm_sys_no_cxx_stdlib)");
+    println!("cargo:rustc-check-cfg=cfg(olm_sys_aes_schedule)");
+    println!("cargo:rustc-check-cfg=cfg(olm_sys_message_shim)");
+
+    // Rebuild if link variant changed
+    println!("cargo:rerun-if-env-changed={}", OLM_LINK_VARIANT_ENV);
+
+    // libolm gets loaded at runtime, so there's nothing to build or link
+    if env::var_os("CARGO_FEATURE_RUNTIME_DLOPEN").is_some() {
+        return;
+    }
+
     let olm_link_variant = env::var(OLM_LINK_VARIANT_ENV).unwrap_or_else(|_| "static".to_string());
     let target_arch = std::env::var("CARGO_CFG_TARGET_ARCH").unwrap_or_default();
 
+    if prefix_symbols() && olm_link_variant != "static" {
+        panic!("The prefix-symbols feature requires linking libolm statically");
+    }
+
+    // Only a static libolm exports the AES block cipher that `aes256_ctr`
+    // expands the key for once, shared libraries hide it
+    if olm_link_variant == "static" {
+        println!("cargo:rustc-cfg=olm_sys_aes_schedule");
+    }
+
     let src_dir =
         PathBuf::from(env::var_os("CARGO_MANIFEST_DIR").expect("Unable to find manifest dir"))
             .join("olm");
@@ -46,18 +73,16 @@ fn main() {
     } else {
         native_build(&dest_dir, olm_link_variant);
     }
-    // Rebuild if link variant changed
-    println!("cargo:rerun-if-env-changed={}", OLM_LINK_VARIANT_ENV);
 }
 
 fn native_build<P: AsRef<Path>>(src: P, olm_link_variant: String) {
     let target_os = std::env::var("CARGO_CFG_TARGET_OS").unwrap_or_default();
 
     // building libolm as a static lib
-    let mut cmake = cmake::Config::new(src);
+    let mut cmake = cmake::Config::new(src.as_ref());
     cmake.define("BUILD_SHARED_LIBS", "NO");
-    // disable tests for libolm
-    cmake.define("OLM_TESTS", "OFF");
+    // libolm's own tests are only built on request, see tests/libolm.rs
+    cmake.define("OLM_TESTS", if libolm_tests() { "ON" } else { "OFF" });
     cmake.define("CMAKE_POLICY_VERSION_MINIMUM", "3.5");
 
     if target_os == "android" {
@@ -124,32 +149,575 @@ fn native_build<P: AsRef<Path>>(src: P, olm_link_variant: String) {
         }
     }
 
+    configure_from_env(&mut cmake);
+    let sanitizer_flags = sanitizer_flags();
+    for flag in &sanitizer_flags {
+        cmake.cflag(flag).cxxflag(flag);
+    }
+
+    let stdlib = cxx_stdlib(&target_os);
+    if stdlib.as_deref() == Some("none") {
+        if libolm_tests() {
+            panic!(
+                "The libolm-tests feature requires a C++ standard library, unset {}",
+                OLM_CXX_STDLIB_ENV
+            );
+        }
+        configure_without_cxx_stdlib(&mut cmake);
+    }
+
     let dst = cmake.build();
 
+    if libolm_tests() {
+        println!(
+            "cargo:rustc-env=OLM_SYS_LIBOLM_TESTS_DIR={}",
+            dst.join("build/tests").display()
+        );
+    }
+
     // See https://gitlab.gnome.org/BrainBlasted/olm-sys/-/issues/6 for details why this is required
-    if Path::new(&format!("{}/lib64", dst.display())).exists() {
-        println!("cargo:rustc-link-search=native={}/lib64", dst.display());
+    let lib_dir = if Path::new(&format!("{}/lib64", dst.display())).exists() {
+        dst.join("lib64")
     } else {
-        println!("cargo:rustc-link-search=native={}/lib", dst.display());
+        dst.join("lib")
+    };
+    println!("cargo:rustc-link-search=native={}", lib_dir.display());
+
+    let renames = if prefix_symbols() {
+        Some(prefix_archive_symbols(
+            &lib_dir.join(static_lib_name("olm")),
+        ))
+    } else {
+        None
+    };
+
+    if olm_link_variant == "static" {
+        let mut shim = cc::Build::new();
+        for flag in env_words(OLM_CFLAGS_ENV).iter().chain(&sanitizer_flags) {
+            shim.flag(flag);
+        }
+        if stdlib.as_deref() == Some("none") {
+            shim.flag("-fno-exceptions")
+                .flag("-fno-rtti")
+                .flag("-nostdinc++")
+                .include(shim_dir().join("include/c++"));
+        }
+        build_message_shim(shim, src.as_ref(), renames.as_deref());
     }
 
     println!("cargo:rustc-link-lib={}=olm", olm_link_variant);
 
-    if target_os == "linux" || target_os == "android" || target_os == "illumos" {
-        println!("cargo:rustc-link-lib=stdc++");
+    match stdlib.as_deref() {
+        None | Some("none") => {}
+        Some(stdlib) => link_cxx_stdlib(stdlib),
+    }
+
+    if olm_link_variant == "static" {
+        publish_metadata(&dst, &dst.join("include"), &olm_version(&src), true);
+    } else {
+        publish_system_metadata(src.as_ref());
+    }
+}
+
+/// Tells dependent build scripts where to find libolm, as `DEP_OLM_ROOT`,
+/// `DEP_OLM_INCLUDE`, `DEP_OLM_VERSION` and `DEP_OLM_STATIC`.
+fn publish_metadata(
+    root: &Path,
+    include: &Path,
+    version: &(String, String, String),
+    is_static: bool,
+) {
+    let (major, minor, patch) = version;
+    println!("cargo:root={}", root.display());
+    println!("cargo:include={}", include.display());
+    println!("cargo:version={}.{}.{}", major, minor, patch);
+    println!("cargo:static={}", if is_static { 1 } else { 0 });
+}
+
+/// Publishes the metadata of the system libolm, as found by pkg-config.
+///
+/// Without pkg-config the vendored headers are published instead, which the
+/// bindings have been generated from.
+fn publish_system_metadata(src: &Path) {
+    let library = pkg_config::Config::new()
+        .cargo_metadata(false)
+        .env_metadata(true)
+        .probe("olm");
+
+    match library {
+        Ok(library) => {
+            let root = pkg_config::get_variable("olm", "prefix").unwrap_or_default();
+            let include = library
+                .include_paths
+                .first()
+                .cloned()
+                .unwrap_or_else(|| PathBuf::from(&root).join("include"));
+            let mut parts = library.version.split('.').map(str::to_string);
+            let version = (
+                parts.next().unwrap_or_default(),
+                parts.next().unwrap_or_default(),
+                parts.next().unwrap_or_default(),
+            );
+
+            publish_metadata(Path::new(&root), &include, &version, false);
+        }
+        Err(_) => {
+            println!(
+                "cargo:warning=libolm not found by pkg-config, publishing the vendored headers"
+            );
+            publish_metadata(src, &src.join("include"), &olm_version(src), false);
+        }
+    }
+}
+
+/// Applies the user's `OLM_CMAKE_ARGS` and `OLM_CFLAGS` to the build.
+///
+/// `OLM_CMAKE_ARGS` holds whitespace separated `-DNAME=VALUE` definitions or
+/// other arguments passed to cmake when configuring. `CMAKE_BUILD_TYPE` sets
+/// the profile libolm is built with. `OLM_CFLAGS` is passed to both the C and
+/// the C++ compiler.
+///
+/// Neither variable supports quoting, so an argument can't contain whitespace,
+/// e.g. a path with spaces.
+fn configure_from_env(cmake: &mut cmake::Config) {
+    for arg in env_words(OLM_CMAKE_ARGS_ENV) {
+        match arg
+            .strip_prefix("-D")
+            .and_then(|definition| definition.split_once('='))
+        {
+            Some((name, value)) => {
+                // A type may be given as in `-DNAME:BOOL=ON`
+                if name.split(':').next() == Some("CMAKE_BUILD_TYPE") {
+                    cmake.profile(value);
+                } else {
+                    cmake.define(name, value);
+                }
+            }
+            None => {
+                cmake.configure_arg(arg);
+            }
+        }
+    }
+
+    for flag in env_words(OLM_CFLAGS_ENV) {
+        cmake.cflag(&flag).cxxflag(&flag);
+    }
+}
+
+/// The flags instrumenting libolm for the sanitizers the Rust code is built
+/// with (`-Zsanitizer=...`), or those listed in `OLM_SANITIZER` if it isn't
+/// empty.
+///
+/// The sanitizer runtime isn't linked here, rustc links it for instrumented
+/// Rust code.
+fn sanitizer_flags() -> Vec<String> {
+    println!("cargo:rerun-if-env-changed={}", OLM_SANITIZER_ENV);
+    // An empty `OLM_SANITIZER` counts as unset rather than disabling them
+    let sanitizers = env::var(OLM_SANITIZER_ENV)
+        .ok()
+        .filter(|sanitizers| !sanitizers.trim().is_empty())
+        .or_else(|| env::var("CARGO_CFG_SANITIZE").ok())
+        .unwrap_or_default();
+
+    let mut flags = Vec::new();
+    for sanitizer in sanitizers.split(',').map(str::trim) {
+        match sanitizer {
+            "" => {}
+            "address" | "hwaddress" | "leak" | "thread" => {
+                flags.push(format!("-fsanitize={}", sanitizer));
+            }
+            "memory" => {
+                flags.push("-fsanitize=memory".to_string());
+                flags.push("-fsanitize-memory-track-origins".to_string());
+            }
+            _ => println!(
+                "cargo:warning=libolm isn't instrumented for the {} sanitizer",
+                sanitizer
+            ),
+        }
+    }
+
+    if !flags.is_empty() {
+        // For usable stack traces in the sanitizers' reports
+        flags.push("-fno-omit-frame-pointer".to_string());
+    }
+
+    flags
+}
+
+/// The whitespace separated words of the environment variable `var`.
+///
+/// Quotes and backslashes are taken literally, there is no shell-like escaping.
+fn env_words(var: &str) -> Vec<String> {
+    println!("cargo:rerun-if-env-changed={}", var);
+
+    env::var(var)
+        .unwrap_or_default()
+        .split_whitespace()
+        .map(str::to_string)
+        .collect()
+}
+
+/// The C++ standard library to link for the C++ parts of libolm, if any.
+///
+/// The library can be overridden with `OLM_CXX_STDLIB`, using the syntax of
+/// `cargo:rustc-link-lib`, e.g. `c++` or `static=stdc++`. `none` builds libolm
+/// so that it doesn't need any, see [`configure_without_cxx_stdlib`].
+fn cxx_stdlib(target_os: &str) -> Option<String> {
+    println!("cargo:rerun-if-env-changed={}", OLM_CXX_STDLIB_ENV);
+
+    if let Ok(stdlib) = env::var(OLM_CXX_STDLIB_ENV) {
+        return Some(stdlib);
+    }
+
+    let target_env = env::var("CARGO_CFG_TARGET_ENV").unwrap_or_default();
+    let stdlib = match (target_os, target_env.as_str()) {
+        // musl binaries are linked statically, there's no shared libstdc++ to use
+        ("linux", "musl") => "static=stdc++",
+        ("linux", _) | ("android", _) | ("illumos", _) => "stdc++",
+        ("freebsd", _) | ("macos", _) | ("ios", _) => "c++",
+        // e.g. MSVC, which links its C++ runtime on its own
+        _ => return None,
+    };
+
+    Some(stdlib.to_string())
+}
+
+/// Builds the C++ parts of libolm without the C++ standard library.
+///
+/// libolm uses no exceptions, RTTI or STL containers, so its C++ sources build
+/// against the header shims in `shim/include/c++`. Those leave out `<iostream>`,
+/// which would otherwise pull in the runtime's static initialisers. The few
+/// runtime symbols a C++ compiler may still emit references to are provided
+/// by the `cxxrt` module of this crate.
+fn configure_without_cxx_stdlib(cmake: &mut cmake::Config) {
+    let target_env = env::var("CARGO_CFG_TARGET_ENV").unwrap_or_default();
+    if target_env == "msvc" {
+        panic!("{}=none is not supported with MSVC", OLM_CXX_STDLIB_ENV);
     }
-    if target_os == "freebsd" || target_os == "macos" || target_os == "ios" {
-        println!("cargo:rustc-link-lib=c++");
+
+    // -nostdlib++ only matters when linking, which happens in rustc, where the
+    // C++ library is simply not passed.
+    cmake
+        .cxxflag("-fno-exceptions")
+        .cxxflag("-fno-rtti")
+        .cxxflag("-nostdinc++")
+        .cxxflag("-isystem")
+        .cxxflag(shim_dir().join("include/c++"));
+
+    println!("cargo:rustc-cfg=olm_sys_no_cxx_stdlib");
+    println!("cargo:rerun-if-changed={}", shim_dir().display());
+}
+
+/// Links `stdlib`, a value in the syntax of `cargo:rustc-link-lib`.
+fn link_cxx_stdlib(stdlib: &str) {
+    if let Some(name) = stdlib.strip_prefix("static=") {
+        // The static C++ library lives next to the compiler, not in a
+        // directory rustc searches by default.
+        let compiler = cc::Build::new().cpp(true).get_compiler();
+        let output = compiler
+            .to_command()
+            .arg(format!("-print-file-name=lib{}.a", name))
+            .output()
+            .expect("Failed to run the C++ compiler");
+        let archive = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim());
+
+        match archive.parent() {
+            Some(dir) if archive.is_absolute() => {
+                println!("cargo:rustc-link-search=native={}", dir.display())
+            }
+            _ => println!(
+                "cargo:warning=lib{}.a not found by the C++ compiler, relying on the linker's search path",
+                name
+            ),
+        }
+    }
+
+    println!("cargo:rustc-link-lib={}", stdlib);
+}
+
+/// Sources making up libolm, mirroring the `olm` target in its CMakeLists.txt.
+const OLM_SOURCES: &[&str] = &[
+    "src/account.cpp",
+    "src/base64.cpp",
+    "src/cipher.cpp",
+    "src/crypto.cpp",
+    "src/memory.cpp",
+    "src/message.cpp",
+    "src/pickle.cpp",
+    "src/ratchet.cpp",
+    "src/session.cpp",
+    "src/utility.cpp",
+    "src/pk.cpp",
+    "src/sas.c",
+    "src/ed25519.c",
+    "src/error.c",
+    "src/inbound_group_session.c",
+    "src/megolm.c",
+    "src/olm.cpp",
+    "src/outbound_group_session.c",
+    "src/pickle_encoding.c",
+    "lib/crypto-algorithms/aes.c",
+    "lib/crypto-algorithms/sha256.c",
+    "lib/curve25519-donna/curve25519-donna.c",
+];
+
+/// Headers and sources standing in for the C and C++ standard libraries, and
+/// C entry points to libolm's C++ internals.
+fn shim_dir() -> PathBuf {
+    PathBuf::from(env::var_os("CARGO_MANIFEST_DIR").expect("Unable to find manifest dir"))
+        .join("shim")
+}
+
+/// Compiles `shim/message.cpp`, the C entry points to libolm's Olm message
+/// decoders, with `build` set up like for libolm's C++ sources in `src`.
+///
+/// Only a static libolm has the decoders, see `src/message.rs`. With the
+/// prefix-symbols feature the shim's references to libolm are redefined with
+/// the `renames` of libolm's symbols.
+fn build_message_shim(mut build: cc::Build, src: &Path, renames: Option<&Path>) {
+    build
+        .cpp(true)
+        .cpp_link_stdlib(None)
+        .include(src.join("include"))
+        .define("OLM_STATIC_DEFINE", None)
+        .warnings(false)
+        .file(shim_dir().join("message.cpp"))
+        .compile("olm_sys_shim");
+
+    if let Some(renames) = renames {
+        let out_dir = PathBuf::from(env::var_os("OUT_DIR").expect("Unable to find output dir"));
+        redefine_symbols(&out_dir.join(static_lib_name("olm_sys_shim")), renames);
     }
+
+    println!("cargo:rustc-cfg=olm_sys_message_shim");
+    println!(
+        "cargo:rerun-if-changed={}",
+        shim_dir().join("message.cpp").display()
+    );
+}
+
+/// Reads the libolm version from the `project()` call in its CMakeLists.txt.
+fn olm_version<P: AsRef<Path>>(src: P) -> (String, String, String) {
+    let cmake_lists = std::fs::read_to_string(src.as_ref().join("CMakeLists.txt"))
+        .expect("Failed to read CMakeLists.txt of libolm");
+    let version = cmake_lists
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("project(olm VERSION "))
+        .and_then(|rest| rest.split_whitespace().next())
+        .expect("Unable to find the libolm version in CMakeLists.txt");
+
+    let mut parts = version.split('.').map(str::to_string);
+    (
+        parts.next().expect("Missing major version"),
+        parts.next().expect("Missing minor version"),
+        parts.next().expect("Missing patch version"),
+    )
 }
 
 fn wasm_build<P: AsRef<Path>>(src: P) {
+    let target_os = env::var("CARGO_CFG_TARGET_OS").unwrap_or_default();
+
+    match target_os.as_str() {
+        "emscripten" => emscripten_build(src),
+        "unknown" | "wasi" => clang_wasm_build(src, &target_os),
+        _ => panic!(
+            "Unsupported WebAssembly target OS {} given, if this is an error please report a bug",
+            target_os
+        ),
+    }
+}
+
+fn emscripten_build<P: AsRef<Path>>(src: P) {
     let lib_search_path = src.as_ref().join("build/wasm/");
 
     // building libolm as a static lib
-    run(Command::new("make").arg("wasm").current_dir(src));
+    run(Command::new("make").arg("wasm").current_dir(&src));
+    let renames = if prefix_symbols() {
+        Some(prefix_archive_symbols(&lib_search_path.join("libolm.a")))
+    } else {
+        None
+    };
+    build_message_shim(cc::Build::new(), src.as_ref(), renames.as_deref());
     println!("cargo:rustc-link-search={}", lib_search_path.display());
     println!("cargo:rustc-link-lib=static=olm");
+
+    publish_metadata(
+        src.as_ref(),
+        &src.as_ref().join("include"),
+        &olm_version(&src),
+        true,
+    );
+}
+
+/// Builds libolm with clang's WebAssembly backend, without Emscripten.
+///
+/// libolm's C++ code needs neither exceptions nor RTTI, so it is built against
+/// the header shims in `shim/include/c++` instead of a C++ standard library.
+/// On `wasm32-unknown-unknown` there's no libc either, `shim/include/c` and
+/// `shim/libc.c` stand in for the little that libolm uses of it.
+fn clang_wasm_build<P: AsRef<Path>>(src: P, target_os: &str) {
+    let src = src.as_ref();
+    let shim_dir = shim_dir();
+    let (major, minor, patch) = olm_version(src);
+    let freestanding = target_os == "unknown";
+
+    let mut base = cc::Build::new();
+    base.include(src.join("include"))
+        .include(src.join("lib"))
+        .define("NDEBUG", None)
+        .define("OLM_STATIC_DEFINE", None)
+        .define("OLMLIB_VERSION_MAJOR", major.as_str())
+        .define("OLMLIB_VERSION_MINOR", minor.as_str())
+        .define("OLMLIB_VERSION_PATCH", patch.as_str())
+        .warnings(false);
+    for flag in env_words(OLM_CFLAGS_ENV) {
+        base.flag(&flag);
+    }
+    if freestanding {
+        base.flag("-ffreestanding")
+            .flag("-nostdlibinc")
+            .include(shim_dir.join("include/c"));
+    }
+
+    let mut c = base.clone();
+    c.std("c99");
+    for source in OLM_SOURCES.iter().filter(|source| source.ends_with(".c")) {
+        c.file(src.join(source));
+    }
+    if freestanding {
+        c.file(shim_dir.join("libc.c"));
+    }
+
+    let mut cxx = base;
+    cxx.cpp(true)
+        .cpp_link_stdlib(None)
+        .std("c++11")
+        .flag("-fno-exceptions")
+        .flag("-fno-rtti")
+        .flag("-nostdinc++")
+        .include(shim_dir.join("include/c++"));
+    let shim = cxx.clone();
+    for source in OLM_SOURCES.iter().filter(|source| source.ends_with(".cpp")) {
+        cxx.file(src.join(source));
+    }
+
+    let mut objects = c.compile_intermediates();
+    objects.extend(cxx.compile_intermediates());
+    cc::Build::new().objects(objects).compile("olm");
+    let out_dir = PathBuf::from(env::var_os("OUT_DIR").expect("Unable to find output dir"));
+    let renames = if prefix_symbols() {
+        Some(prefix_archive_symbols(&out_dir.join("libolm.a")))
+    } else {
+        None
+    };
+    build_message_shim(shim, src, renames.as_deref());
+
+    publish_metadata(&out_dir, &src.join("include"), &(major, minor, patch), true);
+
+    println!("cargo:rerun-if-changed={}", shim_dir.display());
+}
+
+/// Whether the `prefix-symbols` feature is enabled.
+fn prefix_symbols() -> bool {
+    env::var_os("CARGO_FEATURE_PREFIX_SYMBOLS").is_some()
+}
+
+/// Whether the `libolm-tests` feature is enabled.
+fn libolm_tests() -> bool {
+    env::var_os("CARGO_FEATURE_LIBOLM_TESTS").is_some()
+}
+
+/// File name of the static library `name` on the target.
+fn static_lib_name(name: &str) -> String {
+    let target_env = env::var("CARGO_CFG_TARGET_ENV").unwrap_or_default();
+    if target_env == "msvc" {
+        format!("{}.lib", name)
+    } else {
+        format!("lib{}.a", name)
+    }
+}
+
+/// Prepends `OLM_SYMBOL_PREFIX` to every symbol the archive defines, so that
+/// it can be linked next to another copy of libolm.
+///
+/// Undefined symbols, e.g. those of libc, are left alone. `NM` and `OBJCOPY`
+/// (optionally suffixed with the target, like `OBJCOPY_x86_64_unknown_linux_gnu`)
+/// select the tools, which default to `nm` and `objcopy`.
+///
+/// Returns the file listing the renames, for [`redefine_symbols`].
+fn prefix_archive_symbols(archive: &Path) -> PathBuf {
+    let output = Command::new(binutil("NM", "nm"))
+        .arg("--defined-only")
+        .arg("--extern-only")
+        .arg("--portability")
+        .arg(archive)
+        .stderr(Stdio::inherit())
+        .output()
+        .expect("Failed to run nm, which the prefix-symbols feature requires");
+    assert!(
+        output.status.success(),
+        "nm failed on {}",
+        archive.display()
+    );
+
+    // Symbols of C functions carry a leading underscore on these targets
+    let target_vendor = env::var("CARGO_CFG_TARGET_VENDOR").unwrap_or_default();
+    let target_os = env::var("CARGO_CFG_TARGET_OS").unwrap_or_default();
+    let target_arch = env::var("CARGO_CFG_TARGET_ARCH").unwrap_or_default();
+    let leading_underscore =
+        target_vendor == "apple" || (target_os == "windows" && target_arch == "x86");
+
+    let listing = String::from_utf8_lossy(&output.stdout);
+    let mut symbols: Vec<&str> = listing
+        .lines()
+        // Archive members are listed as `libolm.a[account.cpp.o]:`
+        .filter(|line| !line.ends_with(':'))
+        .filter_map(|line| line.split_whitespace().next())
+        .collect();
+    symbols.sort_unstable();
+    symbols.dedup();
+
+    let renames: String = symbols
+        .iter()
+        .map(|&symbol| match symbol.strip_prefix('_') {
+            Some(name) if leading_underscore => ("_", name),
+            _ => ("", symbol),
+        })
+        // The archive may have been prefixed by an earlier run of this script,
+        // the shim still needs the renames then
+        .map(|(underscore, name)| (underscore, name.trim_start_matches(OLM_SYMBOL_PREFIX)))
+        .map(|(underscore, name)| {
+            format!("{0}{1} {0}{2}{1}\n", underscore, name, OLM_SYMBOL_PREFIX)
+        })
+        .collect();
+    let renames_file = archive.with_extension("renames");
+    std::fs::write(&renames_file, renames).expect("Failed to write the symbol renames");
+
+    redefine_symbols(archive, &renames_file);
+    renames_file
+}
+
+/// Renames the symbols of `archive` as listed in the file `renames`, defined
+/// ones as well as references to them.
+fn redefine_symbols(archive: &Path, renames: &Path) {
+    run(Command::new(binutil("OBJCOPY", "objcopy"))
+        .arg(format!("--redefine-syms={}", renames.display()))
+        .arg(archive));
+}
+
+/// The binary utility configured with the environment variable `var` for the
+/// target, or `default`.
+fn binutil(var: &str, default: &str) -> String {
+    let target = env::var("TARGET").unwrap_or_default().replace('-', "_");
+    for var in [format!("{}_{}", var, target), var.to_string()] {
+        println!("cargo:rerun-if-env-changed={}", var);
+        if let Ok(tool) = env::var(&var) {
+            return tool;
+        }
+    }
+
+    default.to_string()
 }
 
 fn run(cmd: &mut Command) {
@@ -0,0 +1,54 @@
+// Copyright 2020 Johannes Hayeß
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Rust counterpart of `olm/fuzzing/fuzzers/fuzz_decode_message.cpp`, which
+//! calls the message decoder directly, like `olm_sys::message` does through
+//! `shim/message.cpp`. The input is also decoded through a session, as a
+//! normal message by `olm_decrypt_max_plaintext_length(…)` and as a pre-key
+//! message by `olm_matches_inbound_session(…)`, neither of which checks a MAC.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use olm_sys::message::{OlmMessage, PreKeyMessage};
+use olm_sys::*;
+use olm_sys_fuzz::{alloc, conversation};
+
+use std::ffi::c_void;
+
+fuzz_target!(|data: &[u8]| {
+    let mut memory = alloc(unsafe { olm_session_size() });
+    let session = conversation().session(&mut memory);
+
+    let mut message = data.to_vec();
+    unsafe {
+        olm_decrypt_max_plaintext_length(
+            session,
+            OLM_MESSAGE_TYPE_MESSAGE,
+            message.as_mut_ptr() as *mut c_void,
+            message.len(),
+        )
+    };
+    message.copy_from_slice(data);
+    unsafe {
+        olm_matches_inbound_session(session, message.as_mut_ptr() as *mut c_void, message.len())
+    };
+
+    unsafe { olm_clear_session(session) };
+
+    if let Ok(message) = std::str::from_utf8(data) {
+        let _ = OlmMessage::parse(message);
+        let _ = PreKeyMessage::parse(message);
+    }
+});
@@ -0,0 +1,36 @@
+// Copyright 2020 Johannes Hayeß
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Rust counterpart of `olm/fuzzing/fuzzers/fuzz_decrypt.cpp`: decrypts the
+//! input with Bob's session. The first byte selects the message type, the
+//! rest is the base64 message.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use olm_sys::*;
+use olm_sys_fuzz::{alloc, conversation, decrypt};
+
+fuzz_target!(|data: &[u8]| {
+    let Some((&message_type, message)) = data.split_first() else {
+        return;
+    };
+
+    let mut memory = alloc(unsafe { olm_session_size() });
+    let session = conversation().session(&mut memory);
+    unsafe {
+        decrypt(session, usize::from(message_type & 1), message);
+        olm_clear_session(session);
+    }
+});
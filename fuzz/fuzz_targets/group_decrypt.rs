@@ -0,0 +1,54 @@
+// Copyright 2020 Johannes Hayeß
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Rust counterpart of `olm/fuzzing/fuzzers/fuzz_group_decrypt.cpp`: decrypts
+//! the input, a base64 group message, with an inbound group session.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use olm_sys::*;
+use olm_sys_fuzz::{alloc, memory, GROUP_SESSION_KEY};
+
+fuzz_target!(|data: &[u8]| {
+    let mut session_memory = alloc(unsafe { olm_inbound_group_session_size() });
+    let session = unsafe { olm_inbound_group_session(memory(&mut session_memory)) };
+    let result = unsafe {
+        olm_init_inbound_group_session(session, GROUP_SESSION_KEY.as_ptr(), GROUP_SESSION_KEY.len())
+    };
+    assert_ne!(result, unsafe { olm_error() });
+
+    // Both steps destroy the message, so only copies are passed
+    let mut message = data.to_vec();
+    let max_length = unsafe {
+        olm_group_decrypt_max_plaintext_length(session, message.as_mut_ptr(), message.len())
+    };
+    if max_length != unsafe { olm_error() } {
+        let mut plaintext = vec![0; max_length];
+        let mut message_index = 0;
+        message.copy_from_slice(data);
+        unsafe {
+            olm_group_decrypt(
+                session,
+                message.as_mut_ptr(),
+                message.len(),
+                plaintext.as_mut_ptr(),
+                plaintext.len(),
+                &mut message_index,
+            )
+        };
+    }
+
+    unsafe { olm_clear_inbound_group_session(session) };
+});
@@ -0,0 +1,72 @@
+// Copyright 2020 Johannes Hayeß
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Decrypts the input with the private key of `olm/tests/test_pk.cpp`.
+//! libolm has no C fuzzer for this one.
+//!
+//! The first two bytes are the lengths of the base64 ephemeral key and MAC
+//! which follow them, the rest is the base64 ciphertext.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use olm_sys::*;
+use olm_sys_fuzz::{alloc, memory, PK_PRIVATE_KEY};
+
+use std::ffi::c_void;
+
+fuzz_target!(|data: &[u8]| {
+    let [key_length, mac_length, rest @ ..] = data else {
+        return;
+    };
+    let (key_length, mac_length) = (usize::from(*key_length), usize::from(*mac_length));
+    if rest.len() < key_length + mac_length {
+        return;
+    }
+    let (ephemeral_key, rest) = rest.split_at(key_length);
+    let (mac, ciphertext) = rest.split_at(mac_length);
+
+    let mut decryption_memory = alloc(unsafe { olm_pk_decryption_size() });
+    let decryption = unsafe { olm_pk_decryption(memory(&mut decryption_memory)) };
+    let mut public_key = vec![0u8; unsafe { olm_pk_key_length() }];
+    let result = unsafe {
+        olm_pk_key_from_private(
+            decryption,
+            public_key.as_mut_ptr() as *mut c_void,
+            public_key.len(),
+            PK_PRIVATE_KEY.as_ptr() as *const c_void,
+            PK_PRIVATE_KEY.len(),
+        )
+    };
+    assert_ne!(result, unsafe { olm_error() });
+
+    // libolm destroys the ciphertext
+    let mut ciphertext = ciphertext.to_vec();
+    let mut plaintext =
+        vec![0u8; unsafe { olm_pk_max_plaintext_length(decryption, ciphertext.len()) }];
+    unsafe {
+        olm_pk_decrypt(
+            decryption,
+            ephemeral_key.as_ptr() as *const c_void,
+            ephemeral_key.len(),
+            mac.as_ptr() as *const c_void,
+            mac.len(),
+            ciphertext.as_mut_ptr() as *mut c_void,
+            ciphertext.len(),
+            plaintext.as_mut_ptr() as *mut c_void,
+            plaintext.len(),
+        );
+        olm_clear_pk_decryption(decryption);
+    }
+});
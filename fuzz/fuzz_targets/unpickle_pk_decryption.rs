@@ -0,0 +1,44 @@
+// Copyright 2020 Johannes Hayeß
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Unpickles the input, a raw `OlmPkDecryption` pickle. libolm has no C
+//! fuzzer for this one.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use olm_sys::*;
+use olm_sys_fuzz::{alloc, encrypt_pickle, memory, PICKLE_KEY};
+
+use std::ffi::c_void;
+
+fuzz_target!(|data: &[u8]| {
+    let mut decryption_memory = alloc(unsafe { olm_pk_decryption_size() });
+    let decryption = unsafe { olm_pk_decryption(memory(&mut decryption_memory)) };
+
+    let mut pickle = encrypt_pickle(data);
+    let mut public_key = vec![0u8; unsafe { olm_pk_key_length() }];
+    unsafe {
+        olm_unpickle_pk_decryption(
+            decryption,
+            PICKLE_KEY.as_ptr() as *const c_void,
+            PICKLE_KEY.len(),
+            pickle.as_mut_ptr() as *mut c_void,
+            pickle.len(),
+            public_key.as_mut_ptr() as *mut c_void,
+            public_key.len(),
+        );
+        olm_clear_pk_decryption(decryption);
+    }
+});
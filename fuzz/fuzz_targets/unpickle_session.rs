@@ -0,0 +1,41 @@
+// Copyright 2020 Johannes Hayeß
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Rust counterpart of `olm/fuzzing/fuzzers/fuzz_unpickle_session.cpp`:
+//! unpickles the input, a raw session pickle, into a session.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use olm_sys::*;
+use olm_sys_fuzz::{alloc, encrypt_pickle, memory, PICKLE_KEY};
+
+use std::ffi::c_void;
+
+fuzz_target!(|data: &[u8]| {
+    let mut session_memory = alloc(unsafe { olm_session_size() });
+    let session = unsafe { olm_session(memory(&mut session_memory)) };
+
+    let mut pickle = encrypt_pickle(data);
+    unsafe {
+        olm_unpickle_session(
+            session,
+            PICKLE_KEY.as_ptr() as *const c_void,
+            PICKLE_KEY.len(),
+            pickle.as_mut_ptr() as *mut c_void,
+            pickle.len(),
+        );
+        olm_clear_session(session);
+    }
+});
@@ -0,0 +1,178 @@
+// Copyright 2020 Johannes Hayeß
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Fixtures shared by the fuzz targets in `fuzz_targets/`.
+//!
+//! The C fuzzers in `olm/fuzzing/fuzzers` read their sessions from files;
+//! these targets build theirs from `libolm`'s test vectors instead, so they
+//! need no setup besides a seed corpus.
+
+use olm_sys::*;
+
+use std::ffi::c_void;
+use std::sync::OnceLock;
+
+// The fixtures of the tests and benchmarks, which these build on
+#[path = "../../tests/common/mod.rs"]
+mod common;
+
+pub use common::{alloc, memory};
+use common::{
+    create_account, create_inbound_session, create_outbound_session, decrypt as try_decrypt,
+    encrypt, generate_one_time_keys, identity_keys, key_at, one_time_keys, MockRandom,
+};
+
+/// The key all fixtures are pickled with. As the C fuzzers, which unpickle
+/// with an empty key, the unpickle targets re-encrypt their input with it.
+pub const PICKLE_KEY: &[u8] = b"";
+
+/// Session key of the export/import test in `olm/tests/test_group_session.cpp`.
+pub const GROUP_SESSION_KEY: &[u8] = common::SESSION_KEY;
+
+/// Alice's private key of the decryption test in `olm/tests/test_pk.cpp`.
+pub const PK_PRIVATE_KEY: [u8; 32] = [
+    0x77, 0x07, 0x6D, 0x0A, 0x73, 0x18, 0xA5, 0x7D, 0x3C, 0x16, 0xC1, 0x72, 0x51, 0xB2, 0x66, 0x45,
+    0xDF, 0x4C, 0x2F, 0x87, 0xEB, 0xC0, 0x99, 0x2A, 0xB1, 0x77, 0xFB, 0xA5, 0x1D, 0xB9, 0x2C, 0x2A,
+];
+
+/// Encrypts the raw pickle `raw` with [`PICKLE_KEY`], so that the fuzzer
+/// reaches the parsing of the pickle instead of failing its MAC.
+pub fn encrypt_pickle(raw: &[u8]) -> Vec<u8> {
+    let mut pickle = vec![0; unsafe { _olm_enc_output_length(raw.len()) }];
+    unsafe {
+        let position = _olm_enc_output_pos(pickle.as_mut_ptr(), raw.len());
+        std::ptr::copy_nonoverlapping(raw.as_ptr(), position, raw.len());
+        let length = _olm_enc_output(
+            PICKLE_KEY.as_ptr(),
+            PICKLE_KEY.len(),
+            pickle.as_mut_ptr(),
+            raw.len(),
+        );
+        pickle.truncate(length);
+    }
+
+    pickle
+}
+
+/// Bob's side of a conversation with Alice, as in the loopback test of
+/// `olm/tests/test_olm.cpp`.
+pub struct Conversation {
+    /// Bob's session, pickled with [`PICKLE_KEY`] after decrypting Alice's
+    /// first message.
+    pub session_pickle: Vec<u8>,
+    /// Alice's pre-key message which started the session.
+    pub pre_key_message: Vec<u8>,
+    /// Alice's normal message after Bob's reply, which Bob hasn't decrypted.
+    pub message: Vec<u8>,
+}
+
+/// The conversation the session targets decrypt with, built once.
+pub fn conversation() -> &'static Conversation {
+    static CONVERSATION: OnceLock<Conversation> = OnceLock::new();
+    CONVERSATION.get_or_init(Conversation::new)
+}
+
+impl Conversation {
+    fn new() -> Self {
+        let mut random = MockRandom::new(b'A', 0);
+        let mut alice_account_memory = alloc(unsafe { olm_account_size() });
+        let alice_account = create_account(&mut alice_account_memory, &mut random);
+        let mut bob_account_memory = alloc(unsafe { olm_account_size() });
+        let bob_account = create_account(&mut bob_account_memory, &mut random);
+        generate_one_time_keys(bob_account, 1, &mut random);
+
+        let mut alice_session_memory = alloc(unsafe { olm_session_size() });
+        let (alice_session, result) = create_outbound_session(
+            &mut alice_session_memory,
+            alice_account,
+            key_at(&identity_keys(bob_account), 15),
+            key_at(&one_time_keys(bob_account), 25),
+            &mut random,
+        );
+        assert_ne!(result, unsafe { olm_error() });
+
+        let (message_type, pre_key_message) = encrypt(alice_session, b"Hello, Bob", &mut random);
+        assert_eq!(message_type, OLM_MESSAGE_TYPE_PRE_KEY);
+
+        let mut bob_session_memory = alloc(unsafe { olm_session_size() });
+        let (bob_session, result) =
+            create_inbound_session(&mut bob_session_memory, bob_account, &pre_key_message);
+        assert_ne!(result, unsafe { olm_error() });
+        try_decrypt(bob_session, OLM_MESSAGE_TYPE_PRE_KEY, &pre_key_message).unwrap();
+
+        let (message_type, reply) = encrypt(bob_session, b"Hello, Alice", &mut random);
+        assert_eq!(message_type, OLM_MESSAGE_TYPE_MESSAGE);
+        try_decrypt(alice_session, OLM_MESSAGE_TYPE_MESSAGE, &reply).unwrap();
+
+        let (message_type, message) = encrypt(alice_session, b"Bye, Bob", &mut random);
+        assert_eq!(message_type, OLM_MESSAGE_TYPE_MESSAGE);
+
+        let mut session_pickle = vec![0; unsafe { olm_pickle_session_length(bob_session) }];
+        let result = unsafe {
+            olm_pickle_session(
+                bob_session,
+                PICKLE_KEY.as_ptr() as *const c_void,
+                PICKLE_KEY.len(),
+                session_pickle.as_mut_ptr() as *mut c_void,
+                session_pickle.len(),
+            )
+        };
+        assert_ne!(result, unsafe { olm_error() });
+
+        unsafe {
+            olm_clear_session(alice_session);
+            olm_clear_session(bob_session);
+            olm_clear_account(alice_account);
+            olm_clear_account(bob_account);
+        }
+
+        Conversation {
+            session_pickle,
+            pre_key_message,
+            message,
+        }
+    }
+
+    /// Unpickles a fresh copy of Bob's session into `memory`.
+    pub fn session(&self, memory: &mut [u64]) -> *mut OlmSession {
+        let session = unsafe { olm_session(self::memory(memory)) };
+        let mut pickle = self.session_pickle.clone();
+        let result = unsafe {
+            olm_unpickle_session(
+                session,
+                PICKLE_KEY.as_ptr() as *const c_void,
+                PICKLE_KEY.len(),
+                pickle.as_mut_ptr() as *mut c_void,
+                pickle.len(),
+            )
+        };
+        assert_ne!(result, unsafe { olm_error() });
+
+        session
+    }
+}
+
+/// Decrypts `message` with `session`, returning `None` if `libolm` rejects
+/// it.
+///
+/// # Safety
+///
+/// `session` has to point to an initialised session.
+pub unsafe fn decrypt(
+    session: *mut OlmSession,
+    message_type: usize,
+    message: &[u8],
+) -> Option<Vec<u8>> {
+    try_decrypt(session, message_type, message).ok()
+}
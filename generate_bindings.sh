@@ -6,7 +6,18 @@
 # and
 # https://rust-lang.github.io/rust-bindgen/command-line-usage.html
 # first.
-bindgen --size_t-is-usize wrapper.h -o bindings.rs -- -I./olm/include
+#
+# Only libolm's own items are kept, so that the bindings don't depend on the
+# libc of the machine they were generated on. The C types come from `core`, so
+# that the bindings work without `std`. The C library items earlier bindings
+# had stay in src/glibc.rs, deprecated.
+bindgen --size_t-is-usize --use-core --ctypes-prefix '::core::ffi' \
+    --allowlist-function '_?olm_.*' --allowlist-type 'Olm.*' --allowlist-var 'OLM_.*' \
+    wrapper.h -o bindings.rs -- -I./olm/include
+
+# With the `prefix-symbols` feature, build.rs renames the symbols of libolm.
+sed -i -E 's/^( *)pub fn (\w+)/\1#[cfg_attr(feature = "prefix-symbols", link_name = "olmsys_\2")]\n\1pub fn \2/' \
+    bindings.rs
 
 # Prepend headers and write everything into the final file.
 cat <( awk '{print "// "$0}' copyright_header.txt) \
@@ -18,3 +29,17 @@ cat <( awk '{print "// "$0}' copyright_header.txt) \
     > src/lib.rs
 
 rustfmt src/lib.rs
+
+# Generate the function table used for loading libolm at runtime with the
+# `runtime-dlopen` feature. The types are shared with `src/lib.rs`.
+bindgen --size_t-is-usize --use-core --ctypes-prefix '::core::ffi' --dynamic-loading OlmLibrary \
+    --allowlist-function '_?olm_.*' --blocklist-type '.*' --blocklist-var '.*' \
+    --raw-line 'use crate::*;' \
+    wrapper.h -o dynamic.rs -- -I./olm/include
+
+cat <( awk '{print "// "$0}' copyright_header.txt) \
+    <(printf '\n') \
+    dynamic.rs \
+    > src/dynamic.rs
+
+rustfmt src/dynamic.rs
@@ -0,0 +1,37 @@
+/* Copyright 2020 Johannes Hayeß
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+/* Minimal <stdio.h> for building libolm without a libc.
+ *
+ * Only snprintf() is needed, for olm_session_describe(). The implementation in
+ * shim/libc.c understands just the conversions libolm uses.
+ */
+
+#ifndef OLM_SYS_SHIM_STDIO_H
+#define OLM_SYS_SHIM_STDIO_H
+
+#include <stddef.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+int snprintf(char *buffer, size_t size, const char *format, ...);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
@@ -0,0 +1,38 @@
+/* Copyright 2020 Johannes Hayeß
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+/* Minimal <stdlib.h> for building libolm without a libc.
+ *
+ * malloc() and free() are only used by the AES-CCM functions, which libolm
+ * never calls. They are provided by olm-sys on top of Rust's allocator.
+ */
+
+#ifndef OLM_SYS_SHIM_STDLIB_H
+#define OLM_SYS_SHIM_STDLIB_H
+
+#include <stddef.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+void *malloc(size_t size);
+void free(void *ptr);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
@@ -0,0 +1,123 @@
+/* Copyright 2020 Johannes Hayeß
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+/* The few libc functions libolm needs when it is built for a wasm target
+ * without a libc (wasm32-unknown-unknown).
+ *
+ * The memory functions are weak, as Rust's compiler-builtins usually provides
+ * them already.
+ */
+
+#include <stdarg.h>
+#include <stdio.h>
+#include <string.h>
+
+__attribute__((weak)) void *memcpy(void *dest, const void *src, size_t n) {
+    unsigned char *d = dest;
+    const unsigned char *s = src;
+    while (n--) {
+        *d++ = *s++;
+    }
+    return dest;
+}
+
+__attribute__((weak)) void *memmove(void *dest, const void *src, size_t n) {
+    unsigned char *d = dest;
+    const unsigned char *s = src;
+    if (d < s) {
+        while (n--) {
+            *d++ = *s++;
+        }
+    } else {
+        while (n--) {
+            d[n] = s[n];
+        }
+    }
+    return dest;
+}
+
+__attribute__((weak)) void *memset(void *dest, int c, size_t n) {
+    unsigned char *d = dest;
+    while (n--) {
+        *d++ = (unsigned char)c;
+    }
+    return dest;
+}
+
+__attribute__((weak)) int memcmp(const void *lhs, const void *rhs, size_t n) {
+    const unsigned char *l = lhs;
+    const unsigned char *r = rhs;
+    for (; n; n--, l++, r++) {
+        if (*l != *r) {
+            return *l - *r;
+        }
+    }
+    return 0;
+}
+
+static void put(char *buffer, size_t size, size_t *written, char c) {
+    if (*written + 1 < size) {
+        buffer[*written] = c;
+    }
+    (*written)++;
+}
+
+/* Supports only the conversions used by libolm: %d, %s and %%. */
+int snprintf(char *buffer, size_t size, const char *format, ...) {
+    va_list args;
+    size_t written = 0;
+
+    va_start(args, format);
+    for (; *format; format++) {
+        if (*format != '%') {
+            put(buffer, size, &written, *format);
+            continue;
+        }
+
+        format++;
+        if (*format == 'd') {
+            int value = va_arg(args, int);
+            unsigned int magnitude = value < 0 ? 0u - (unsigned int)value : (unsigned int)value;
+            char digits[10];
+            int count = 0;
+
+            if (value < 0) {
+                put(buffer, size, &written, '-');
+            }
+            do {
+                digits[count++] = (char)('0' + magnitude % 10);
+                magnitude /= 10;
+            } while (magnitude);
+            while (count) {
+                put(buffer, size, &written, digits[--count]);
+            }
+        } else if (*format == 's') {
+            const char *string = va_arg(args, const char *);
+            while (*string) {
+                put(buffer, size, &written, *string++);
+            }
+        } else if (*format == '%') {
+            put(buffer, size, &written, '%');
+        } else {
+            break;
+        }
+    }
+    va_end(args);
+
+    if (size) {
+        buffer[written < size ? written : size - 1] = '\0';
+    }
+    return (int)written;
+}
@@ -0,0 +1,79 @@
+/* Copyright 2020 Johannes Hayeß
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+/* C entry points to libolm's Olm message decoders, which it only has as C++
+ * functions with compiler specific names. src/message.rs binds them when
+ * libolm is linked statically, as shared builds of libolm hide the decoders.
+ *
+ * The readers mirror olm::MessageReader and olm::PreKeyMessageReader: the
+ * fields found point into the input, missing ones are NULL.
+ */
+
+#include "olm/message.hh"
+
+extern "C" {
+
+struct olm_sys_message_reader {
+    std::uint8_t version;
+    std::uint8_t has_counter;
+    std::uint32_t counter;
+    std::uint8_t const * ratchet_key; std::size_t ratchet_key_length;
+    std::uint8_t const * ciphertext; std::size_t ciphertext_length;
+};
+
+struct olm_sys_pre_key_message_reader {
+    std::uint8_t version;
+    std::uint8_t const * identity_key; std::size_t identity_key_length;
+    std::uint8_t const * base_key; std::size_t base_key_length;
+    std::uint8_t const * one_time_key; std::size_t one_time_key_length;
+    std::uint8_t const * message; std::size_t message_length;
+};
+
+void olm_sys_decode_message(
+    olm_sys_message_reader * reader,
+    std::uint8_t const * input, std::size_t input_length,
+    std::size_t mac_length
+) {
+    olm::MessageReader decoded;
+    olm::decode_message(decoded, input, input_length, mac_length);
+
+    reader->version = decoded.version;
+    reader->has_counter = decoded.has_counter;
+    reader->counter = decoded.counter;
+    reader->ratchet_key = decoded.ratchet_key;
+    reader->ratchet_key_length = decoded.ratchet_key_length;
+    reader->ciphertext = decoded.ciphertext;
+    reader->ciphertext_length = decoded.ciphertext_length;
+}
+
+void olm_sys_decode_pre_key_message(
+    olm_sys_pre_key_message_reader * reader,
+    std::uint8_t const * input, std::size_t input_length
+) {
+    olm::PreKeyMessageReader decoded;
+    olm::decode_one_time_key_message(decoded, input, input_length);
+
+    reader->version = decoded.version;
+    reader->identity_key = decoded.identity_key;
+    reader->identity_key_length = decoded.identity_key_length;
+    reader->base_key = decoded.base_key;
+    reader->base_key_length = decoded.base_key_length;
+    reader->one_time_key = decoded.one_time_key;
+    reader->one_time_key_length = decoded.one_time_key_length;
+    reader->message = decoded.message;
+    reader->message_length = decoded.message_length;
+}
+
+}
@@ -0,0 +1,323 @@
+// Copyright 2020 Johannes Hayeß
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A safe wrapper around `OlmAccount`.
+
+use alloc::boxed::Box;
+#[cfg(feature = "matrix")]
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+
+use core::ffi::c_void;
+
+use crate::backend::olm;
+use crate::error::OlmError;
+use crate::memory::{into_string, object_memory};
+use crate::random::{random_bytes, RandomSource};
+#[cfg(feature = "matrix")]
+use crate::signing::sign_json;
+use crate::OlmAccount;
+
+#[cfg(feature = "matrix")]
+use serde_json::{json, Map, Value};
+use zeroize::Zeroizing;
+
+/// The encryption algorithms a device announces in its device keys.
+#[cfg(feature = "matrix")]
+pub const DEVICE_ALGORITHMS: [&str; 2] = ["m.olm.v1.curve25519-aes-sha2", "m.megolm.v1.aes-sha2"];
+
+/// The identity keys and one-time keys of a device.
+///
+/// The account is wiped when it's dropped.
+pub struct Account {
+    account: *mut OlmAccount,
+    // Backs `account`, so it has to live as long as the account does
+    _memory: Box<[u64]>,
+}
+
+// `libolm` keeps no global state, so an account can move between threads.
+unsafe impl Send for Account {}
+
+impl Account {
+    fn allocate() -> Self {
+        let mut memory = object_memory(unsafe { olm!(olm_account_size()) });
+        let account = unsafe { olm!(olm_account(memory.as_mut_ptr() as *mut c_void)) };
+
+        Account {
+            account,
+            _memory: memory,
+        }
+    }
+
+    /// Creates an account with new identity keys.
+    pub fn new(random: &mut impl RandomSource) -> Self {
+        let account = Account::allocate();
+        let mut random = random_bytes(random, unsafe {
+            olm!(olm_create_account_random_length(account.account))
+        });
+        let result = unsafe {
+            olm!(olm_create_account(
+                account.account,
+                random.as_mut_ptr() as *mut c_void,
+                random.len(),
+            ))
+        };
+        account.check(result).expect("libolm got enough randomness");
+
+        account
+    }
+
+    /// Restores an account from a pickle made by [`Account::pickle`].
+    pub fn unpickle(key: &[u8], pickle: &str) -> Result<Self, OlmError> {
+        let account = Account::allocate();
+        // Unpickling decrypts in place, leaving the private keys in the buffer
+        let mut pickle = Zeroizing::new(pickle.as_bytes().to_vec());
+        let result = unsafe {
+            olm!(olm_unpickle_account(
+                account.account,
+                key.as_ptr() as *const c_void,
+                key.len(),
+                pickle.as_mut_ptr() as *mut c_void,
+                pickle.len(),
+            ))
+        };
+        account.check(result)?;
+
+        Ok(account)
+    }
+
+    /// Encrypts the account with `key` into a base64 pickle.
+    pub fn pickle(&self, key: &[u8]) -> String {
+        let mut pickle = vec![0; unsafe { olm!(olm_pickle_account_length(self.account)) }];
+        let result = unsafe {
+            olm!(olm_pickle_account(
+                self.account,
+                key.as_ptr() as *const c_void,
+                key.len(),
+                pickle.as_mut_ptr() as *mut c_void,
+                pickle.len(),
+            ))
+        };
+        self.check(result).expect("libolm sized the pickle");
+
+        into_string(pickle)
+    }
+
+    /// The public identity keys as JSON, e.g.
+    /// `{"curve25519":"…","ed25519":"…"}`.
+    pub fn identity_keys(&self) -> String {
+        let mut keys = vec![0; unsafe { olm!(olm_account_identity_keys_length(self.account)) }];
+        let result = unsafe {
+            olm!(olm_account_identity_keys(
+                self.account,
+                keys.as_mut_ptr() as *mut c_void,
+                keys.len(),
+            ))
+        };
+        self.check(result).expect("libolm sized the identity keys");
+
+        into_string(keys)
+    }
+
+    /// Signs `message` with the Ed25519 identity key, returning the base64
+    /// signature.
+    pub fn sign(&self, message: &[u8]) -> String {
+        let mut signature = vec![0; unsafe { olm!(olm_account_signature_length(self.account)) }];
+        let result = unsafe {
+            olm!(olm_account_sign(
+                self.account,
+                message.as_ptr() as *const c_void,
+                message.len(),
+                signature.as_mut_ptr() as *mut c_void,
+                signature.len(),
+            ))
+        };
+        self.check(result).expect("libolm sized the signature");
+
+        into_string(signature)
+    }
+
+    /// How many one-time keys the account can hold. Generating more discards
+    /// the oldest ones.
+    pub fn max_number_of_one_time_keys(&self) -> usize {
+        unsafe { olm!(olm_account_max_number_of_one_time_keys(self.account)) }
+    }
+
+    /// The unpublished one-time keys as JSON, e.g.
+    /// `{"curve25519":{"AAAAAQ":"…"}}`.
+    pub fn one_time_keys(&self) -> String {
+        let mut keys = vec![0; unsafe { olm!(olm_account_one_time_keys_length(self.account)) }];
+        let result = unsafe {
+            olm!(olm_account_one_time_keys(
+                self.account,
+                keys.as_mut_ptr() as *mut c_void,
+                keys.len(),
+            ))
+        };
+        self.check(result).expect("libolm sized the one-time keys");
+
+        into_string(keys)
+    }
+
+    /// Generates `count` new one-time keys.
+    pub fn generate_one_time_keys(&mut self, count: usize, random: &mut impl RandomSource) {
+        let mut random = random_bytes(random, unsafe {
+            olm!(olm_account_generate_one_time_keys_random_length(
+                self.account,
+                count
+            ))
+        });
+        let result = unsafe {
+            olm!(olm_account_generate_one_time_keys(
+                self.account,
+                count,
+                random.as_mut_ptr() as *mut c_void,
+                random.len(),
+            ))
+        };
+        self.check(result).expect("libolm got enough randomness");
+    }
+
+    /// Marks the current one-time keys and fallback key as published, so
+    /// they're no longer returned by [`Account::one_time_keys`] and
+    /// [`Account::unpublished_fallback_key`].
+    pub fn mark_keys_as_published(&mut self) {
+        unsafe { olm!(olm_account_mark_keys_as_published(self.account)) };
+    }
+
+    /// Generates a new fallback key, keeping the previous one around until
+    /// [`Account::forget_old_fallback_key`] is called.
+    pub fn generate_fallback_key(&mut self, random: &mut impl RandomSource) {
+        let mut random = random_bytes(random, unsafe {
+            olm!(olm_account_generate_fallback_key_random_length(
+                self.account
+            ))
+        });
+        let result = unsafe {
+            olm!(olm_account_generate_fallback_key(
+                self.account,
+                random.as_mut_ptr() as *mut c_void,
+                random.len(),
+            ))
+        };
+        self.check(result).expect("libolm got enough randomness");
+    }
+
+    /// The fallback key as JSON if it hasn't been published yet, like
+    /// [`Account::one_time_keys`].
+    pub fn unpublished_fallback_key(&self) -> String {
+        let length = unsafe { olm!(olm_account_unpublished_fallback_key_length(self.account)) };
+        let mut key = vec![0; length];
+        let result = unsafe {
+            olm!(olm_account_unpublished_fallback_key(
+                self.account,
+                key.as_mut_ptr() as *mut c_void,
+                key.len(),
+            ))
+        };
+        self.check(result).expect("libolm sized the fallback key");
+
+        into_string(key)
+    }
+
+    /// Forgets the previous fallback key, once no more sessions are expected
+    /// to be created with it.
+    pub fn forget_old_fallback_key(&mut self) {
+        unsafe { olm!(olm_account_forget_old_fallback_key(self.account)) };
+    }
+
+    /// The underlying `OlmAccount`, for use with the raw bindings.
+    pub fn as_ptr(&self) -> *mut OlmAccount {
+        self.account
+    }
+
+    fn check(&self, result: usize) -> Result<usize, OlmError> {
+        if result == unsafe { olm!(olm_error()) } {
+            let code = unsafe { olm!(olm_account_last_error_code(self.account)) };
+            Err(OlmError::new(code))
+        } else {
+            Ok(result)
+        }
+    }
+}
+
+// The JSON bodies of `/keys/upload`
+#[cfg(feature = "matrix")]
+impl Account {
+    /// The `device_keys` of the device `device_id` of `user_id`, signed by
+    /// the account.
+    pub fn device_keys(&self, user_id: &str, device_id: &str) -> Value {
+        let identity_keys = parse_keys(&self.identity_keys());
+        let mut device_keys = json!({
+            "user_id": user_id,
+            "device_id": device_id,
+            "algorithms": DEVICE_ALGORITHMS,
+            "keys": {
+                format!("curve25519:{device_id}"): identity_keys["curve25519"],
+                format!("ed25519:{device_id}"): identity_keys["ed25519"],
+            },
+        });
+        sign_json(self, user_id, device_id, &mut device_keys).expect("device keys are an object");
+
+        device_keys
+    }
+
+    /// The unpublished one-time keys and fallback key as the
+    /// `one_time_keys` and `fallback_keys` of `/keys/upload`, each a
+    /// `signed_curve25519` key signed by the account.
+    ///
+    /// Nothing is marked as published: call
+    /// [`Account::mark_keys_as_published`] once the server accepted the
+    /// upload, until then the same keys are returned again so a failed
+    /// upload can be retried.
+    pub fn signed_one_time_keys(&self, user_id: &str, device_id: &str) -> Value {
+        let sign = |keys: &str, fallback: bool| -> Map<String, Value> {
+            let keys = parse_keys(keys);
+            let keys = keys["curve25519"]
+                .as_object()
+                .expect("libolm lists curve25519 keys");
+
+            keys.iter()
+                .map(|(key_id, key)| {
+                    let mut signed = json!({ "key": key });
+                    if fallback {
+                        signed["fallback"] = Value::Bool(true);
+                    }
+                    sign_json(self, user_id, device_id, &mut signed)
+                        .expect("signed keys are objects");
+
+                    (format!("signed_curve25519:{key_id}"), signed)
+                })
+                .collect()
+        };
+
+        json!({
+            "one_time_keys": sign(&self.one_time_keys(), false),
+            "fallback_keys": sign(&self.unpublished_fallback_key(), true),
+        })
+    }
+}
+
+/// Parses the JSON of `olm_account_identity_keys(…)` and the like.
+#[cfg(feature = "matrix")]
+fn parse_keys(keys: &str) -> Value {
+    serde_json::from_str(keys).expect("libolm wrote valid JSON")
+}
+
+impl Drop for Account {
+    fn drop(&mut self) {
+        unsafe { olm!(olm_clear_account(self.account)) };
+    }
+}
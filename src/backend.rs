@@ -0,0 +1,90 @@
+// Copyright 2020 Johannes Hayeß
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Where the safe wrappers of this crate find `libolm`'s functions.
+//!
+//! By default they call the functions `libolm` is linked with. With the
+//! `runtime-dlopen` feature nothing is linked; instead an [`OlmLibrary`] has
+//! to be loaded and [`install`]ed before any safe wrapper is used:
+//!
+//! ```no_run
+//! # #[cfg(feature = "runtime-dlopen")]
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! use olm_sys::backend::{self, OlmLibrary};
+//!
+//! let library = unsafe { OlmLibrary::new("libolm.so.3")? };
+//! if backend::install(library).is_err() {
+//!     eprintln!("a libolm has already been installed");
+//! }
+//! olm_sys::version::check_compatible()?;
+//! # Ok(())
+//! # }
+//! # #[cfg(not(feature = "runtime-dlopen"))]
+//! # fn main() {}
+//! ```
+//!
+//! Functions missing from the loaded library don't fail the loading, but
+//! panic when called. [`check_compatible`](crate::version::check_compatible)
+//! reports them.
+
+#[cfg(feature = "runtime-dlopen")]
+pub use crate::dynamic::OlmLibrary;
+
+#[cfg(feature = "runtime-dlopen")]
+static LIBRARY: std::sync::OnceLock<OlmLibrary> = std::sync::OnceLock::new();
+
+/// Makes `library` the backend of all safe wrappers for the rest of the
+/// process' lifetime.
+///
+/// Only one library can be installed, later calls hand their library back.
+#[cfg(feature = "runtime-dlopen")]
+#[allow(clippy::result_large_err)]
+pub fn install(library: OlmLibrary) -> Result<&'static OlmLibrary, OlmLibrary> {
+    let mut library = Some(library);
+    let installed = LIBRARY.get_or_init(|| library.take().unwrap());
+
+    match library {
+        None => Ok(installed),
+        Some(library) => Err(library),
+    }
+}
+
+/// The installed library, if any.
+#[cfg(feature = "runtime-dlopen")]
+pub fn installed() -> Option<&'static OlmLibrary> {
+    LIBRARY.get()
+}
+
+#[cfg(feature = "runtime-dlopen")]
+pub(crate) fn library() -> &'static OlmLibrary {
+    installed().expect("no libolm has been installed, see `olm_sys::backend::install`")
+}
+
+/// Calls a `libolm` function through the active backend.
+#[cfg(not(feature = "runtime-dlopen"))]
+macro_rules! olm {
+    ($function:ident($($argument:expr),* $(,)?)) => {
+        crate::$function($($argument),*)
+    };
+}
+
+/// Calls a `libolm` function through the active backend.
+#[cfg(feature = "runtime-dlopen")]
+macro_rules! olm {
+    ($function:ident($($argument:expr),* $(,)?)) => {
+        crate::backend::library().$function($($argument),*)
+    };
+}
+
+pub(crate) use olm;
@@ -0,0 +1,277 @@
+// Copyright 2020 Johannes Hayeß
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Server-side key backup with the `m.megolm_backup.v1.curve25519-aes-sha2`
+//! algorithm.
+//!
+//! Room keys are encrypted with [`PkEncryption`] for the public key of the
+//! backup, so any device can add keys to it. Only the holder of the
+//! [`RecoveryKey`], the private key, can decrypt them again:
+//!
+//! ```no_run
+//! use olm_sys::backup::{BackupEncryption, RecoveryKey, SessionData};
+//! # use olm_sys::group_session::InboundGroupSession;
+//! # use olm_sys::random::RandomSource;
+//! # struct Random;
+//! # impl RandomSource for Random {
+//! #     fn fill_bytes(&mut self, buffer: &mut [u8]) {}
+//! # }
+//! # fn main() -> Result<(), olm_sys::backup::BackupError> {
+//! # let session = InboundGroupSession::new("").unwrap();
+//!
+//! let recovery_key = RecoveryKey::new(&mut Random);
+//! println!("Write this down: {}", recovery_key.to_base58());
+//!
+//! let mut backup = BackupEncryption::new(recovery_key.public_key())?;
+//! let session_data = SessionData::new("<sender curve25519>", &session);
+//! let encrypted = backup.encrypt(&session_data, &mut Random);
+//!
+//! let mut recovery_key = RecoveryKey::from_base58("EsSz ykH7 LCZx …")?;
+//! assert_eq!(recovery_key.decrypt(&encrypted)?, session_data);
+//! # Ok(())
+//! # }
+//! ```
+
+use alloc::borrow::ToOwned;
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use core::fmt;
+
+use serde_json::{json, Map, Value};
+
+use crate::base58;
+use crate::error::OlmError;
+use crate::group_session::InboundGroupSession;
+use crate::key_export::{self, MEGOLM_ALGORITHM};
+use crate::pk::{PkDecryption, PkEncryption, PkMessage};
+use crate::random::RandomSource;
+
+/// The backup algorithm of this module, as it appears in the backup's
+/// `auth_data`.
+pub const BACKUP_ALGORITHM: &str = "m.megolm_backup.v1.curve25519-aes-sha2";
+
+/// The plaintext of a backed up room key.
+///
+/// The room and session ID aren't part of it, the backup stores the key
+/// under them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionData {
+    /// The Curve25519 identity key of the device that created the session.
+    pub sender_key: String,
+    /// The session exported at its first known index, see
+    /// [`InboundGroupSession::export_at`].
+    pub session_key: String,
+    /// Keys the sending device claims to own, usually just its `ed25519` key.
+    pub sender_claimed_keys: BTreeMap<String, String>,
+    /// The Curve25519 keys of the devices the session was forwarded through.
+    pub forwarding_curve25519_key_chain: Vec<String>,
+}
+
+impl SessionData {
+    /// Exports `session` from its first known index, without claimed keys or
+    /// forwarding chain.
+    pub fn new(sender_key: &str, session: &InboundGroupSession) -> Self {
+        let session_key = session
+            .export_at(session.first_known_index())
+            .expect("the first known index can be exported");
+
+        SessionData {
+            sender_key: sender_key.to_owned(),
+            session_key,
+            sender_claimed_keys: BTreeMap::new(),
+            forwarding_curve25519_key_chain: Vec::new(),
+        }
+    }
+
+    /// Imports the session key into a new session.
+    pub fn session(&self) -> Result<InboundGroupSession, OlmError> {
+        InboundGroupSession::import(&self.session_key)
+    }
+
+    fn to_json(&self) -> Value {
+        json!({
+            "algorithm": MEGOLM_ALGORITHM,
+            "forwarding_curve25519_key_chain": self.forwarding_curve25519_key_chain,
+            "sender_claimed_keys": self.sender_claimed_keys,
+            "sender_key": self.sender_key,
+            "session_key": self.session_key,
+        })
+    }
+
+    fn from_json(data: &Map<String, Value>) -> Option<Self> {
+        if data.get("algorithm")?.as_str()? != MEGOLM_ALGORITHM {
+            return None;
+        }
+
+        let (sender_claimed_keys, forwarding_curve25519_key_chain) =
+            key_export::parse_provenance(data)?;
+
+        Some(SessionData {
+            sender_key: data.get("sender_key")?.as_str()?.to_owned(),
+            session_key: data.get("session_key")?.as_str()?.to_owned(),
+            sender_claimed_keys,
+            forwarding_curve25519_key_chain,
+        })
+    }
+}
+
+/// The `session_data` of a backed up room key, `{"ciphertext", "mac",
+/// "ephemeral"}`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EncryptedSessionData(pub PkMessage);
+
+impl EncryptedSessionData {
+    /// The JSON object stored in the backup.
+    pub fn to_json(&self) -> Value {
+        json!({
+            "ciphertext": self.0.ciphertext,
+            "mac": self.0.mac,
+            "ephemeral": self.0.ephemeral_key,
+        })
+    }
+
+    /// Reads the JSON object stored in the backup.
+    pub fn from_json(session_data: &Value) -> Result<Self, BackupError> {
+        let field = |name: &str| {
+            session_data
+                .get(name)
+                .and_then(Value::as_str)
+                .map(str::to_owned)
+                .ok_or(BackupError::InvalidJson)
+        };
+
+        Ok(EncryptedSessionData(PkMessage {
+            ciphertext: field("ciphertext")?,
+            mac: field("mac")?,
+            ephemeral_key: field("ephemeral")?,
+        }))
+    }
+}
+
+/// Why a backup operation failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BackupError {
+    /// The recovery key isn't base58, has the wrong length, prefix or parity.
+    InvalidRecoveryKey,
+    /// `libolm` couldn't use the public key or decrypt the session data,
+    /// e.g. because it was encrypted for another backup.
+    Olm(OlmError),
+    /// The session data isn't a Megolm room key.
+    InvalidJson,
+}
+
+impl From<OlmError> for BackupError {
+    fn from(error: OlmError) -> Self {
+        BackupError::Olm(error)
+    }
+}
+
+impl fmt::Display for BackupError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BackupError::InvalidRecoveryKey => write!(f, "invalid recovery key"),
+            BackupError::Olm(error) => error.fmt(f),
+            BackupError::InvalidJson => write!(f, "invalid backed up room key"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for BackupError {}
+
+/// Encrypts room keys for a backup.
+pub struct BackupEncryption {
+    encryption: PkEncryption,
+}
+
+impl BackupEncryption {
+    /// Encrypts for the backup with the base64 Curve25519 `public_key` of its
+    /// `auth_data`.
+    pub fn new(public_key: &str) -> Result<Self, BackupError> {
+        Ok(BackupEncryption {
+            encryption: PkEncryption::new(public_key)?,
+        })
+    }
+
+    /// Encrypts `session_data` with a new ephemeral key.
+    pub fn encrypt(
+        &mut self,
+        session_data: &SessionData,
+        random: &mut impl RandomSource,
+    ) -> EncryptedSessionData {
+        let plaintext =
+            serde_json::to_vec(&session_data.to_json()).expect("session data serializes to JSON");
+        EncryptedSessionData(self.encryption.encrypt(&plaintext, random))
+    }
+}
+
+/// The private key of a backup, which decrypts the backed up room keys.
+///
+/// Users write it down in its base58 form, e.g.
+/// `EsSz ykH7 LCZx 7Cae cmKD wcmY JRXi Ybtu 8iQ3 t8Ez nRwK pUY1`.
+pub struct RecoveryKey {
+    decryption: PkDecryption,
+}
+
+impl RecoveryKey {
+    /// Creates the key of a new backup.
+    pub fn new(random: &mut impl RandomSource) -> Self {
+        RecoveryKey {
+            decryption: PkDecryption::new(random),
+        }
+    }
+
+    /// Restores the key from its unencoded private key.
+    pub fn from_bytes(private_key: &[u8; PkDecryption::PRIVATE_KEY_LENGTH]) -> Self {
+        RecoveryKey {
+            decryption: PkDecryption::from_private_key(private_key)
+                .expect("the private key has the right length"),
+        }
+    }
+
+    /// Decodes a recovery key, ignoring whitespace.
+    pub fn from_base58(recovery_key: &str) -> Result<Self, BackupError> {
+        let private_key =
+            base58::decode_recovery_key(recovery_key).ok_or(BackupError::InvalidRecoveryKey)?;
+
+        Ok(RecoveryKey::from_bytes(&private_key))
+    }
+
+    /// Encodes the key for the user, in groups of four characters.
+    pub fn to_base58(&self) -> String {
+        let private_key = self.decryption.private_key();
+        base58::encode_recovery_key(private_key.as_slice().try_into().unwrap())
+    }
+
+    /// The base64 Curve25519 public key of the backup, for its `auth_data`.
+    pub fn public_key(&self) -> &str {
+        self.decryption.public_key()
+    }
+
+    /// Decrypts a backed up room key.
+    pub fn decrypt(
+        &mut self,
+        session_data: &EncryptedSessionData,
+    ) -> Result<SessionData, BackupError> {
+        let plaintext = self.decryption.decrypt(&session_data.0)?;
+        serde_json::from_slice::<Value>(&plaintext)
+            .ok()
+            .as_ref()
+            .and_then(Value::as_object)
+            .and_then(SessionData::from_json)
+            .ok_or(BackupError::InvalidJson)
+    }
+}
@@ -0,0 +1,156 @@
+// Copyright 2020 Johannes Hayeß
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The Bitcoin base58 encoding of the keys Matrix users write down.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use zeroize::Zeroizing;
+
+const ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// Precedes the key in a recovery key.
+const RECOVERY_KEY_PREFIX: [u8; 2] = [0x8b, 0x01];
+
+/// Encodes the 32 byte `key` as a recovery key: base58 of the prefix, the key
+/// and a parity byte, in groups of four characters.
+pub(crate) fn encode_recovery_key(key: &[u8; 32]) -> String {
+    let mut bytes = Zeroizing::new(Vec::with_capacity(
+        RECOVERY_KEY_PREFIX.len() + key.len() + 1,
+    ));
+    bytes.extend_from_slice(&RECOVERY_KEY_PREFIX);
+    bytes.extend_from_slice(key);
+    let parity = bytes.iter().fold(0, |parity, byte| parity ^ byte);
+    bytes.push(parity);
+
+    let encoded = Zeroizing::new(encode(&bytes));
+    let mut output = String::with_capacity(encoded.len() * 5 / 4);
+    for (i, character) in encoded.chars().enumerate() {
+        if i > 0 && i % 4 == 0 {
+            output.push(' ');
+        }
+        output.push(character);
+    }
+
+    output
+}
+
+/// Decodes a recovery key, ignoring whitespace. Returns `None` if it isn't
+/// base58, or has the wrong length, prefix or parity.
+pub(crate) fn decode_recovery_key(recovery_key: &str) -> Option<Zeroizing<[u8; 32]>> {
+    let bytes = decode(recovery_key)?;
+    let parity = bytes.iter().fold(0, |parity, byte| parity ^ byte);
+    if parity != 0 {
+        return None;
+    }
+
+    let key = bytes.strip_prefix(&RECOVERY_KEY_PREFIX)?;
+    key[..key.len().saturating_sub(1)]
+        .try_into()
+        .ok()
+        .map(Zeroizing::new)
+}
+
+/// Encodes `bytes` with the Bitcoin base58 alphabet.
+///
+/// The buffers are sized up front and wiped, as `bytes` is usually a key.
+pub(crate) fn encode(bytes: &[u8]) -> String {
+    // Little endian base58 digits
+    let mut digits: Zeroizing<Vec<u8>> =
+        Zeroizing::new(Vec::with_capacity(bytes.len() * 138 / 100 + 1));
+    for &byte in bytes {
+        let mut carry = u32::from(byte);
+        for digit in digits.iter_mut() {
+            carry += u32::from(*digit) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            digits.push((carry % 58) as u8);
+            carry /= 58;
+        }
+    }
+
+    let zeroes = bytes.iter().take_while(|&&byte| byte == 0).count();
+    let mut output = String::with_capacity(zeroes + digits.len());
+    output.extend(
+        core::iter::repeat_n(b'1', zeroes)
+            .chain(digits.iter().rev().map(|&digit| ALPHABET[digit as usize]))
+            .map(char::from),
+    );
+
+    output
+}
+
+/// Decodes base58 `input`, skipping whitespace. Returns `None` if it has
+/// characters outside of the alphabet.
+///
+/// Like [`encode`], it never reallocates and wipes what it decoded.
+pub(crate) fn decode(input: &str) -> Option<Zeroizing<Vec<u8>>> {
+    let mut characters = Zeroizing::new(Vec::with_capacity(input.len()));
+    characters.extend(input.bytes().filter(|c| !c.is_ascii_whitespace()));
+
+    // Little endian bytes, at most one per character
+    let mut bytes: Zeroizing<Vec<u8>> = Zeroizing::new(Vec::with_capacity(characters.len()));
+    for &character in characters.iter() {
+        let mut carry = ALPHABET.iter().position(|&c| c == character)? as u32;
+        for byte in bytes.iter_mut() {
+            carry += u32::from(*byte) * 58;
+            *byte = carry as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.push(carry as u8);
+            carry >>= 8;
+        }
+    }
+
+    let zeroes = characters.iter().take_while(|&&c| c == b'1').count();
+    bytes.extend(core::iter::repeat_n(0, zeroes));
+    bytes.reverse();
+
+    Some(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::vec;
+
+    #[test]
+    fn base58_round_trips() {
+        for bytes in [
+            vec![],
+            vec![0],
+            vec![0, 0, 1],
+            vec![0x8b, 0x01, 0xff, 0x00, 0x42],
+            (0..=255).collect(),
+        ] {
+            assert_eq!(*decode(&encode(&bytes)).unwrap(), bytes);
+        }
+    }
+
+    #[test]
+    fn base58_matches_bitcoin() {
+        assert_eq!(encode(b"Hello World!"), "2NEpo7TZRRrLZSi2U");
+        assert_eq!(encode(&[0, 0, 0x28, 0x7f, 0xb4, 0xcd]), "11233QC4");
+        assert_eq!(
+            *decode("1 1233 QC4").unwrap(),
+            [0, 0, 0x28, 0x7f, 0xb4, 0xcd]
+        );
+        assert_eq!(decode("0OIl"), None);
+    }
+}
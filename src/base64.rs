@@ -0,0 +1,100 @@
+// Copyright 2020 Johannes Hayeß
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The unpadded standard base64 `libolm` and Matrix use for keys, messages
+//! and signatures.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Encodes `bytes` without padding.
+pub(crate) fn encode(bytes: &[u8]) -> String {
+    let mut output = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let group = chunk.iter().enumerate().fold(0u32, |group, (i, &byte)| {
+            group | u32::from(byte) << (16 - 8 * i)
+        });
+        for i in 0..=chunk.len() {
+            output.push(ALPHABET[(group >> (18 - 6 * i)) as usize & 0x3f] as char);
+        }
+    }
+
+    output
+}
+
+/// Encodes `bytes` with padding, as the Matrix key formats which predate
+/// unpadded base64 do.
+#[cfg(feature = "matrix")]
+pub(crate) fn encode_padded(bytes: &[u8]) -> String {
+    let mut output = encode(bytes);
+    while !output.len().is_multiple_of(4) {
+        output.push('=');
+    }
+
+    output
+}
+
+/// Decodes `input`, with or without padding. Returns `None` if it isn't
+/// valid base64.
+pub(crate) fn decode(input: &str) -> Option<Vec<u8>> {
+    let input = input.trim_end_matches('=').as_bytes();
+    if input.len() % 4 == 1 {
+        return None;
+    }
+
+    let mut output = Vec::with_capacity(input.len() * 3 / 4);
+    for chunk in input.chunks(4) {
+        let mut group = 0u32;
+        for (i, &character) in chunk.iter().enumerate() {
+            let value = ALPHABET.iter().position(|&c| c == character)? as u32;
+            group |= value << (18 - 6 * i);
+        }
+        for i in 0..chunk.len() - 1 {
+            output.push((group >> (16 - 8 * i)) as u8);
+        }
+    }
+
+    Some(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_all_lengths() {
+        let bytes: Vec<u8> = (0..=255).collect();
+        for length in 0..bytes.len() {
+            let encoded = encode(&bytes[..length]);
+            assert_eq!(encoded.len(), (length * 4).div_ceil(3));
+            assert_eq!(decode(&encoded).unwrap(), &bytes[..length]);
+        }
+    }
+
+    #[test]
+    fn matches_libolm() {
+        assert_eq!(encode(b"Hello, World"), "SGVsbG8sIFdvcmxk");
+        assert_eq!(encode(b"This is a test"), "VGhpcyBpcyBhIHRlc3Q");
+        assert_eq!(decode("VGhpcyBpcyBhIHRlc3Q=").unwrap(), b"This is a test");
+    }
+
+    #[test]
+    fn rejects_invalid_input() {
+        assert_eq!(decode("A"), None);
+        assert_eq!(decode("AB*D"), None);
+        assert_eq!(decode("AB-_"), None);
+    }
+}
@@ -0,0 +1,385 @@
+// Copyright 2020 Johannes Hayeß
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Cross-signing, with a master key that signs the self-signing and
+//! user-signing keys.
+//!
+//! The self-signing key signs the user's own devices, the user-signing key
+//! signs the master keys of other users. A [`CrossSigningIdentity`] holds
+//! the seeds of all three, [`verify_device`] and [`verify_user`] check the
+//! chains of signatures they make:
+//!
+//! ```no_run
+//! use olm_sys::cross_signing::{verify_device, CrossSigningIdentity};
+//! # use olm_sys::random::RandomSource;
+//! # struct Random;
+//! # impl RandomSource for Random {
+//! #     fn fill_bytes(&mut self, buffer: &mut [u8]) {}
+//! # }
+//! # fn main() -> Result<(), olm_sys::cross_signing::CrossSigningError> {
+//! # let mut device_keys = serde_json::Value::Null;
+//!
+//! let identity = CrossSigningIdentity::new("@alice:example.org", &mut Random);
+//! let upload = identity.upload_keys();
+//!
+//! identity.sign_device(&mut device_keys)?;
+//! verify_device(&upload["master_key"], &upload["self_signing_key"], &device_keys)?;
+//! # Ok(())
+//! # }
+//! ```
+
+use alloc::borrow::ToOwned;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use core::fmt;
+
+use serde_json::{json, Value};
+use zeroize::Zeroizing;
+
+use crate::error::OlmError;
+use crate::pickle::Reader;
+use crate::pk::PkSigning;
+use crate::random::RandomSource;
+use crate::signing::{sign_json, verify_json, SignatureError};
+
+/// Version of [`CrossSigningIdentity::pickle`]'s format.
+const PICKLE_VERSION: u32 = 1;
+
+/// Length of a cross-signing key's seed.
+pub const SEED_LENGTH: usize = PkSigning::SEED_LENGTH;
+
+/// Why a cross-signing key couldn't be used or verified.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CrossSigningError {
+    /// A key isn't a cross-signing key with the expected usage and a single
+    /// Ed25519 key.
+    InvalidKey,
+    /// A key or device belongs to another user than the chain does.
+    WrongUser,
+    /// A signature of the chain is missing or doesn't match.
+    Signature(SignatureError),
+    /// `libolm` couldn't decrypt the pickle, e.g. `OLM_BAD_ACCOUNT_KEY` if
+    /// the key is wrong.
+    Olm(OlmError),
+}
+
+impl From<SignatureError> for CrossSigningError {
+    fn from(error: SignatureError) -> Self {
+        CrossSigningError::Signature(error)
+    }
+}
+
+impl From<OlmError> for CrossSigningError {
+    fn from(error: OlmError) -> Self {
+        CrossSigningError::Olm(error)
+    }
+}
+
+impl fmt::Display for CrossSigningError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CrossSigningError::InvalidKey => write!(f, "invalid cross-signing key"),
+            CrossSigningError::WrongUser => write!(f, "key of another user"),
+            CrossSigningError::Signature(error) => error.fmt(f),
+            CrossSigningError::Olm(error) => error.fmt(f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CrossSigningError {}
+
+/// A cross-signing key, with the seed it was derived from.
+struct SigningKey {
+    seed: Zeroizing<[u8; SEED_LENGTH]>,
+    signing: PkSigning,
+}
+
+impl SigningKey {
+    fn new(seed: &[u8; SEED_LENGTH]) -> Self {
+        SigningKey {
+            seed: Zeroizing::new(*seed),
+            signing: PkSigning::from_seed(seed).expect("the seed has the right length"),
+        }
+    }
+
+    fn public_key(&self) -> &str {
+        self.signing.public_key()
+    }
+
+    /// The key's JSON for `user_id`, signed by `signer` if given.
+    fn to_json(&self, user_id: &str, usage: &str, signer: Option<&SigningKey>) -> Value {
+        let public_key = self.public_key();
+        let mut key = json!({
+            "user_id": user_id,
+            "usage": [usage],
+            "keys": { format!("ed25519:{public_key}"): public_key },
+        });
+        if let Some(signer) = signer {
+            signer
+                .sign(user_id, &mut key)
+                .expect("the key is a JSON object");
+        }
+
+        key
+    }
+
+    fn sign(&self, user_id: &str, value: &mut Value) -> Result<(), SignatureError> {
+        sign_json(&self.signing, user_id, self.public_key(), value)
+    }
+}
+
+/// A user's master, self-signing and user-signing keys.
+///
+/// The seeds are wiped when the identity is dropped.
+pub struct CrossSigningIdentity {
+    user_id: String,
+    master: SigningKey,
+    self_signing: SigningKey,
+    user_signing: SigningKey,
+}
+
+impl CrossSigningIdentity {
+    /// Creates new keys for `user_id`.
+    pub fn new(user_id: &str, random: &mut impl RandomSource) -> Self {
+        let mut seeds = Zeroizing::new([[0; SEED_LENGTH]; 3]);
+        for seed in seeds.iter_mut() {
+            random.fill_bytes(seed);
+        }
+
+        CrossSigningIdentity::from_seeds(user_id, &seeds[0], &seeds[1], &seeds[2])
+    }
+
+    /// Restores the keys of `user_id` from their seeds, e.g. from the
+    /// `m.cross_signing.*` secrets of secret storage.
+    pub fn from_seeds(
+        user_id: &str,
+        master: &[u8; SEED_LENGTH],
+        self_signing: &[u8; SEED_LENGTH],
+        user_signing: &[u8; SEED_LENGTH],
+    ) -> Self {
+        CrossSigningIdentity {
+            user_id: user_id.to_owned(),
+            master: SigningKey::new(master),
+            self_signing: SigningKey::new(self_signing),
+            user_signing: SigningKey::new(user_signing),
+        }
+    }
+
+    /// The user the keys belong to.
+    pub fn user_id(&self) -> &str {
+        &self.user_id
+    }
+
+    /// The base64 public master key.
+    pub fn master_public_key(&self) -> &str {
+        self.master.public_key()
+    }
+
+    /// The base64 public self-signing key.
+    pub fn self_signing_public_key(&self) -> &str {
+        self.self_signing.public_key()
+    }
+
+    /// The base64 public user-signing key.
+    pub fn user_signing_public_key(&self) -> &str {
+        self.user_signing.public_key()
+    }
+
+    /// The seed of the master key.
+    pub fn master_seed(&self) -> &[u8; SEED_LENGTH] {
+        &self.master.seed
+    }
+
+    /// The seed of the self-signing key.
+    pub fn self_signing_seed(&self) -> &[u8; SEED_LENGTH] {
+        &self.self_signing.seed
+    }
+
+    /// The seed of the user-signing key.
+    pub fn user_signing_seed(&self) -> &[u8; SEED_LENGTH] {
+        &self.user_signing.seed
+    }
+
+    /// The `master_key` to upload. It isn't signed, devices sign it with
+    /// [`sign_json`] once they trust it.
+    pub fn master_key(&self) -> Value {
+        self.master.to_json(&self.user_id, "master", None)
+    }
+
+    /// The `self_signing_key` to upload, signed by the master key.
+    pub fn self_signing_key(&self) -> Value {
+        self.self_signing
+            .to_json(&self.user_id, "self_signing", Some(&self.master))
+    }
+
+    /// The `user_signing_key` to upload, signed by the master key.
+    pub fn user_signing_key(&self) -> Value {
+        self.user_signing
+            .to_json(&self.user_id, "user_signing", Some(&self.master))
+    }
+
+    /// The body of `/keys/device_signing/upload`, with all three keys.
+    pub fn upload_keys(&self) -> Value {
+        json!({
+            "master_key": self.master_key(),
+            "self_signing_key": self.self_signing_key(),
+            "user_signing_key": self.user_signing_key(),
+        })
+    }
+
+    /// Signs the device keys of one of the user's own devices with the
+    /// self-signing key.
+    pub fn sign_device(&self, device_keys: &mut Value) -> Result<(), CrossSigningError> {
+        if device_keys.get("user_id").and_then(Value::as_str) != Some(&self.user_id) {
+            return Err(CrossSigningError::WrongUser);
+        }
+        self.self_signing.sign(&self.user_id, device_keys)?;
+
+        Ok(())
+    }
+
+    /// Signs another user's master key with the user-signing key, once
+    /// they're verified.
+    pub fn sign_user(&self, master_key: &mut Value) -> Result<(), CrossSigningError> {
+        let (user_id, _) = parse_key(master_key, "master")?;
+        if user_id == self.user_id {
+            return Err(CrossSigningError::WrongUser);
+        }
+        self.user_signing.sign(&self.user_id, master_key)?;
+
+        Ok(())
+    }
+
+    /// Encrypts the user ID and the seeds with `key` into a base64 pickle,
+    /// like `libolm` pickles its objects.
+    pub fn pickle(&self, key: &[u8]) -> String {
+        // Sized up front, as growing the buffer would leave copies of the seeds
+        let mut raw = Zeroizing::new(Vec::with_capacity(
+            4 + 4 + self.user_id.len() + 3 * SEED_LENGTH,
+        ));
+        raw.extend_from_slice(&PICKLE_VERSION.to_be_bytes());
+        crate::pickle::write_string(&mut raw, &self.user_id);
+        for key in [&self.master, &self.self_signing, &self.user_signing] {
+            raw.extend_from_slice(key.seed.as_ref());
+        }
+
+        crate::pickle::encrypt(key, &raw)
+    }
+
+    /// Restores an identity from a pickle made by
+    /// [`CrossSigningIdentity::pickle`].
+    pub fn unpickle(key: &[u8], pickle: &str) -> Result<Self, CrossSigningError> {
+        let raw = crate::pickle::decrypt(key, pickle)?;
+        let mut reader = Reader::new(&raw);
+
+        reader.version(PICKLE_VERSION)?;
+        let user_id = reader.string()?;
+        let master = reader.array()?;
+        let self_signing = reader.array()?;
+        let user_signing = reader.array()?;
+        reader.finish()?;
+
+        Ok(CrossSigningIdentity::from_seeds(
+            user_id,
+            master,
+            self_signing,
+            user_signing,
+        ))
+    }
+}
+
+/// The user ID and public key of the cross-signing key `key` with `usage`.
+fn parse_key<'a>(key: &'a Value, usage: &str) -> Result<(&'a str, &'a str), CrossSigningError> {
+    let user_id = key.get("user_id").and_then(Value::as_str);
+    let has_usage = key
+        .get("usage")
+        .and_then(Value::as_array)
+        .is_some_and(|usages| usages.iter().any(|u| u.as_str() == Some(usage)));
+    let keys = key.get("keys").and_then(Value::as_object);
+
+    match (user_id, has_usage, keys) {
+        (Some(user_id), true, Some(keys)) if keys.len() == 1 => {
+            let (key_id, public_key) = keys.iter().next().unwrap();
+            match public_key.as_str() {
+                Some(public_key) if key_id.strip_prefix("ed25519:") == Some(public_key) => {
+                    Ok((user_id, public_key))
+                }
+                _ => Err(CrossSigningError::InvalidKey),
+            }
+        }
+        _ => Err(CrossSigningError::InvalidKey),
+    }
+}
+
+/// Checks that `key` with `usage` is signed by the master key, returning its
+/// public key.
+fn verify_subkey<'a>(
+    master_key: &Value,
+    key: &'a Value,
+    usage: &str,
+) -> Result<(&'a str, &'a str), CrossSigningError> {
+    let (user_id, master) = parse_key(master_key, "master")?;
+    let (key_user_id, public_key) = parse_key(key, usage)?;
+    if key_user_id != user_id {
+        return Err(CrossSigningError::WrongUser);
+    }
+    verify_json(master, user_id, master, key)?;
+
+    Ok((key_user_id, public_key))
+}
+
+/// Verifies that `device_keys` are signed by their own Ed25519 key and by
+/// `self_signing_key`, which in turn is signed by `master_key`.
+pub fn verify_device(
+    master_key: &Value,
+    self_signing_key: &Value,
+    device_keys: &Value,
+) -> Result<(), CrossSigningError> {
+    let (user_id, self_signing) = verify_subkey(master_key, self_signing_key, "self_signing")?;
+    if device_keys.get("user_id").and_then(Value::as_str) != Some(user_id) {
+        return Err(CrossSigningError::WrongUser);
+    }
+    let device_id = device_keys
+        .get("device_id")
+        .and_then(Value::as_str)
+        .ok_or(CrossSigningError::InvalidKey)?;
+    let device_key = device_keys
+        .get("keys")
+        .and_then(|keys| keys.get(format!("ed25519:{device_id}")))
+        .and_then(Value::as_str)
+        .ok_or(CrossSigningError::InvalidKey)?;
+
+    verify_json(device_key, user_id, device_id, device_keys)?;
+    verify_json(self_signing, user_id, self_signing, device_keys)?;
+
+    Ok(())
+}
+
+/// Verifies that another user's `other_master_key` is signed by
+/// `user_signing_key`, which in turn is signed by `master_key`.
+pub fn verify_user(
+    master_key: &Value,
+    user_signing_key: &Value,
+    other_master_key: &Value,
+) -> Result<(), CrossSigningError> {
+    let (user_id, user_signing) = verify_subkey(master_key, user_signing_key, "user_signing")?;
+    parse_key(other_master_key, "master")?;
+
+    verify_json(user_signing, user_id, user_signing, other_master_key)?;
+
+    Ok(())
+}
@@ -0,0 +1,402 @@
+// Copyright 2020 Johannes Hayeß
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The symmetric primitives of `libolm`'s `crypto.h`, and the constructions
+//! the Matrix key formats build from them.
+//!
+//! SHA-512 is the exception. Matrix derives keys from passphrases with
+//! PBKDF2-HMAC-SHA-512, but `crypto.h` has no SHA-512. The one inside
+//! `libolm`'s Ed25519 code is undeclared and hidden in shared builds, like the
+//! raw AES functions, so the `sha2` crate provides it instead, and every way
+//! of linking `libolm` derives the same keys.
+
+#[cfg(olm_sys_aes_schedule)]
+use core::ffi::{c_int, c_uint};
+
+use sha2::{Digest, Sha512};
+use zeroize::Zeroizing;
+
+use crate::backend::olm;
+#[cfg(not(olm_sys_aes_schedule))]
+use crate::{_olm_aes256_iv, _olm_aes256_key};
+
+/// Length of a SHA-256 hash and thus of an HMAC-SHA-256.
+pub(crate) const SHA256_LENGTH: usize = 32;
+
+/// Length of an AES block and thus of an initialisation vector.
+pub(crate) const AES_BLOCK_LENGTH: usize = 16;
+
+/// HMAC-SHA-256 of `input` under `key`.
+pub(crate) fn hmac_sha256(key: &[u8], input: &[u8]) -> [u8; SHA256_LENGTH] {
+    let mut output = [0; SHA256_LENGTH];
+    unsafe {
+        olm!(_olm_crypto_hmac_sha256(
+            key.as_ptr(),
+            key.len(),
+            input.as_ptr(),
+            input.len(),
+            output.as_mut_ptr(),
+        ))
+    };
+
+    output
+}
+
+/// HKDF with SHA-256 (RFC 5869), filling `output` with the key derived from
+/// `input`.
+pub(crate) fn hkdf_sha256(input: &[u8], salt: &[u8], info: &[u8], output: &mut [u8]) {
+    // `olm/crypto.h` names the parameters info then salt, but `crypto.cpp`
+    // and all of its callers take the salt first
+    unsafe {
+        olm!(_olm_crypto_hkdf_sha256(
+            input.as_ptr(),
+            input.len(),
+            salt.as_ptr(),
+            salt.len(),
+            info.as_ptr(),
+            info.len(),
+            output.as_mut_ptr(),
+            output.len(),
+        ))
+    };
+}
+
+/// Length of a SHA-512 hash and thus of an HMAC-SHA-512.
+pub(crate) const SHA512_LENGTH: usize = 64;
+
+/// SHA-512 (FIPS 180-4) of `input`.
+fn sha512(input: &[u8]) -> [u8; SHA512_LENGTH] {
+    Sha512::digest(input).into()
+}
+
+/// HMAC-SHA-512 (RFC 2104) of `input` under `key`.
+pub(crate) fn hmac_sha512(key: &[u8], input: &[u8]) -> [u8; SHA512_LENGTH] {
+    const BLOCK_LENGTH: usize = 128;
+
+    // The key is usually a passphrase, wipe everything derived from it
+    let mut block = Zeroizing::new([0; BLOCK_LENGTH]);
+    if key.len() > BLOCK_LENGTH {
+        block[..SHA512_LENGTH].copy_from_slice(Zeroizing::new(sha512(key)).as_ref());
+    } else {
+        block[..key.len()].copy_from_slice(key);
+    }
+    let pad = |value: u8| {
+        let mut padded = block.clone();
+        padded.iter_mut().for_each(|byte| *byte ^= value);
+        padded
+    };
+
+    let inner: Zeroizing<[u8; SHA512_LENGTH]> = Zeroizing::new(
+        Sha512::new()
+            .chain_update(pad(0x36))
+            .chain_update(input)
+            .finalize()
+            .into(),
+    );
+    Sha512::new()
+        .chain_update(pad(0x5c))
+        .chain_update(inner)
+        .finalize()
+        .into()
+}
+
+/// PBKDF2 (RFC 8018) with the pseudorandom function `hmac`, filling `output`
+/// with the key derived from `passphrase`.
+///
+/// Callers reject zero `rounds`, which PBKDF2 doesn't define.
+pub(crate) fn pbkdf2<const N: usize>(
+    hmac: fn(&[u8], &[u8]) -> [u8; N],
+    passphrase: &[u8],
+    salt: &[u8],
+    rounds: u32,
+    output: &mut [u8],
+) {
+    assert!(rounds > 0, "PBKDF2 needs at least one round");
+
+    for (i, block) in output.chunks_mut(N).enumerate() {
+        let mut input = salt.to_vec();
+        input.extend_from_slice(&(i as u32 + 1).to_be_bytes());
+
+        let mut u = Zeroizing::new(hmac(passphrase, &input));
+        let mut t = u.clone();
+        for _ in 1..rounds {
+            *u = hmac(passphrase, u.as_ref());
+            t.iter_mut().zip(u.iter()).for_each(|(t, u)| *t ^= u);
+        }
+        block.copy_from_slice(&t[..block.len()]);
+    }
+}
+
+/// Encrypts or decrypts `data` in place with AES-256 in CTR mode, counting in
+/// the last 8 bytes of `iv` like the Matrix specification does.
+pub(crate) fn aes256_ctr(key: &[u8; 32], iv: &[u8; AES_BLOCK_LENGTH], data: &mut [u8]) {
+    let cipher = Aes256::new(key);
+    let mut counter = *iv;
+
+    for block in data.chunks_mut(AES_BLOCK_LENGTH) {
+        let key_stream = cipher.encrypt_block(&counter);
+        block
+            .iter_mut()
+            .zip(&key_stream)
+            .for_each(|(byte, key)| *byte ^= key);
+
+        let (_, low) = counter.split_at_mut(8);
+        let next = u64::from_be_bytes(low.try_into().unwrap()).wrapping_add(1);
+        low.copy_from_slice(&next.to_be_bytes());
+    }
+}
+
+// The block cipher behind `libolm`'s `crypto.h`. A static `libolm` still has
+// these symbols, but its CMake build hides them from shared libraries.
+#[cfg(olm_sys_aes_schedule)]
+extern "C" {
+    #[cfg_attr(feature = "prefix-symbols", link_name = "olmsys_aes_key_setup")]
+    fn aes_key_setup(key: *const u8, w: *mut c_uint, keysize: c_int);
+    #[cfg_attr(feature = "prefix-symbols", link_name = "olmsys_aes_encrypt")]
+    fn aes_encrypt(input: *const u8, out: *mut u8, key: *const c_uint, keysize: c_int);
+}
+
+/// AES-256 with the key expanded once, for encrypting block after block.
+#[cfg(olm_sys_aes_schedule)]
+struct Aes256 {
+    schedule: Zeroizing<[c_uint; 60]>,
+}
+
+#[cfg(olm_sys_aes_schedule)]
+impl Aes256 {
+    fn new(key: &[u8; 32]) -> Self {
+        let mut schedule = Zeroizing::new([0; 60]);
+        unsafe { aes_key_setup(key.as_ptr(), schedule.as_mut_ptr(), 256) };
+        Self { schedule }
+    }
+
+    fn encrypt_block(&self, input: &[u8; AES_BLOCK_LENGTH]) -> [u8; AES_BLOCK_LENGTH] {
+        let mut output = [0; AES_BLOCK_LENGTH];
+        unsafe {
+            aes_encrypt(
+                input.as_ptr(),
+                output.as_mut_ptr(),
+                self.schedule.as_ptr(),
+                256,
+            )
+        };
+        output
+    }
+}
+
+/// AES-256 through the CBC mode that `libolm` exports from shared libraries,
+/// which expands the key again for every block.
+///
+/// Encrypting a single block with the input as IV yields exactly the
+/// encrypted input.
+#[cfg(not(olm_sys_aes_schedule))]
+struct Aes256 {
+    key: _olm_aes256_key,
+}
+
+#[cfg(not(olm_sys_aes_schedule))]
+impl Aes256 {
+    fn new(key: &[u8; 32]) -> Self {
+        Self {
+            key: _olm_aes256_key { key: *key },
+        }
+    }
+
+    fn encrypt_block(&self, input: &[u8; AES_BLOCK_LENGTH]) -> [u8; AES_BLOCK_LENGTH] {
+        let iv = _olm_aes256_iv { iv: *input };
+        let zeroes = [0; AES_BLOCK_LENGTH];
+        // The PKCS#7 padding adds a second block
+        let mut output = [0; 2 * AES_BLOCK_LENGTH];
+        unsafe {
+            olm!(_olm_crypto_aes_encrypt_cbc(
+                &self.key,
+                &iv,
+                zeroes.as_ptr(),
+                zeroes.len(),
+                output.as_mut_ptr(),
+            ))
+        };
+        output[..AES_BLOCK_LENGTH].try_into().unwrap()
+    }
+}
+
+/// Compares two MACs in constant time.
+pub(crate) fn mac_matches(expected: &[u8], actual: &[u8]) -> bool {
+    expected.len() == actual.len()
+        && expected
+            .iter()
+            .zip(actual)
+            .fold(0, |difference, (a, b)| difference | (a ^ b))
+            == 0
+}
+
+#[cfg(all(test, not(feature = "runtime-dlopen")))]
+mod tests {
+    use super::*;
+
+    use alloc::vec::Vec;
+
+    fn hex(input: &str) -> Vec<u8> {
+        (0..input.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&input[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn pbkdf2_matches_rfc_7914() {
+        let mut output = [0; 64];
+        pbkdf2(hmac_sha256, b"passwd", b"salt", 1, &mut output);
+        assert_eq!(
+            output.to_vec(),
+            hex(
+                "55ac046e56e3089fec1691c22544b605f94185216dde0465e68b9d57c20dacbc\
+                 49ca9cccf179b645991664b39d77ef317c71b845b1e30bd509112041d3a19783"
+            )
+        );
+
+        let mut output = [0; 64];
+        pbkdf2(hmac_sha256, b"Password", b"NaCl", 80000, &mut output);
+        assert_eq!(
+            output.to_vec(),
+            hex(
+                "4ddcd8f60b98be21830cee5ef22701f9641a4418d04c0414aeff08876b34ab56\
+                 a1d425a1225833549adb841b51c9b3176a272bdebba1d078478f62b397f33c8d"
+            )
+        );
+    }
+
+    #[test]
+    fn hmac_sha512_matches_rfc_4231() {
+        assert_eq!(
+            hmac_sha512(&[0x0b; 20], b"Hi There").to_vec(),
+            hex(
+                "87aa7cdea5ef619d4ff0b4241a1d6cb02379f4e2ce4ec2787ad0b30545e17cde\
+                 daa833b7d6b8a702038b274eaea3f4e4be9d914eeb61f1702e696c203a126854"
+            )
+        );
+        assert_eq!(
+            hmac_sha512(b"Jefe", b"what do ya want for nothing?").to_vec(),
+            hex(
+                "164b7a7bfcf819e2e395fbe73b56e0a387bd64222e831fd610270cd7ea250554\
+                 9758bf75c05a994a6d034f65f8f0e6fdcaeab1a34d4a6b4b636e070a38bce737"
+            )
+        );
+        assert_eq!(
+            hmac_sha512(&[0xaa; 20], &[0xdd; 50]).to_vec(),
+            hex(
+                "fa73b0089d56a284efb0f0756c890be9b1b5dbdd8ee81a3655f83e33b2279d39\
+                 bf3e848279a722c806b485a47e67c807b946a337bee8942674278859e13292fb"
+            )
+        );
+        let key: Vec<u8> = (1..=25).collect();
+        assert_eq!(
+            hmac_sha512(&key, &[0xcd; 50]).to_vec(),
+            hex(
+                "b0ba465637458c6990e5a8c5f61d4af7e576d97ff94b872de76f8050361ee3db\
+                 a91ca5c11aa25eb4d679275cc5788063a5f19741120c4f2de2adebeb10a298dd"
+            )
+        );
+        assert_eq!(
+            hmac_sha512(
+                &[0xaa; 131],
+                b"Test Using Larger Than Block-Size Key - Hash Key First"
+            )
+            .to_vec(),
+            hex(
+                "80b24263c7c1a3ebb71493c1dd7be8b49b46d1f41b4aeec1121b013783f8f352\
+                 6b56d037e05f2598bd0fd2215d6a1e5295e64f73f63f0aec8b915a985d786598"
+            )
+        );
+        assert_eq!(
+            hmac_sha512(
+                &[0xaa; 131],
+                b"This is a test using a larger than block-size key and a larger \
+                  than block-size data. The key needs to be hashed before being \
+                  used by the HMAC algorithm."
+            )
+            .to_vec(),
+            hex(
+                "e37b6a775dc87dbaa4dfa9f96e5e3ffddebd71f8867289865df5a32d20cdc944\
+                 b6022cac3c4982b10d5eeb55c3e4de15134676fb6de0446065c97440fa8c6a58"
+            )
+        );
+
+        let mut output = [0; 64];
+        pbkdf2(hmac_sha512, b"password", b"salt", 2, &mut output);
+        assert_eq!(
+            output.to_vec(),
+            hex(
+                "e1d9c16aa681708a45f5c7c4e215ceb66e011a2e9f0040713f18aefdb866d53c\
+                 f76cab2868a39b9f7840edce4fef5a82be67335c77a6068e04112754f27ccf4e"
+            )
+        );
+    }
+
+    #[test]
+    fn hkdf_matches_rfc_5869() {
+        let mut output = [0; 42];
+        hkdf_sha256(
+            &[0x0b; 22],
+            &hex("000102030405060708090a0b0c"),
+            &hex("f0f1f2f3f4f5f6f7f8f9"),
+            &mut output,
+        );
+        assert_eq!(
+            output.to_vec(),
+            hex(
+                "3cb25f25faacd57a90434f64d0362f2a2d2d0a90cf1a5a4c5db02d56ecc4c5bf\
+                 34007208d5b887185865"
+            )
+        );
+    }
+
+    #[test]
+    fn aes_ctr_matches_sp_800_38a() {
+        let key = hex("603deb1015ca71be2b73aef0857d77811f352c073b6108d72d9810a30914dff4");
+        let iv = hex("f0f1f2f3f4f5f6f7f8f9fafbfcfdfeff");
+        let mut data = hex(
+            "6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e51\
+                            30c81c46a35ce411e5fbc1191a0a52eff69f2445df4f9b17ad2b417be66c3710",
+        );
+
+        aes256_ctr(
+            key.as_slice().try_into().unwrap(),
+            iv.as_slice().try_into().unwrap(),
+            &mut data,
+        );
+        assert_eq!(
+            data,
+            hex(
+                "601ec313775789a5b7a7f504bbf3d228f443e3ca4d62b59aca84e990cacaf5c5\
+                 2b0930daa23de94ce87017ba2d84988ddfc9c58db67aada613c2dd08457941a6"
+            )
+        );
+
+        // Decrypting is the same operation, also for partial blocks
+        aes256_ctr(
+            key.as_slice().try_into().unwrap(),
+            iv.as_slice().try_into().unwrap(),
+            &mut data[..20],
+        );
+        assert_eq!(data[..20], hex("6bc1bee22e409f96e93d7e117393172aae2d8a57"));
+    }
+
+    #[test]
+    fn macs_compare_by_value_and_length() {
+        assert!(mac_matches(b"abc", b"abc"));
+        assert!(!mac_matches(b"abc", b"abd"));
+        assert!(!mac_matches(b"abc", b"ab"));
+    }
+}
@@ -0,0 +1,85 @@
+// Copyright 2020 Johannes Hayeß
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The parts of the C++ runtime that `libolm` may need when it is built
+//! without a C++ standard library (`OLM_CXX_STDLIB=none`).
+//!
+//! `libolm` constructs its objects with placement new in caller-provided
+//! memory and has no virtual functions, so none of these should ever run.
+//! They exist so that a compiler emitting references to them doesn't make
+//! linking fail. The names follow the Itanium C++ ABI.
+
+use core::ffi::c_void;
+
+extern "C" {
+    fn malloc(size: usize) -> *mut c_void;
+    fn free(ptr: *mut c_void);
+    fn abort() -> !;
+}
+
+unsafe fn allocate(size: usize) -> *mut c_void {
+    // C++ requires a unique pointer, even for zero sized allocations.
+    let ptr = malloc(size.max(1));
+    if ptr.is_null() {
+        abort();
+    }
+
+    ptr
+}
+
+/// `operator new(std::size_t)`
+#[cfg_attr(target_pointer_width = "64", export_name = "_Znwm")]
+#[cfg_attr(target_pointer_width = "32", export_name = "_Znwj")]
+unsafe extern "C" fn operator_new(size: usize) -> *mut c_void {
+    allocate(size)
+}
+
+/// `operator new[](std::size_t)`
+#[cfg_attr(target_pointer_width = "64", export_name = "_Znam")]
+#[cfg_attr(target_pointer_width = "32", export_name = "_Znaj")]
+unsafe extern "C" fn operator_new_array(size: usize) -> *mut c_void {
+    allocate(size)
+}
+
+/// `operator delete(void*)`
+#[export_name = "_ZdlPv"]
+unsafe extern "C" fn operator_delete(ptr: *mut c_void) {
+    free(ptr)
+}
+
+/// `operator delete[](void*)`
+#[export_name = "_ZdaPv"]
+unsafe extern "C" fn operator_delete_array(ptr: *mut c_void) {
+    free(ptr)
+}
+
+/// `operator delete(void*, std::size_t)`
+#[cfg_attr(target_pointer_width = "64", export_name = "_ZdlPvm")]
+#[cfg_attr(target_pointer_width = "32", export_name = "_ZdlPvj")]
+unsafe extern "C" fn operator_delete_sized(ptr: *mut c_void, _size: usize) {
+    free(ptr)
+}
+
+/// `operator delete[](void*, std::size_t)`
+#[cfg_attr(target_pointer_width = "64", export_name = "_ZdaPvm")]
+#[cfg_attr(target_pointer_width = "32", export_name = "_ZdaPvj")]
+unsafe extern "C" fn operator_delete_array_sized(ptr: *mut c_void, _size: usize) {
+    free(ptr)
+}
+
+/// Called when a pure virtual function is invoked, which is always a bug.
+#[no_mangle]
+extern "C" fn __cxa_pure_virtual() {
+    unsafe { abort() };
+}
@@ -0,0 +1,3440 @@
+// Copyright 2020 Johannes Hayeß
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+/* automatically generated by rust-bindgen */
+
+use crate::*;
+
+pub struct OlmLibrary {
+    __library: ::libloading::Library,
+    pub _olm_error_to_string: Result<
+        unsafe extern "C" fn(error: OlmErrorCode) -> *const ::core::ffi::c_char,
+        ::libloading::Error,
+    >,
+    pub olm_inbound_group_session_size:
+        Result<unsafe extern "C" fn() -> usize, ::libloading::Error>,
+    pub olm_inbound_group_session: Result<
+        unsafe extern "C" fn(memory: *mut ::core::ffi::c_void) -> *mut OlmInboundGroupSession,
+        ::libloading::Error,
+    >,
+    pub olm_inbound_group_session_last_error: Result<
+        unsafe extern "C" fn(session: *const OlmInboundGroupSession) -> *const ::core::ffi::c_char,
+        ::libloading::Error,
+    >,
+    pub olm_inbound_group_session_last_error_code: Result<
+        unsafe extern "C" fn(session: *const OlmInboundGroupSession) -> OlmErrorCode,
+        ::libloading::Error,
+    >,
+    pub olm_clear_inbound_group_session: Result<
+        unsafe extern "C" fn(session: *mut OlmInboundGroupSession) -> usize,
+        ::libloading::Error,
+    >,
+    pub olm_pickle_inbound_group_session_length: Result<
+        unsafe extern "C" fn(session: *const OlmInboundGroupSession) -> usize,
+        ::libloading::Error,
+    >,
+    pub olm_pickle_inbound_group_session: Result<
+        unsafe extern "C" fn(
+            session: *mut OlmInboundGroupSession,
+            key: *const ::core::ffi::c_void,
+            key_length: usize,
+            pickled: *mut ::core::ffi::c_void,
+            pickled_length: usize,
+        ) -> usize,
+        ::libloading::Error,
+    >,
+    pub olm_unpickle_inbound_group_session: Result<
+        unsafe extern "C" fn(
+            session: *mut OlmInboundGroupSession,
+            key: *const ::core::ffi::c_void,
+            key_length: usize,
+            pickled: *mut ::core::ffi::c_void,
+            pickled_length: usize,
+        ) -> usize,
+        ::libloading::Error,
+    >,
+    pub olm_init_inbound_group_session: Result<
+        unsafe extern "C" fn(
+            session: *mut OlmInboundGroupSession,
+            session_key: *const u8,
+            session_key_length: usize,
+        ) -> usize,
+        ::libloading::Error,
+    >,
+    pub olm_import_inbound_group_session: Result<
+        unsafe extern "C" fn(
+            session: *mut OlmInboundGroupSession,
+            session_key: *const u8,
+            session_key_length: usize,
+        ) -> usize,
+        ::libloading::Error,
+    >,
+    pub olm_group_decrypt_max_plaintext_length: Result<
+        unsafe extern "C" fn(
+            session: *mut OlmInboundGroupSession,
+            message: *mut u8,
+            message_length: usize,
+        ) -> usize,
+        ::libloading::Error,
+    >,
+    pub olm_group_decrypt: Result<
+        unsafe extern "C" fn(
+            session: *mut OlmInboundGroupSession,
+            message: *mut u8,
+            message_length: usize,
+            plaintext: *mut u8,
+            max_plaintext_length: usize,
+            message_index: *mut u32,
+        ) -> usize,
+        ::libloading::Error,
+    >,
+    pub olm_inbound_group_session_id_length: Result<
+        unsafe extern "C" fn(session: *const OlmInboundGroupSession) -> usize,
+        ::libloading::Error,
+    >,
+    pub olm_inbound_group_session_id: Result<
+        unsafe extern "C" fn(
+            session: *mut OlmInboundGroupSession,
+            id: *mut u8,
+            id_length: usize,
+        ) -> usize,
+        ::libloading::Error,
+    >,
+    pub olm_inbound_group_session_first_known_index: Result<
+        unsafe extern "C" fn(session: *const OlmInboundGroupSession) -> u32,
+        ::libloading::Error,
+    >,
+    pub olm_inbound_group_session_is_verified: Result<
+        unsafe extern "C" fn(session: *const OlmInboundGroupSession) -> ::core::ffi::c_int,
+        ::libloading::Error,
+    >,
+    pub olm_export_inbound_group_session_length: Result<
+        unsafe extern "C" fn(session: *const OlmInboundGroupSession) -> usize,
+        ::libloading::Error,
+    >,
+    pub olm_export_inbound_group_session: Result<
+        unsafe extern "C" fn(
+            session: *mut OlmInboundGroupSession,
+            key: *mut u8,
+            key_length: usize,
+            message_index: u32,
+        ) -> usize,
+        ::libloading::Error,
+    >,
+    pub olm_outbound_group_session_size:
+        Result<unsafe extern "C" fn() -> usize, ::libloading::Error>,
+    pub olm_outbound_group_session: Result<
+        unsafe extern "C" fn(memory: *mut ::core::ffi::c_void) -> *mut OlmOutboundGroupSession,
+        ::libloading::Error,
+    >,
+    pub olm_outbound_group_session_last_error: Result<
+        unsafe extern "C" fn(session: *const OlmOutboundGroupSession) -> *const ::core::ffi::c_char,
+        ::libloading::Error,
+    >,
+    pub olm_outbound_group_session_last_error_code: Result<
+        unsafe extern "C" fn(session: *const OlmOutboundGroupSession) -> OlmErrorCode,
+        ::libloading::Error,
+    >,
+    pub olm_clear_outbound_group_session: Result<
+        unsafe extern "C" fn(session: *mut OlmOutboundGroupSession) -> usize,
+        ::libloading::Error,
+    >,
+    pub olm_pickle_outbound_group_session_length: Result<
+        unsafe extern "C" fn(session: *const OlmOutboundGroupSession) -> usize,
+        ::libloading::Error,
+    >,
+    pub olm_pickle_outbound_group_session: Result<
+        unsafe extern "C" fn(
+            session: *mut OlmOutboundGroupSession,
+            key: *const ::core::ffi::c_void,
+            key_length: usize,
+            pickled: *mut ::core::ffi::c_void,
+            pickled_length: usize,
+        ) -> usize,
+        ::libloading::Error,
+    >,
+    pub olm_unpickle_outbound_group_session: Result<
+        unsafe extern "C" fn(
+            session: *mut OlmOutboundGroupSession,
+            key: *const ::core::ffi::c_void,
+            key_length: usize,
+            pickled: *mut ::core::ffi::c_void,
+            pickled_length: usize,
+        ) -> usize,
+        ::libloading::Error,
+    >,
+    pub olm_init_outbound_group_session_random_length: Result<
+        unsafe extern "C" fn(session: *const OlmOutboundGroupSession) -> usize,
+        ::libloading::Error,
+    >,
+    pub olm_init_outbound_group_session: Result<
+        unsafe extern "C" fn(
+            session: *mut OlmOutboundGroupSession,
+            random: *mut u8,
+            random_length: usize,
+        ) -> usize,
+        ::libloading::Error,
+    >,
+    pub olm_group_encrypt_message_length: Result<
+        unsafe extern "C" fn(
+            session: *mut OlmOutboundGroupSession,
+            plaintext_length: usize,
+        ) -> usize,
+        ::libloading::Error,
+    >,
+    pub olm_group_encrypt: Result<
+        unsafe extern "C" fn(
+            session: *mut OlmOutboundGroupSession,
+            plaintext: *const u8,
+            plaintext_length: usize,
+            message: *mut u8,
+            message_length: usize,
+        ) -> usize,
+        ::libloading::Error,
+    >,
+    pub olm_outbound_group_session_id_length: Result<
+        unsafe extern "C" fn(session: *const OlmOutboundGroupSession) -> usize,
+        ::libloading::Error,
+    >,
+    pub olm_outbound_group_session_id: Result<
+        unsafe extern "C" fn(
+            session: *mut OlmOutboundGroupSession,
+            id: *mut u8,
+            id_length: usize,
+        ) -> usize,
+        ::libloading::Error,
+    >,
+    pub olm_outbound_group_session_message_index: Result<
+        unsafe extern "C" fn(session: *mut OlmOutboundGroupSession) -> u32,
+        ::libloading::Error,
+    >,
+    pub olm_outbound_group_session_key_length: Result<
+        unsafe extern "C" fn(session: *const OlmOutboundGroupSession) -> usize,
+        ::libloading::Error,
+    >,
+    pub olm_outbound_group_session_key: Result<
+        unsafe extern "C" fn(
+            session: *mut OlmOutboundGroupSession,
+            key: *mut u8,
+            key_length: usize,
+        ) -> usize,
+        ::libloading::Error,
+    >,
+    pub olm_get_library_version: Result<
+        unsafe extern "C" fn(major: *mut u8, minor: *mut u8, patch: *mut u8),
+        ::libloading::Error,
+    >,
+    pub olm_account_size: Result<unsafe extern "C" fn() -> usize, ::libloading::Error>,
+    pub olm_session_size: Result<unsafe extern "C" fn() -> usize, ::libloading::Error>,
+    pub olm_utility_size: Result<unsafe extern "C" fn() -> usize, ::libloading::Error>,
+    pub olm_account: Result<
+        unsafe extern "C" fn(memory: *mut ::core::ffi::c_void) -> *mut OlmAccount,
+        ::libloading::Error,
+    >,
+    pub olm_session: Result<
+        unsafe extern "C" fn(memory: *mut ::core::ffi::c_void) -> *mut OlmSession,
+        ::libloading::Error,
+    >,
+    pub olm_utility: Result<
+        unsafe extern "C" fn(memory: *mut ::core::ffi::c_void) -> *mut OlmUtility,
+        ::libloading::Error,
+    >,
+    pub olm_error: Result<unsafe extern "C" fn() -> usize, ::libloading::Error>,
+    pub olm_account_last_error: Result<
+        unsafe extern "C" fn(account: *const OlmAccount) -> *const ::core::ffi::c_char,
+        ::libloading::Error,
+    >,
+    pub olm_account_last_error_code: Result<
+        unsafe extern "C" fn(account: *const OlmAccount) -> OlmErrorCode,
+        ::libloading::Error,
+    >,
+    pub olm_session_last_error: Result<
+        unsafe extern "C" fn(session: *const OlmSession) -> *const ::core::ffi::c_char,
+        ::libloading::Error,
+    >,
+    pub olm_session_last_error_code: Result<
+        unsafe extern "C" fn(session: *const OlmSession) -> OlmErrorCode,
+        ::libloading::Error,
+    >,
+    pub olm_utility_last_error: Result<
+        unsafe extern "C" fn(utility: *const OlmUtility) -> *const ::core::ffi::c_char,
+        ::libloading::Error,
+    >,
+    pub olm_utility_last_error_code: Result<
+        unsafe extern "C" fn(utility: *const OlmUtility) -> OlmErrorCode,
+        ::libloading::Error,
+    >,
+    pub olm_clear_account:
+        Result<unsafe extern "C" fn(account: *mut OlmAccount) -> usize, ::libloading::Error>,
+    pub olm_clear_session:
+        Result<unsafe extern "C" fn(session: *mut OlmSession) -> usize, ::libloading::Error>,
+    pub olm_clear_utility:
+        Result<unsafe extern "C" fn(utility: *mut OlmUtility) -> usize, ::libloading::Error>,
+    pub olm_pickle_account_length:
+        Result<unsafe extern "C" fn(account: *const OlmAccount) -> usize, ::libloading::Error>,
+    pub olm_pickle_session_length:
+        Result<unsafe extern "C" fn(session: *const OlmSession) -> usize, ::libloading::Error>,
+    pub olm_pickle_account: Result<
+        unsafe extern "C" fn(
+            account: *mut OlmAccount,
+            key: *const ::core::ffi::c_void,
+            key_length: usize,
+            pickled: *mut ::core::ffi::c_void,
+            pickled_length: usize,
+        ) -> usize,
+        ::libloading::Error,
+    >,
+    pub olm_pickle_session: Result<
+        unsafe extern "C" fn(
+            session: *mut OlmSession,
+            key: *const ::core::ffi::c_void,
+            key_length: usize,
+            pickled: *mut ::core::ffi::c_void,
+            pickled_length: usize,
+        ) -> usize,
+        ::libloading::Error,
+    >,
+    pub olm_unpickle_account: Result<
+        unsafe extern "C" fn(
+            account: *mut OlmAccount,
+            key: *const ::core::ffi::c_void,
+            key_length: usize,
+            pickled: *mut ::core::ffi::c_void,
+            pickled_length: usize,
+        ) -> usize,
+        ::libloading::Error,
+    >,
+    pub olm_unpickle_session: Result<
+        unsafe extern "C" fn(
+            session: *mut OlmSession,
+            key: *const ::core::ffi::c_void,
+            key_length: usize,
+            pickled: *mut ::core::ffi::c_void,
+            pickled_length: usize,
+        ) -> usize,
+        ::libloading::Error,
+    >,
+    pub olm_create_account_random_length:
+        Result<unsafe extern "C" fn(account: *const OlmAccount) -> usize, ::libloading::Error>,
+    pub olm_create_account: Result<
+        unsafe extern "C" fn(
+            account: *mut OlmAccount,
+            random: *mut ::core::ffi::c_void,
+            random_length: usize,
+        ) -> usize,
+        ::libloading::Error,
+    >,
+    pub olm_account_identity_keys_length:
+        Result<unsafe extern "C" fn(account: *const OlmAccount) -> usize, ::libloading::Error>,
+    pub olm_account_identity_keys: Result<
+        unsafe extern "C" fn(
+            account: *mut OlmAccount,
+            identity_keys: *mut ::core::ffi::c_void,
+            identity_key_length: usize,
+        ) -> usize,
+        ::libloading::Error,
+    >,
+    pub olm_account_signature_length:
+        Result<unsafe extern "C" fn(account: *const OlmAccount) -> usize, ::libloading::Error>,
+    pub olm_account_sign: Result<
+        unsafe extern "C" fn(
+            account: *mut OlmAccount,
+            message: *const ::core::ffi::c_void,
+            message_length: usize,
+            signature: *mut ::core::ffi::c_void,
+            signature_length: usize,
+        ) -> usize,
+        ::libloading::Error,
+    >,
+    pub olm_account_one_time_keys_length:
+        Result<unsafe extern "C" fn(account: *const OlmAccount) -> usize, ::libloading::Error>,
+    pub olm_account_one_time_keys: Result<
+        unsafe extern "C" fn(
+            account: *mut OlmAccount,
+            one_time_keys: *mut ::core::ffi::c_void,
+            one_time_keys_length: usize,
+        ) -> usize,
+        ::libloading::Error,
+    >,
+    pub olm_account_mark_keys_as_published:
+        Result<unsafe extern "C" fn(account: *mut OlmAccount) -> usize, ::libloading::Error>,
+    pub olm_account_max_number_of_one_time_keys:
+        Result<unsafe extern "C" fn(account: *const OlmAccount) -> usize, ::libloading::Error>,
+    pub olm_account_generate_one_time_keys_random_length: Result<
+        unsafe extern "C" fn(account: *const OlmAccount, number_of_keys: usize) -> usize,
+        ::libloading::Error,
+    >,
+    pub olm_account_generate_one_time_keys: Result<
+        unsafe extern "C" fn(
+            account: *mut OlmAccount,
+            number_of_keys: usize,
+            random: *mut ::core::ffi::c_void,
+            random_length: usize,
+        ) -> usize,
+        ::libloading::Error,
+    >,
+    pub olm_account_generate_fallback_key_random_length:
+        Result<unsafe extern "C" fn(account: *const OlmAccount) -> usize, ::libloading::Error>,
+    pub olm_account_generate_fallback_key: Result<
+        unsafe extern "C" fn(
+            account: *mut OlmAccount,
+            random: *mut ::core::ffi::c_void,
+            random_length: usize,
+        ) -> usize,
+        ::libloading::Error,
+    >,
+    pub olm_account_fallback_key_length:
+        Result<unsafe extern "C" fn(account: *const OlmAccount) -> usize, ::libloading::Error>,
+    pub olm_account_fallback_key: Result<
+        unsafe extern "C" fn(
+            account: *mut OlmAccount,
+            fallback_key: *mut ::core::ffi::c_void,
+            fallback_key_size: usize,
+        ) -> usize,
+        ::libloading::Error,
+    >,
+    pub olm_account_unpublished_fallback_key_length:
+        Result<unsafe extern "C" fn(account: *const OlmAccount) -> usize, ::libloading::Error>,
+    pub olm_account_unpublished_fallback_key: Result<
+        unsafe extern "C" fn(
+            account: *mut OlmAccount,
+            fallback_key: *mut ::core::ffi::c_void,
+            fallback_key_size: usize,
+        ) -> usize,
+        ::libloading::Error,
+    >,
+    pub olm_account_forget_old_fallback_key:
+        Result<unsafe extern "C" fn(account: *mut OlmAccount), ::libloading::Error>,
+    pub olm_create_outbound_session_random_length:
+        Result<unsafe extern "C" fn(session: *const OlmSession) -> usize, ::libloading::Error>,
+    pub olm_create_outbound_session: Result<
+        unsafe extern "C" fn(
+            session: *mut OlmSession,
+            account: *const OlmAccount,
+            their_identity_key: *const ::core::ffi::c_void,
+            their_identity_key_length: usize,
+            their_one_time_key: *const ::core::ffi::c_void,
+            their_one_time_key_length: usize,
+            random: *mut ::core::ffi::c_void,
+            random_length: usize,
+        ) -> usize,
+        ::libloading::Error,
+    >,
+    pub olm_create_inbound_session: Result<
+        unsafe extern "C" fn(
+            session: *mut OlmSession,
+            account: *mut OlmAccount,
+            one_time_key_message: *mut ::core::ffi::c_void,
+            message_length: usize,
+        ) -> usize,
+        ::libloading::Error,
+    >,
+    pub olm_create_inbound_session_from: Result<
+        unsafe extern "C" fn(
+            session: *mut OlmSession,
+            account: *mut OlmAccount,
+            their_identity_key: *const ::core::ffi::c_void,
+            their_identity_key_length: usize,
+            one_time_key_message: *mut ::core::ffi::c_void,
+            message_length: usize,
+        ) -> usize,
+        ::libloading::Error,
+    >,
+    pub olm_session_id_length:
+        Result<unsafe extern "C" fn(session: *const OlmSession) -> usize, ::libloading::Error>,
+    pub olm_session_id: Result<
+        unsafe extern "C" fn(
+            session: *mut OlmSession,
+            id: *mut ::core::ffi::c_void,
+            id_length: usize,
+        ) -> usize,
+        ::libloading::Error,
+    >,
+    pub olm_session_has_received_message: Result<
+        unsafe extern "C" fn(session: *const OlmSession) -> ::core::ffi::c_int,
+        ::libloading::Error,
+    >,
+    pub olm_session_describe: Result<
+        unsafe extern "C" fn(
+            session: *mut OlmSession,
+            buf: *mut ::core::ffi::c_char,
+            buflen: usize,
+        ),
+        ::libloading::Error,
+    >,
+    pub olm_matches_inbound_session: Result<
+        unsafe extern "C" fn(
+            session: *mut OlmSession,
+            one_time_key_message: *mut ::core::ffi::c_void,
+            message_length: usize,
+        ) -> usize,
+        ::libloading::Error,
+    >,
+    pub olm_matches_inbound_session_from: Result<
+        unsafe extern "C" fn(
+            session: *mut OlmSession,
+            their_identity_key: *const ::core::ffi::c_void,
+            their_identity_key_length: usize,
+            one_time_key_message: *mut ::core::ffi::c_void,
+            message_length: usize,
+        ) -> usize,
+        ::libloading::Error,
+    >,
+    pub olm_remove_one_time_keys: Result<
+        unsafe extern "C" fn(account: *mut OlmAccount, session: *mut OlmSession) -> usize,
+        ::libloading::Error,
+    >,
+    pub olm_encrypt_message_type:
+        Result<unsafe extern "C" fn(session: *const OlmSession) -> usize, ::libloading::Error>,
+    pub olm_encrypt_random_length:
+        Result<unsafe extern "C" fn(session: *const OlmSession) -> usize, ::libloading::Error>,
+    pub olm_encrypt_message_length: Result<
+        unsafe extern "C" fn(session: *const OlmSession, plaintext_length: usize) -> usize,
+        ::libloading::Error,
+    >,
+    pub olm_encrypt: Result<
+        unsafe extern "C" fn(
+            session: *mut OlmSession,
+            plaintext: *const ::core::ffi::c_void,
+            plaintext_length: usize,
+            random: *mut ::core::ffi::c_void,
+            random_length: usize,
+            message: *mut ::core::ffi::c_void,
+            message_length: usize,
+        ) -> usize,
+        ::libloading::Error,
+    >,
+    pub olm_decrypt_max_plaintext_length: Result<
+        unsafe extern "C" fn(
+            session: *mut OlmSession,
+            message_type: usize,
+            message: *mut ::core::ffi::c_void,
+            message_length: usize,
+        ) -> usize,
+        ::libloading::Error,
+    >,
+    pub olm_decrypt: Result<
+        unsafe extern "C" fn(
+            session: *mut OlmSession,
+            message_type: usize,
+            message: *mut ::core::ffi::c_void,
+            message_length: usize,
+            plaintext: *mut ::core::ffi::c_void,
+            max_plaintext_length: usize,
+        ) -> usize,
+        ::libloading::Error,
+    >,
+    pub olm_sha256_length:
+        Result<unsafe extern "C" fn(utility: *const OlmUtility) -> usize, ::libloading::Error>,
+    pub olm_sha256: Result<
+        unsafe extern "C" fn(
+            utility: *mut OlmUtility,
+            input: *const ::core::ffi::c_void,
+            input_length: usize,
+            output: *mut ::core::ffi::c_void,
+            output_length: usize,
+        ) -> usize,
+        ::libloading::Error,
+    >,
+    pub olm_ed25519_verify: Result<
+        unsafe extern "C" fn(
+            utility: *mut OlmUtility,
+            key: *const ::core::ffi::c_void,
+            key_length: usize,
+            message: *const ::core::ffi::c_void,
+            message_length: usize,
+            signature: *mut ::core::ffi::c_void,
+            signature_length: usize,
+        ) -> usize,
+        ::libloading::Error,
+    >,
+    pub olm_sas_last_error: Result<
+        unsafe extern "C" fn(sas: *const OlmSAS) -> *const ::core::ffi::c_char,
+        ::libloading::Error,
+    >,
+    pub olm_sas_last_error_code:
+        Result<unsafe extern "C" fn(sas: *const OlmSAS) -> OlmErrorCode, ::libloading::Error>,
+    pub olm_sas_size: Result<unsafe extern "C" fn() -> usize, ::libloading::Error>,
+    pub olm_sas: Result<
+        unsafe extern "C" fn(memory: *mut ::core::ffi::c_void) -> *mut OlmSAS,
+        ::libloading::Error,
+    >,
+    pub olm_clear_sas: Result<unsafe extern "C" fn(sas: *mut OlmSAS) -> usize, ::libloading::Error>,
+    pub olm_create_sas_random_length:
+        Result<unsafe extern "C" fn(sas: *const OlmSAS) -> usize, ::libloading::Error>,
+    pub olm_create_sas: Result<
+        unsafe extern "C" fn(
+            sas: *mut OlmSAS,
+            random: *mut ::core::ffi::c_void,
+            random_length: usize,
+        ) -> usize,
+        ::libloading::Error,
+    >,
+    pub olm_sas_pubkey_length:
+        Result<unsafe extern "C" fn(sas: *const OlmSAS) -> usize, ::libloading::Error>,
+    pub olm_sas_get_pubkey: Result<
+        unsafe extern "C" fn(
+            sas: *mut OlmSAS,
+            pubkey: *mut ::core::ffi::c_void,
+            pubkey_length: usize,
+        ) -> usize,
+        ::libloading::Error,
+    >,
+    pub olm_sas_set_their_key: Result<
+        unsafe extern "C" fn(
+            sas: *mut OlmSAS,
+            their_key: *mut ::core::ffi::c_void,
+            their_key_length: usize,
+        ) -> usize,
+        ::libloading::Error,
+    >,
+    pub olm_sas_is_their_key_set:
+        Result<unsafe extern "C" fn(sas: *const OlmSAS) -> ::core::ffi::c_int, ::libloading::Error>,
+    pub olm_sas_generate_bytes: Result<
+        unsafe extern "C" fn(
+            sas: *mut OlmSAS,
+            info: *const ::core::ffi::c_void,
+            info_length: usize,
+            output: *mut ::core::ffi::c_void,
+            output_length: usize,
+        ) -> usize,
+        ::libloading::Error,
+    >,
+    pub olm_sas_mac_length:
+        Result<unsafe extern "C" fn(sas: *const OlmSAS) -> usize, ::libloading::Error>,
+    pub olm_sas_calculate_mac: Result<
+        unsafe extern "C" fn(
+            sas: *mut OlmSAS,
+            input: *const ::core::ffi::c_void,
+            input_length: usize,
+            info: *const ::core::ffi::c_void,
+            info_length: usize,
+            mac: *mut ::core::ffi::c_void,
+            mac_length: usize,
+        ) -> usize,
+        ::libloading::Error,
+    >,
+    pub olm_sas_calculate_mac_fixed_base64: Result<
+        unsafe extern "C" fn(
+            sas: *mut OlmSAS,
+            input: *const ::core::ffi::c_void,
+            input_length: usize,
+            info: *const ::core::ffi::c_void,
+            info_length: usize,
+            mac: *mut ::core::ffi::c_void,
+            mac_length: usize,
+        ) -> usize,
+        ::libloading::Error,
+    >,
+    pub olm_sas_calculate_mac_long_kdf: Result<
+        unsafe extern "C" fn(
+            sas: *mut OlmSAS,
+            input: *const ::core::ffi::c_void,
+            input_length: usize,
+            info: *const ::core::ffi::c_void,
+            info_length: usize,
+            mac: *mut ::core::ffi::c_void,
+            mac_length: usize,
+        ) -> usize,
+        ::libloading::Error,
+    >,
+    pub olm_pk_encryption_size: Result<unsafe extern "C" fn() -> usize, ::libloading::Error>,
+    pub olm_pk_encryption: Result<
+        unsafe extern "C" fn(memory: *mut ::core::ffi::c_void) -> *mut OlmPkEncryption,
+        ::libloading::Error,
+    >,
+    pub olm_pk_encryption_last_error: Result<
+        unsafe extern "C" fn(encryption: *const OlmPkEncryption) -> *const ::core::ffi::c_char,
+        ::libloading::Error,
+    >,
+    pub olm_pk_encryption_last_error_code: Result<
+        unsafe extern "C" fn(encryption: *const OlmPkEncryption) -> OlmErrorCode,
+        ::libloading::Error,
+    >,
+    pub olm_clear_pk_encryption: Result<
+        unsafe extern "C" fn(encryption: *mut OlmPkEncryption) -> usize,
+        ::libloading::Error,
+    >,
+    pub olm_pk_encryption_set_recipient_key: Result<
+        unsafe extern "C" fn(
+            encryption: *mut OlmPkEncryption,
+            public_key: *const ::core::ffi::c_void,
+            public_key_length: usize,
+        ) -> usize,
+        ::libloading::Error,
+    >,
+    pub olm_pk_ciphertext_length: Result<
+        unsafe extern "C" fn(encryption: *const OlmPkEncryption, plaintext_length: usize) -> usize,
+        ::libloading::Error,
+    >,
+    pub olm_pk_mac_length: Result<
+        unsafe extern "C" fn(encryption: *const OlmPkEncryption) -> usize,
+        ::libloading::Error,
+    >,
+    pub olm_pk_key_length: Result<unsafe extern "C" fn() -> usize, ::libloading::Error>,
+    pub olm_pk_encrypt_random_length: Result<
+        unsafe extern "C" fn(encryption: *const OlmPkEncryption) -> usize,
+        ::libloading::Error,
+    >,
+    pub olm_pk_encrypt: Result<
+        unsafe extern "C" fn(
+            encryption: *mut OlmPkEncryption,
+            plaintext: *const ::core::ffi::c_void,
+            plaintext_length: usize,
+            ciphertext: *mut ::core::ffi::c_void,
+            ciphertext_length: usize,
+            mac: *mut ::core::ffi::c_void,
+            mac_length: usize,
+            ephemeral_key: *mut ::core::ffi::c_void,
+            ephemeral_key_size: usize,
+            random: *const ::core::ffi::c_void,
+            random_length: usize,
+        ) -> usize,
+        ::libloading::Error,
+    >,
+    pub olm_pk_decryption_size: Result<unsafe extern "C" fn() -> usize, ::libloading::Error>,
+    pub olm_pk_decryption: Result<
+        unsafe extern "C" fn(memory: *mut ::core::ffi::c_void) -> *mut OlmPkDecryption,
+        ::libloading::Error,
+    >,
+    pub olm_pk_decryption_last_error: Result<
+        unsafe extern "C" fn(decryption: *const OlmPkDecryption) -> *const ::core::ffi::c_char,
+        ::libloading::Error,
+    >,
+    pub olm_pk_decryption_last_error_code: Result<
+        unsafe extern "C" fn(decryption: *const OlmPkDecryption) -> OlmErrorCode,
+        ::libloading::Error,
+    >,
+    pub olm_clear_pk_decryption: Result<
+        unsafe extern "C" fn(decryption: *mut OlmPkDecryption) -> usize,
+        ::libloading::Error,
+    >,
+    pub olm_pk_private_key_length: Result<unsafe extern "C" fn() -> usize, ::libloading::Error>,
+    pub olm_pk_generate_key_random_length:
+        Result<unsafe extern "C" fn() -> usize, ::libloading::Error>,
+    pub olm_pk_key_from_private: Result<
+        unsafe extern "C" fn(
+            decryption: *mut OlmPkDecryption,
+            pubkey: *mut ::core::ffi::c_void,
+            pubkey_length: usize,
+            privkey: *const ::core::ffi::c_void,
+            privkey_length: usize,
+        ) -> usize,
+        ::libloading::Error,
+    >,
+    pub olm_pk_generate_key: Result<
+        unsafe extern "C" fn(
+            decryption: *mut OlmPkDecryption,
+            pubkey: *mut ::core::ffi::c_void,
+            pubkey_length: usize,
+            privkey: *const ::core::ffi::c_void,
+            privkey_length: usize,
+        ) -> usize,
+        ::libloading::Error,
+    >,
+    pub olm_pickle_pk_decryption_length: Result<
+        unsafe extern "C" fn(decryption: *const OlmPkDecryption) -> usize,
+        ::libloading::Error,
+    >,
+    pub olm_pickle_pk_decryption: Result<
+        unsafe extern "C" fn(
+            decryption: *mut OlmPkDecryption,
+            key: *const ::core::ffi::c_void,
+            key_length: usize,
+            pickled: *mut ::core::ffi::c_void,
+            pickled_length: usize,
+        ) -> usize,
+        ::libloading::Error,
+    >,
+    pub olm_unpickle_pk_decryption: Result<
+        unsafe extern "C" fn(
+            decryption: *mut OlmPkDecryption,
+            key: *const ::core::ffi::c_void,
+            key_length: usize,
+            pickled: *mut ::core::ffi::c_void,
+            pickled_length: usize,
+            pubkey: *mut ::core::ffi::c_void,
+            pubkey_length: usize,
+        ) -> usize,
+        ::libloading::Error,
+    >,
+    pub olm_pk_max_plaintext_length: Result<
+        unsafe extern "C" fn(decryption: *const OlmPkDecryption, ciphertext_length: usize) -> usize,
+        ::libloading::Error,
+    >,
+    pub olm_pk_decrypt: Result<
+        unsafe extern "C" fn(
+            decryption: *mut OlmPkDecryption,
+            ephemeral_key: *const ::core::ffi::c_void,
+            ephemeral_key_length: usize,
+            mac: *const ::core::ffi::c_void,
+            mac_length: usize,
+            ciphertext: *mut ::core::ffi::c_void,
+            ciphertext_length: usize,
+            plaintext: *mut ::core::ffi::c_void,
+            max_plaintext_length: usize,
+        ) -> usize,
+        ::libloading::Error,
+    >,
+    pub olm_pk_get_private_key: Result<
+        unsafe extern "C" fn(
+            decryption: *mut OlmPkDecryption,
+            private_key: *mut ::core::ffi::c_void,
+            private_key_length: usize,
+        ) -> usize,
+        ::libloading::Error,
+    >,
+    pub olm_pk_signing_size: Result<unsafe extern "C" fn() -> usize, ::libloading::Error>,
+    pub olm_pk_signing: Result<
+        unsafe extern "C" fn(memory: *mut ::core::ffi::c_void) -> *mut OlmPkSigning,
+        ::libloading::Error,
+    >,
+    pub olm_pk_signing_last_error: Result<
+        unsafe extern "C" fn(sign: *const OlmPkSigning) -> *const ::core::ffi::c_char,
+        ::libloading::Error,
+    >,
+    pub olm_pk_signing_last_error_code: Result<
+        unsafe extern "C" fn(sign: *const OlmPkSigning) -> OlmErrorCode,
+        ::libloading::Error,
+    >,
+    pub olm_clear_pk_signing:
+        Result<unsafe extern "C" fn(sign: *mut OlmPkSigning) -> usize, ::libloading::Error>,
+    pub olm_pk_signing_key_from_seed: Result<
+        unsafe extern "C" fn(
+            sign: *mut OlmPkSigning,
+            pubkey: *mut ::core::ffi::c_void,
+            pubkey_length: usize,
+            seed: *const ::core::ffi::c_void,
+            seed_length: usize,
+        ) -> usize,
+        ::libloading::Error,
+    >,
+    pub olm_pk_signing_seed_length: Result<unsafe extern "C" fn() -> usize, ::libloading::Error>,
+    pub olm_pk_signing_public_key_length:
+        Result<unsafe extern "C" fn() -> usize, ::libloading::Error>,
+    pub olm_pk_signature_length: Result<unsafe extern "C" fn() -> usize, ::libloading::Error>,
+    pub olm_pk_sign: Result<
+        unsafe extern "C" fn(
+            sign: *mut OlmPkSigning,
+            message: *const u8,
+            message_length: usize,
+            signature: *mut u8,
+            signature_length: usize,
+        ) -> usize,
+        ::libloading::Error,
+    >,
+    pub _olm_encode_group_message_length: Result<
+        unsafe extern "C" fn(
+            chain_index: u32,
+            ciphertext_length: usize,
+            mac_length: usize,
+            signature_length: usize,
+        ) -> usize,
+        ::libloading::Error,
+    >,
+    pub _olm_encode_group_message: Result<
+        unsafe extern "C" fn(
+            version: u8,
+            message_index: u32,
+            ciphertext_length: usize,
+            output: *mut u8,
+            ciphertext_ptr: *mut *mut u8,
+        ) -> usize,
+        ::libloading::Error,
+    >,
+    pub _olm_decode_group_message: Result<
+        unsafe extern "C" fn(
+            input: *const u8,
+            input_length: usize,
+            mac_length: usize,
+            signature_length: usize,
+            results: *mut _OlmDecodeGroupMessageResults,
+        ),
+        ::libloading::Error,
+    >,
+    pub _olm_crypto_aes_encrypt_cbc_length:
+        Result<unsafe extern "C" fn(input_length: usize) -> usize, ::libloading::Error>,
+    pub _olm_crypto_aes_encrypt_cbc: Result<
+        unsafe extern "C" fn(
+            key: *const _olm_aes256_key,
+            iv: *const _olm_aes256_iv,
+            input: *const u8,
+            input_length: usize,
+            output: *mut u8,
+        ),
+        ::libloading::Error,
+    >,
+    pub _olm_crypto_aes_decrypt_cbc: Result<
+        unsafe extern "C" fn(
+            key: *const _olm_aes256_key,
+            iv: *const _olm_aes256_iv,
+            input: *const u8,
+            input_length: usize,
+            output: *mut u8,
+        ) -> usize,
+        ::libloading::Error,
+    >,
+    pub _olm_crypto_sha256: Result<
+        unsafe extern "C" fn(input: *const u8, input_length: usize, output: *mut u8),
+        ::libloading::Error,
+    >,
+    pub _olm_crypto_hmac_sha256: Result<
+        unsafe extern "C" fn(
+            key: *const u8,
+            key_length: usize,
+            input: *const u8,
+            input_length: usize,
+            output: *mut u8,
+        ),
+        ::libloading::Error,
+    >,
+    pub _olm_crypto_hkdf_sha256: Result<
+        unsafe extern "C" fn(
+            input: *const u8,
+            input_length: usize,
+            info: *const u8,
+            info_length: usize,
+            salt: *const u8,
+            salt_length: usize,
+            output: *mut u8,
+            output_length: usize,
+        ),
+        ::libloading::Error,
+    >,
+    pub _olm_crypto_curve25519_generate_key: Result<
+        unsafe extern "C" fn(random_32_bytes: *const u8, output: *mut _olm_curve25519_key_pair),
+        ::libloading::Error,
+    >,
+    pub _olm_crypto_curve25519_shared_secret: Result<
+        unsafe extern "C" fn(
+            our_key: *const _olm_curve25519_key_pair,
+            their_key: *const _olm_curve25519_public_key,
+            output: *mut u8,
+        ),
+        ::libloading::Error,
+    >,
+    pub _olm_crypto_ed25519_generate_key: Result<
+        unsafe extern "C" fn(random_bytes: *const u8, output: *mut _olm_ed25519_key_pair),
+        ::libloading::Error,
+    >,
+    pub _olm_crypto_ed25519_sign: Result<
+        unsafe extern "C" fn(
+            our_key: *const _olm_ed25519_key_pair,
+            message: *const u8,
+            message_length: usize,
+            output: *mut u8,
+        ),
+        ::libloading::Error,
+    >,
+    pub _olm_crypto_ed25519_verify: Result<
+        unsafe extern "C" fn(
+            their_key: *const _olm_ed25519_public_key,
+            message: *const u8,
+            message_length: usize,
+            signature: *const u8,
+        ) -> ::core::ffi::c_int,
+        ::libloading::Error,
+    >,
+    pub _olm_enc_output_length:
+        Result<unsafe extern "C" fn(raw_length: usize) -> usize, ::libloading::Error>,
+    pub _olm_enc_output_pos: Result<
+        unsafe extern "C" fn(output: *mut u8, raw_length: usize) -> *mut u8,
+        ::libloading::Error,
+    >,
+    pub _olm_enc_output: Result<
+        unsafe extern "C" fn(
+            key: *const u8,
+            key_length: usize,
+            pickle: *mut u8,
+            raw_length: usize,
+        ) -> usize,
+        ::libloading::Error,
+    >,
+    pub _olm_enc_input: Result<
+        unsafe extern "C" fn(
+            key: *const u8,
+            key_length: usize,
+            input: *mut u8,
+            b64_length: usize,
+            last_error: *mut OlmErrorCode,
+        ) -> usize,
+        ::libloading::Error,
+    >,
+    pub _olm_encode_base64_length:
+        Result<unsafe extern "C" fn(input_length: usize) -> usize, ::libloading::Error>,
+    pub _olm_encode_base64: Result<
+        unsafe extern "C" fn(input: *const u8, input_length: usize, output: *mut u8) -> usize,
+        ::libloading::Error,
+    >,
+    pub _olm_decode_base64_length:
+        Result<unsafe extern "C" fn(input_length: usize) -> usize, ::libloading::Error>,
+    pub _olm_decode_base64: Result<
+        unsafe extern "C" fn(input: *const u8, input_length: usize, output: *mut u8) -> usize,
+        ::libloading::Error,
+    >,
+}
+impl OlmLibrary {
+    pub unsafe fn new<P>(path: P) -> Result<Self, ::libloading::Error>
+    where
+        P: AsRef<::std::ffi::OsStr>,
+    {
+        let library = ::libloading::Library::new(path)?;
+        Self::from_library(library)
+    }
+    pub unsafe fn from_library<L>(library: L) -> Result<Self, ::libloading::Error>
+    where
+        L: Into<::libloading::Library>,
+    {
+        let __library = library.into();
+        let _olm_error_to_string = __library.get(b"_olm_error_to_string\0").map(|sym| *sym);
+        let olm_inbound_group_session_size = __library
+            .get(b"olm_inbound_group_session_size\0")
+            .map(|sym| *sym);
+        let olm_inbound_group_session = __library
+            .get(b"olm_inbound_group_session\0")
+            .map(|sym| *sym);
+        let olm_inbound_group_session_last_error = __library
+            .get(b"olm_inbound_group_session_last_error\0")
+            .map(|sym| *sym);
+        let olm_inbound_group_session_last_error_code = __library
+            .get(b"olm_inbound_group_session_last_error_code\0")
+            .map(|sym| *sym);
+        let olm_clear_inbound_group_session = __library
+            .get(b"olm_clear_inbound_group_session\0")
+            .map(|sym| *sym);
+        let olm_pickle_inbound_group_session_length = __library
+            .get(b"olm_pickle_inbound_group_session_length\0")
+            .map(|sym| *sym);
+        let olm_pickle_inbound_group_session = __library
+            .get(b"olm_pickle_inbound_group_session\0")
+            .map(|sym| *sym);
+        let olm_unpickle_inbound_group_session = __library
+            .get(b"olm_unpickle_inbound_group_session\0")
+            .map(|sym| *sym);
+        let olm_init_inbound_group_session = __library
+            .get(b"olm_init_inbound_group_session\0")
+            .map(|sym| *sym);
+        let olm_import_inbound_group_session = __library
+            .get(b"olm_import_inbound_group_session\0")
+            .map(|sym| *sym);
+        let olm_group_decrypt_max_plaintext_length = __library
+            .get(b"olm_group_decrypt_max_plaintext_length\0")
+            .map(|sym| *sym);
+        let olm_group_decrypt = __library.get(b"olm_group_decrypt\0").map(|sym| *sym);
+        let olm_inbound_group_session_id_length = __library
+            .get(b"olm_inbound_group_session_id_length\0")
+            .map(|sym| *sym);
+        let olm_inbound_group_session_id = __library
+            .get(b"olm_inbound_group_session_id\0")
+            .map(|sym| *sym);
+        let olm_inbound_group_session_first_known_index = __library
+            .get(b"olm_inbound_group_session_first_known_index\0")
+            .map(|sym| *sym);
+        let olm_inbound_group_session_is_verified = __library
+            .get(b"olm_inbound_group_session_is_verified\0")
+            .map(|sym| *sym);
+        let olm_export_inbound_group_session_length = __library
+            .get(b"olm_export_inbound_group_session_length\0")
+            .map(|sym| *sym);
+        let olm_export_inbound_group_session = __library
+            .get(b"olm_export_inbound_group_session\0")
+            .map(|sym| *sym);
+        let olm_outbound_group_session_size = __library
+            .get(b"olm_outbound_group_session_size\0")
+            .map(|sym| *sym);
+        let olm_outbound_group_session = __library
+            .get(b"olm_outbound_group_session\0")
+            .map(|sym| *sym);
+        let olm_outbound_group_session_last_error = __library
+            .get(b"olm_outbound_group_session_last_error\0")
+            .map(|sym| *sym);
+        let olm_outbound_group_session_last_error_code = __library
+            .get(b"olm_outbound_group_session_last_error_code\0")
+            .map(|sym| *sym);
+        let olm_clear_outbound_group_session = __library
+            .get(b"olm_clear_outbound_group_session\0")
+            .map(|sym| *sym);
+        let olm_pickle_outbound_group_session_length = __library
+            .get(b"olm_pickle_outbound_group_session_length\0")
+            .map(|sym| *sym);
+        let olm_pickle_outbound_group_session = __library
+            .get(b"olm_pickle_outbound_group_session\0")
+            .map(|sym| *sym);
+        let olm_unpickle_outbound_group_session = __library
+            .get(b"olm_unpickle_outbound_group_session\0")
+            .map(|sym| *sym);
+        let olm_init_outbound_group_session_random_length = __library
+            .get(b"olm_init_outbound_group_session_random_length\0")
+            .map(|sym| *sym);
+        let olm_init_outbound_group_session = __library
+            .get(b"olm_init_outbound_group_session\0")
+            .map(|sym| *sym);
+        let olm_group_encrypt_message_length = __library
+            .get(b"olm_group_encrypt_message_length\0")
+            .map(|sym| *sym);
+        let olm_group_encrypt = __library.get(b"olm_group_encrypt\0").map(|sym| *sym);
+        let olm_outbound_group_session_id_length = __library
+            .get(b"olm_outbound_group_session_id_length\0")
+            .map(|sym| *sym);
+        let olm_outbound_group_session_id = __library
+            .get(b"olm_outbound_group_session_id\0")
+            .map(|sym| *sym);
+        let olm_outbound_group_session_message_index = __library
+            .get(b"olm_outbound_group_session_message_index\0")
+            .map(|sym| *sym);
+        let olm_outbound_group_session_key_length = __library
+            .get(b"olm_outbound_group_session_key_length\0")
+            .map(|sym| *sym);
+        let olm_outbound_group_session_key = __library
+            .get(b"olm_outbound_group_session_key\0")
+            .map(|sym| *sym);
+        let olm_get_library_version = __library.get(b"olm_get_library_version\0").map(|sym| *sym);
+        let olm_account_size = __library.get(b"olm_account_size\0").map(|sym| *sym);
+        let olm_session_size = __library.get(b"olm_session_size\0").map(|sym| *sym);
+        let olm_utility_size = __library.get(b"olm_utility_size\0").map(|sym| *sym);
+        let olm_account = __library.get(b"olm_account\0").map(|sym| *sym);
+        let olm_session = __library.get(b"olm_session\0").map(|sym| *sym);
+        let olm_utility = __library.get(b"olm_utility\0").map(|sym| *sym);
+        let olm_error = __library.get(b"olm_error\0").map(|sym| *sym);
+        let olm_account_last_error = __library.get(b"olm_account_last_error\0").map(|sym| *sym);
+        let olm_account_last_error_code = __library
+            .get(b"olm_account_last_error_code\0")
+            .map(|sym| *sym);
+        let olm_session_last_error = __library.get(b"olm_session_last_error\0").map(|sym| *sym);
+        let olm_session_last_error_code = __library
+            .get(b"olm_session_last_error_code\0")
+            .map(|sym| *sym);
+        let olm_utility_last_error = __library.get(b"olm_utility_last_error\0").map(|sym| *sym);
+        let olm_utility_last_error_code = __library
+            .get(b"olm_utility_last_error_code\0")
+            .map(|sym| *sym);
+        let olm_clear_account = __library.get(b"olm_clear_account\0").map(|sym| *sym);
+        let olm_clear_session = __library.get(b"olm_clear_session\0").map(|sym| *sym);
+        let olm_clear_utility = __library.get(b"olm_clear_utility\0").map(|sym| *sym);
+        let olm_pickle_account_length = __library
+            .get(b"olm_pickle_account_length\0")
+            .map(|sym| *sym);
+        let olm_pickle_session_length = __library
+            .get(b"olm_pickle_session_length\0")
+            .map(|sym| *sym);
+        let olm_pickle_account = __library.get(b"olm_pickle_account\0").map(|sym| *sym);
+        let olm_pickle_session = __library.get(b"olm_pickle_session\0").map(|sym| *sym);
+        let olm_unpickle_account = __library.get(b"olm_unpickle_account\0").map(|sym| *sym);
+        let olm_unpickle_session = __library.get(b"olm_unpickle_session\0").map(|sym| *sym);
+        let olm_create_account_random_length = __library
+            .get(b"olm_create_account_random_length\0")
+            .map(|sym| *sym);
+        let olm_create_account = __library.get(b"olm_create_account\0").map(|sym| *sym);
+        let olm_account_identity_keys_length = __library
+            .get(b"olm_account_identity_keys_length\0")
+            .map(|sym| *sym);
+        let olm_account_identity_keys = __library
+            .get(b"olm_account_identity_keys\0")
+            .map(|sym| *sym);
+        let olm_account_signature_length = __library
+            .get(b"olm_account_signature_length\0")
+            .map(|sym| *sym);
+        let olm_account_sign = __library.get(b"olm_account_sign\0").map(|sym| *sym);
+        let olm_account_one_time_keys_length = __library
+            .get(b"olm_account_one_time_keys_length\0")
+            .map(|sym| *sym);
+        let olm_account_one_time_keys = __library
+            .get(b"olm_account_one_time_keys\0")
+            .map(|sym| *sym);
+        let olm_account_mark_keys_as_published = __library
+            .get(b"olm_account_mark_keys_as_published\0")
+            .map(|sym| *sym);
+        let olm_account_max_number_of_one_time_keys = __library
+            .get(b"olm_account_max_number_of_one_time_keys\0")
+            .map(|sym| *sym);
+        let olm_account_generate_one_time_keys_random_length = __library
+            .get(b"olm_account_generate_one_time_keys_random_length\0")
+            .map(|sym| *sym);
+        let olm_account_generate_one_time_keys = __library
+            .get(b"olm_account_generate_one_time_keys\0")
+            .map(|sym| *sym);
+        let olm_account_generate_fallback_key_random_length = __library
+            .get(b"olm_account_generate_fallback_key_random_length\0")
+            .map(|sym| *sym);
+        let olm_account_generate_fallback_key = __library
+            .get(b"olm_account_generate_fallback_key\0")
+            .map(|sym| *sym);
+        let olm_account_fallback_key_length = __library
+            .get(b"olm_account_fallback_key_length\0")
+            .map(|sym| *sym);
+        let olm_account_fallback_key = __library.get(b"olm_account_fallback_key\0").map(|sym| *sym);
+        let olm_account_unpublished_fallback_key_length = __library
+            .get(b"olm_account_unpublished_fallback_key_length\0")
+            .map(|sym| *sym);
+        let olm_account_unpublished_fallback_key = __library
+            .get(b"olm_account_unpublished_fallback_key\0")
+            .map(|sym| *sym);
+        let olm_account_forget_old_fallback_key = __library
+            .get(b"olm_account_forget_old_fallback_key\0")
+            .map(|sym| *sym);
+        let olm_create_outbound_session_random_length = __library
+            .get(b"olm_create_outbound_session_random_length\0")
+            .map(|sym| *sym);
+        let olm_create_outbound_session = __library
+            .get(b"olm_create_outbound_session\0")
+            .map(|sym| *sym);
+        let olm_create_inbound_session = __library
+            .get(b"olm_create_inbound_session\0")
+            .map(|sym| *sym);
+        let olm_create_inbound_session_from = __library
+            .get(b"olm_create_inbound_session_from\0")
+            .map(|sym| *sym);
+        let olm_session_id_length = __library.get(b"olm_session_id_length\0").map(|sym| *sym);
+        let olm_session_id = __library.get(b"olm_session_id\0").map(|sym| *sym);
+        let olm_session_has_received_message = __library
+            .get(b"olm_session_has_received_message\0")
+            .map(|sym| *sym);
+        let olm_session_describe = __library.get(b"olm_session_describe\0").map(|sym| *sym);
+        let olm_matches_inbound_session = __library
+            .get(b"olm_matches_inbound_session\0")
+            .map(|sym| *sym);
+        let olm_matches_inbound_session_from = __library
+            .get(b"olm_matches_inbound_session_from\0")
+            .map(|sym| *sym);
+        let olm_remove_one_time_keys = __library.get(b"olm_remove_one_time_keys\0").map(|sym| *sym);
+        let olm_encrypt_message_type = __library.get(b"olm_encrypt_message_type\0").map(|sym| *sym);
+        let olm_encrypt_random_length = __library
+            .get(b"olm_encrypt_random_length\0")
+            .map(|sym| *sym);
+        let olm_encrypt_message_length = __library
+            .get(b"olm_encrypt_message_length\0")
+            .map(|sym| *sym);
+        let olm_encrypt = __library.get(b"olm_encrypt\0").map(|sym| *sym);
+        let olm_decrypt_max_plaintext_length = __library
+            .get(b"olm_decrypt_max_plaintext_length\0")
+            .map(|sym| *sym);
+        let olm_decrypt = __library.get(b"olm_decrypt\0").map(|sym| *sym);
+        let olm_sha256_length = __library.get(b"olm_sha256_length\0").map(|sym| *sym);
+        let olm_sha256 = __library.get(b"olm_sha256\0").map(|sym| *sym);
+        let olm_ed25519_verify = __library.get(b"olm_ed25519_verify\0").map(|sym| *sym);
+        let olm_sas_last_error = __library.get(b"olm_sas_last_error\0").map(|sym| *sym);
+        let olm_sas_last_error_code = __library.get(b"olm_sas_last_error_code\0").map(|sym| *sym);
+        let olm_sas_size = __library.get(b"olm_sas_size\0").map(|sym| *sym);
+        let olm_sas = __library.get(b"olm_sas\0").map(|sym| *sym);
+        let olm_clear_sas = __library.get(b"olm_clear_sas\0").map(|sym| *sym);
+        let olm_create_sas_random_length = __library
+            .get(b"olm_create_sas_random_length\0")
+            .map(|sym| *sym);
+        let olm_create_sas = __library.get(b"olm_create_sas\0").map(|sym| *sym);
+        let olm_sas_pubkey_length = __library.get(b"olm_sas_pubkey_length\0").map(|sym| *sym);
+        let olm_sas_get_pubkey = __library.get(b"olm_sas_get_pubkey\0").map(|sym| *sym);
+        let olm_sas_set_their_key = __library.get(b"olm_sas_set_their_key\0").map(|sym| *sym);
+        let olm_sas_is_their_key_set = __library.get(b"olm_sas_is_their_key_set\0").map(|sym| *sym);
+        let olm_sas_generate_bytes = __library.get(b"olm_sas_generate_bytes\0").map(|sym| *sym);
+        let olm_sas_mac_length = __library.get(b"olm_sas_mac_length\0").map(|sym| *sym);
+        let olm_sas_calculate_mac = __library.get(b"olm_sas_calculate_mac\0").map(|sym| *sym);
+        let olm_sas_calculate_mac_fixed_base64 = __library
+            .get(b"olm_sas_calculate_mac_fixed_base64\0")
+            .map(|sym| *sym);
+        let olm_sas_calculate_mac_long_kdf = __library
+            .get(b"olm_sas_calculate_mac_long_kdf\0")
+            .map(|sym| *sym);
+        let olm_pk_encryption_size = __library.get(b"olm_pk_encryption_size\0").map(|sym| *sym);
+        let olm_pk_encryption = __library.get(b"olm_pk_encryption\0").map(|sym| *sym);
+        let olm_pk_encryption_last_error = __library
+            .get(b"olm_pk_encryption_last_error\0")
+            .map(|sym| *sym);
+        let olm_pk_encryption_last_error_code = __library
+            .get(b"olm_pk_encryption_last_error_code\0")
+            .map(|sym| *sym);
+        let olm_clear_pk_encryption = __library.get(b"olm_clear_pk_encryption\0").map(|sym| *sym);
+        let olm_pk_encryption_set_recipient_key = __library
+            .get(b"olm_pk_encryption_set_recipient_key\0")
+            .map(|sym| *sym);
+        let olm_pk_ciphertext_length = __library.get(b"olm_pk_ciphertext_length\0").map(|sym| *sym);
+        let olm_pk_mac_length = __library.get(b"olm_pk_mac_length\0").map(|sym| *sym);
+        let olm_pk_key_length = __library.get(b"olm_pk_key_length\0").map(|sym| *sym);
+        let olm_pk_encrypt_random_length = __library
+            .get(b"olm_pk_encrypt_random_length\0")
+            .map(|sym| *sym);
+        let olm_pk_encrypt = __library.get(b"olm_pk_encrypt\0").map(|sym| *sym);
+        let olm_pk_decryption_size = __library.get(b"olm_pk_decryption_size\0").map(|sym| *sym);
+        let olm_pk_decryption = __library.get(b"olm_pk_decryption\0").map(|sym| *sym);
+        let olm_pk_decryption_last_error = __library
+            .get(b"olm_pk_decryption_last_error\0")
+            .map(|sym| *sym);
+        let olm_pk_decryption_last_error_code = __library
+            .get(b"olm_pk_decryption_last_error_code\0")
+            .map(|sym| *sym);
+        let olm_clear_pk_decryption = __library.get(b"olm_clear_pk_decryption\0").map(|sym| *sym);
+        let olm_pk_private_key_length = __library
+            .get(b"olm_pk_private_key_length\0")
+            .map(|sym| *sym);
+        let olm_pk_generate_key_random_length = __library
+            .get(b"olm_pk_generate_key_random_length\0")
+            .map(|sym| *sym);
+        let olm_pk_key_from_private = __library.get(b"olm_pk_key_from_private\0").map(|sym| *sym);
+        let olm_pk_generate_key = __library.get(b"olm_pk_generate_key\0").map(|sym| *sym);
+        let olm_pickle_pk_decryption_length = __library
+            .get(b"olm_pickle_pk_decryption_length\0")
+            .map(|sym| *sym);
+        let olm_pickle_pk_decryption = __library.get(b"olm_pickle_pk_decryption\0").map(|sym| *sym);
+        let olm_unpickle_pk_decryption = __library
+            .get(b"olm_unpickle_pk_decryption\0")
+            .map(|sym| *sym);
+        let olm_pk_max_plaintext_length = __library
+            .get(b"olm_pk_max_plaintext_length\0")
+            .map(|sym| *sym);
+        let olm_pk_decrypt = __library.get(b"olm_pk_decrypt\0").map(|sym| *sym);
+        let olm_pk_get_private_key = __library.get(b"olm_pk_get_private_key\0").map(|sym| *sym);
+        let olm_pk_signing_size = __library.get(b"olm_pk_signing_size\0").map(|sym| *sym);
+        let olm_pk_signing = __library.get(b"olm_pk_signing\0").map(|sym| *sym);
+        let olm_pk_signing_last_error = __library
+            .get(b"olm_pk_signing_last_error\0")
+            .map(|sym| *sym);
+        let olm_pk_signing_last_error_code = __library
+            .get(b"olm_pk_signing_last_error_code\0")
+            .map(|sym| *sym);
+        let olm_clear_pk_signing = __library.get(b"olm_clear_pk_signing\0").map(|sym| *sym);
+        let olm_pk_signing_key_from_seed = __library
+            .get(b"olm_pk_signing_key_from_seed\0")
+            .map(|sym| *sym);
+        let olm_pk_signing_seed_length = __library
+            .get(b"olm_pk_signing_seed_length\0")
+            .map(|sym| *sym);
+        let olm_pk_signing_public_key_length = __library
+            .get(b"olm_pk_signing_public_key_length\0")
+            .map(|sym| *sym);
+        let olm_pk_signature_length = __library.get(b"olm_pk_signature_length\0").map(|sym| *sym);
+        let olm_pk_sign = __library.get(b"olm_pk_sign\0").map(|sym| *sym);
+        let _olm_encode_group_message_length = __library
+            .get(b"_olm_encode_group_message_length\0")
+            .map(|sym| *sym);
+        let _olm_encode_group_message = __library
+            .get(b"_olm_encode_group_message\0")
+            .map(|sym| *sym);
+        let _olm_decode_group_message = __library
+            .get(b"_olm_decode_group_message\0")
+            .map(|sym| *sym);
+        let _olm_crypto_aes_encrypt_cbc_length = __library
+            .get(b"_olm_crypto_aes_encrypt_cbc_length\0")
+            .map(|sym| *sym);
+        let _olm_crypto_aes_encrypt_cbc = __library
+            .get(b"_olm_crypto_aes_encrypt_cbc\0")
+            .map(|sym| *sym);
+        let _olm_crypto_aes_decrypt_cbc = __library
+            .get(b"_olm_crypto_aes_decrypt_cbc\0")
+            .map(|sym| *sym);
+        let _olm_crypto_sha256 = __library.get(b"_olm_crypto_sha256\0").map(|sym| *sym);
+        let _olm_crypto_hmac_sha256 = __library.get(b"_olm_crypto_hmac_sha256\0").map(|sym| *sym);
+        let _olm_crypto_hkdf_sha256 = __library.get(b"_olm_crypto_hkdf_sha256\0").map(|sym| *sym);
+        let _olm_crypto_curve25519_generate_key = __library
+            .get(b"_olm_crypto_curve25519_generate_key\0")
+            .map(|sym| *sym);
+        let _olm_crypto_curve25519_shared_secret = __library
+            .get(b"_olm_crypto_curve25519_shared_secret\0")
+            .map(|sym| *sym);
+        let _olm_crypto_ed25519_generate_key = __library
+            .get(b"_olm_crypto_ed25519_generate_key\0")
+            .map(|sym| *sym);
+        let _olm_crypto_ed25519_sign = __library.get(b"_olm_crypto_ed25519_sign\0").map(|sym| *sym);
+        let _olm_crypto_ed25519_verify = __library
+            .get(b"_olm_crypto_ed25519_verify\0")
+            .map(|sym| *sym);
+        let _olm_enc_output_length = __library.get(b"_olm_enc_output_length\0").map(|sym| *sym);
+        let _olm_enc_output_pos = __library.get(b"_olm_enc_output_pos\0").map(|sym| *sym);
+        let _olm_enc_output = __library.get(b"_olm_enc_output\0").map(|sym| *sym);
+        let _olm_enc_input = __library.get(b"_olm_enc_input\0").map(|sym| *sym);
+        let _olm_encode_base64_length = __library
+            .get(b"_olm_encode_base64_length\0")
+            .map(|sym| *sym);
+        let _olm_encode_base64 = __library.get(b"_olm_encode_base64\0").map(|sym| *sym);
+        let _olm_decode_base64_length = __library
+            .get(b"_olm_decode_base64_length\0")
+            .map(|sym| *sym);
+        let _olm_decode_base64 = __library.get(b"_olm_decode_base64\0").map(|sym| *sym);
+        Ok(OlmLibrary {
+            __library,
+            _olm_error_to_string,
+            olm_inbound_group_session_size,
+            olm_inbound_group_session,
+            olm_inbound_group_session_last_error,
+            olm_inbound_group_session_last_error_code,
+            olm_clear_inbound_group_session,
+            olm_pickle_inbound_group_session_length,
+            olm_pickle_inbound_group_session,
+            olm_unpickle_inbound_group_session,
+            olm_init_inbound_group_session,
+            olm_import_inbound_group_session,
+            olm_group_decrypt_max_plaintext_length,
+            olm_group_decrypt,
+            olm_inbound_group_session_id_length,
+            olm_inbound_group_session_id,
+            olm_inbound_group_session_first_known_index,
+            olm_inbound_group_session_is_verified,
+            olm_export_inbound_group_session_length,
+            olm_export_inbound_group_session,
+            olm_outbound_group_session_size,
+            olm_outbound_group_session,
+            olm_outbound_group_session_last_error,
+            olm_outbound_group_session_last_error_code,
+            olm_clear_outbound_group_session,
+            olm_pickle_outbound_group_session_length,
+            olm_pickle_outbound_group_session,
+            olm_unpickle_outbound_group_session,
+            olm_init_outbound_group_session_random_length,
+            olm_init_outbound_group_session,
+            olm_group_encrypt_message_length,
+            olm_group_encrypt,
+            olm_outbound_group_session_id_length,
+            olm_outbound_group_session_id,
+            olm_outbound_group_session_message_index,
+            olm_outbound_group_session_key_length,
+            olm_outbound_group_session_key,
+            olm_get_library_version,
+            olm_account_size,
+            olm_session_size,
+            olm_utility_size,
+            olm_account,
+            olm_session,
+            olm_utility,
+            olm_error,
+            olm_account_last_error,
+            olm_account_last_error_code,
+            olm_session_last_error,
+            olm_session_last_error_code,
+            olm_utility_last_error,
+            olm_utility_last_error_code,
+            olm_clear_account,
+            olm_clear_session,
+            olm_clear_utility,
+            olm_pickle_account_length,
+            olm_pickle_session_length,
+            olm_pickle_account,
+            olm_pickle_session,
+            olm_unpickle_account,
+            olm_unpickle_session,
+            olm_create_account_random_length,
+            olm_create_account,
+            olm_account_identity_keys_length,
+            olm_account_identity_keys,
+            olm_account_signature_length,
+            olm_account_sign,
+            olm_account_one_time_keys_length,
+            olm_account_one_time_keys,
+            olm_account_mark_keys_as_published,
+            olm_account_max_number_of_one_time_keys,
+            olm_account_generate_one_time_keys_random_length,
+            olm_account_generate_one_time_keys,
+            olm_account_generate_fallback_key_random_length,
+            olm_account_generate_fallback_key,
+            olm_account_fallback_key_length,
+            olm_account_fallback_key,
+            olm_account_unpublished_fallback_key_length,
+            olm_account_unpublished_fallback_key,
+            olm_account_forget_old_fallback_key,
+            olm_create_outbound_session_random_length,
+            olm_create_outbound_session,
+            olm_create_inbound_session,
+            olm_create_inbound_session_from,
+            olm_session_id_length,
+            olm_session_id,
+            olm_session_has_received_message,
+            olm_session_describe,
+            olm_matches_inbound_session,
+            olm_matches_inbound_session_from,
+            olm_remove_one_time_keys,
+            olm_encrypt_message_type,
+            olm_encrypt_random_length,
+            olm_encrypt_message_length,
+            olm_encrypt,
+            olm_decrypt_max_plaintext_length,
+            olm_decrypt,
+            olm_sha256_length,
+            olm_sha256,
+            olm_ed25519_verify,
+            olm_sas_last_error,
+            olm_sas_last_error_code,
+            olm_sas_size,
+            olm_sas,
+            olm_clear_sas,
+            olm_create_sas_random_length,
+            olm_create_sas,
+            olm_sas_pubkey_length,
+            olm_sas_get_pubkey,
+            olm_sas_set_their_key,
+            olm_sas_is_their_key_set,
+            olm_sas_generate_bytes,
+            olm_sas_mac_length,
+            olm_sas_calculate_mac,
+            olm_sas_calculate_mac_fixed_base64,
+            olm_sas_calculate_mac_long_kdf,
+            olm_pk_encryption_size,
+            olm_pk_encryption,
+            olm_pk_encryption_last_error,
+            olm_pk_encryption_last_error_code,
+            olm_clear_pk_encryption,
+            olm_pk_encryption_set_recipient_key,
+            olm_pk_ciphertext_length,
+            olm_pk_mac_length,
+            olm_pk_key_length,
+            olm_pk_encrypt_random_length,
+            olm_pk_encrypt,
+            olm_pk_decryption_size,
+            olm_pk_decryption,
+            olm_pk_decryption_last_error,
+            olm_pk_decryption_last_error_code,
+            olm_clear_pk_decryption,
+            olm_pk_private_key_length,
+            olm_pk_generate_key_random_length,
+            olm_pk_key_from_private,
+            olm_pk_generate_key,
+            olm_pickle_pk_decryption_length,
+            olm_pickle_pk_decryption,
+            olm_unpickle_pk_decryption,
+            olm_pk_max_plaintext_length,
+            olm_pk_decrypt,
+            olm_pk_get_private_key,
+            olm_pk_signing_size,
+            olm_pk_signing,
+            olm_pk_signing_last_error,
+            olm_pk_signing_last_error_code,
+            olm_clear_pk_signing,
+            olm_pk_signing_key_from_seed,
+            olm_pk_signing_seed_length,
+            olm_pk_signing_public_key_length,
+            olm_pk_signature_length,
+            olm_pk_sign,
+            _olm_encode_group_message_length,
+            _olm_encode_group_message,
+            _olm_decode_group_message,
+            _olm_crypto_aes_encrypt_cbc_length,
+            _olm_crypto_aes_encrypt_cbc,
+            _olm_crypto_aes_decrypt_cbc,
+            _olm_crypto_sha256,
+            _olm_crypto_hmac_sha256,
+            _olm_crypto_hkdf_sha256,
+            _olm_crypto_curve25519_generate_key,
+            _olm_crypto_curve25519_shared_secret,
+            _olm_crypto_ed25519_generate_key,
+            _olm_crypto_ed25519_sign,
+            _olm_crypto_ed25519_verify,
+            _olm_enc_output_length,
+            _olm_enc_output_pos,
+            _olm_enc_output,
+            _olm_enc_input,
+            _olm_encode_base64_length,
+            _olm_encode_base64,
+            _olm_decode_base64_length,
+            _olm_decode_base64,
+        })
+    }
+    pub unsafe fn _olm_error_to_string(&self, error: OlmErrorCode) -> *const ::core::ffi::c_char {
+        (self
+            ._olm_error_to_string
+            .as_ref()
+            .expect("Expected function, got error."))(error)
+    }
+    pub unsafe fn olm_inbound_group_session_size(&self) -> usize {
+        (self
+            .olm_inbound_group_session_size
+            .as_ref()
+            .expect("Expected function, got error."))()
+    }
+    pub unsafe fn olm_inbound_group_session(
+        &self,
+        memory: *mut ::core::ffi::c_void,
+    ) -> *mut OlmInboundGroupSession {
+        (self
+            .olm_inbound_group_session
+            .as_ref()
+            .expect("Expected function, got error."))(memory)
+    }
+    pub unsafe fn olm_inbound_group_session_last_error(
+        &self,
+        session: *const OlmInboundGroupSession,
+    ) -> *const ::core::ffi::c_char {
+        (self
+            .olm_inbound_group_session_last_error
+            .as_ref()
+            .expect("Expected function, got error."))(session)
+    }
+    pub unsafe fn olm_inbound_group_session_last_error_code(
+        &self,
+        session: *const OlmInboundGroupSession,
+    ) -> OlmErrorCode {
+        (self
+            .olm_inbound_group_session_last_error_code
+            .as_ref()
+            .expect("Expected function, got error."))(session)
+    }
+    pub unsafe fn olm_clear_inbound_group_session(
+        &self,
+        session: *mut OlmInboundGroupSession,
+    ) -> usize {
+        (self
+            .olm_clear_inbound_group_session
+            .as_ref()
+            .expect("Expected function, got error."))(session)
+    }
+    pub unsafe fn olm_pickle_inbound_group_session_length(
+        &self,
+        session: *const OlmInboundGroupSession,
+    ) -> usize {
+        (self
+            .olm_pickle_inbound_group_session_length
+            .as_ref()
+            .expect("Expected function, got error."))(session)
+    }
+    pub unsafe fn olm_pickle_inbound_group_session(
+        &self,
+        session: *mut OlmInboundGroupSession,
+        key: *const ::core::ffi::c_void,
+        key_length: usize,
+        pickled: *mut ::core::ffi::c_void,
+        pickled_length: usize,
+    ) -> usize {
+        (self
+            .olm_pickle_inbound_group_session
+            .as_ref()
+            .expect("Expected function, got error."))(
+            session,
+            key,
+            key_length,
+            pickled,
+            pickled_length,
+        )
+    }
+    pub unsafe fn olm_unpickle_inbound_group_session(
+        &self,
+        session: *mut OlmInboundGroupSession,
+        key: *const ::core::ffi::c_void,
+        key_length: usize,
+        pickled: *mut ::core::ffi::c_void,
+        pickled_length: usize,
+    ) -> usize {
+        (self
+            .olm_unpickle_inbound_group_session
+            .as_ref()
+            .expect("Expected function, got error."))(
+            session,
+            key,
+            key_length,
+            pickled,
+            pickled_length,
+        )
+    }
+    pub unsafe fn olm_init_inbound_group_session(
+        &self,
+        session: *mut OlmInboundGroupSession,
+        session_key: *const u8,
+        session_key_length: usize,
+    ) -> usize {
+        (self
+            .olm_init_inbound_group_session
+            .as_ref()
+            .expect("Expected function, got error."))(
+            session, session_key, session_key_length
+        )
+    }
+    pub unsafe fn olm_import_inbound_group_session(
+        &self,
+        session: *mut OlmInboundGroupSession,
+        session_key: *const u8,
+        session_key_length: usize,
+    ) -> usize {
+        (self
+            .olm_import_inbound_group_session
+            .as_ref()
+            .expect("Expected function, got error."))(
+            session, session_key, session_key_length
+        )
+    }
+    pub unsafe fn olm_group_decrypt_max_plaintext_length(
+        &self,
+        session: *mut OlmInboundGroupSession,
+        message: *mut u8,
+        message_length: usize,
+    ) -> usize {
+        (self
+            .olm_group_decrypt_max_plaintext_length
+            .as_ref()
+            .expect("Expected function, got error."))(session, message, message_length)
+    }
+    pub unsafe fn olm_group_decrypt(
+        &self,
+        session: *mut OlmInboundGroupSession,
+        message: *mut u8,
+        message_length: usize,
+        plaintext: *mut u8,
+        max_plaintext_length: usize,
+        message_index: *mut u32,
+    ) -> usize {
+        (self
+            .olm_group_decrypt
+            .as_ref()
+            .expect("Expected function, got error."))(
+            session,
+            message,
+            message_length,
+            plaintext,
+            max_plaintext_length,
+            message_index,
+        )
+    }
+    pub unsafe fn olm_inbound_group_session_id_length(
+        &self,
+        session: *const OlmInboundGroupSession,
+    ) -> usize {
+        (self
+            .olm_inbound_group_session_id_length
+            .as_ref()
+            .expect("Expected function, got error."))(session)
+    }
+    pub unsafe fn olm_inbound_group_session_id(
+        &self,
+        session: *mut OlmInboundGroupSession,
+        id: *mut u8,
+        id_length: usize,
+    ) -> usize {
+        (self
+            .olm_inbound_group_session_id
+            .as_ref()
+            .expect("Expected function, got error."))(session, id, id_length)
+    }
+    pub unsafe fn olm_inbound_group_session_first_known_index(
+        &self,
+        session: *const OlmInboundGroupSession,
+    ) -> u32 {
+        (self
+            .olm_inbound_group_session_first_known_index
+            .as_ref()
+            .expect("Expected function, got error."))(session)
+    }
+    pub unsafe fn olm_inbound_group_session_is_verified(
+        &self,
+        session: *const OlmInboundGroupSession,
+    ) -> ::core::ffi::c_int {
+        (self
+            .olm_inbound_group_session_is_verified
+            .as_ref()
+            .expect("Expected function, got error."))(session)
+    }
+    pub unsafe fn olm_export_inbound_group_session_length(
+        &self,
+        session: *const OlmInboundGroupSession,
+    ) -> usize {
+        (self
+            .olm_export_inbound_group_session_length
+            .as_ref()
+            .expect("Expected function, got error."))(session)
+    }
+    pub unsafe fn olm_export_inbound_group_session(
+        &self,
+        session: *mut OlmInboundGroupSession,
+        key: *mut u8,
+        key_length: usize,
+        message_index: u32,
+    ) -> usize {
+        (self
+            .olm_export_inbound_group_session
+            .as_ref()
+            .expect("Expected function, got error."))(
+            session, key, key_length, message_index
+        )
+    }
+    pub unsafe fn olm_outbound_group_session_size(&self) -> usize {
+        (self
+            .olm_outbound_group_session_size
+            .as_ref()
+            .expect("Expected function, got error."))()
+    }
+    pub unsafe fn olm_outbound_group_session(
+        &self,
+        memory: *mut ::core::ffi::c_void,
+    ) -> *mut OlmOutboundGroupSession {
+        (self
+            .olm_outbound_group_session
+            .as_ref()
+            .expect("Expected function, got error."))(memory)
+    }
+    pub unsafe fn olm_outbound_group_session_last_error(
+        &self,
+        session: *const OlmOutboundGroupSession,
+    ) -> *const ::core::ffi::c_char {
+        (self
+            .olm_outbound_group_session_last_error
+            .as_ref()
+            .expect("Expected function, got error."))(session)
+    }
+    pub unsafe fn olm_outbound_group_session_last_error_code(
+        &self,
+        session: *const OlmOutboundGroupSession,
+    ) -> OlmErrorCode {
+        (self
+            .olm_outbound_group_session_last_error_code
+            .as_ref()
+            .expect("Expected function, got error."))(session)
+    }
+    pub unsafe fn olm_clear_outbound_group_session(
+        &self,
+        session: *mut OlmOutboundGroupSession,
+    ) -> usize {
+        (self
+            .olm_clear_outbound_group_session
+            .as_ref()
+            .expect("Expected function, got error."))(session)
+    }
+    pub unsafe fn olm_pickle_outbound_group_session_length(
+        &self,
+        session: *const OlmOutboundGroupSession,
+    ) -> usize {
+        (self
+            .olm_pickle_outbound_group_session_length
+            .as_ref()
+            .expect("Expected function, got error."))(session)
+    }
+    pub unsafe fn olm_pickle_outbound_group_session(
+        &self,
+        session: *mut OlmOutboundGroupSession,
+        key: *const ::core::ffi::c_void,
+        key_length: usize,
+        pickled: *mut ::core::ffi::c_void,
+        pickled_length: usize,
+    ) -> usize {
+        (self
+            .olm_pickle_outbound_group_session
+            .as_ref()
+            .expect("Expected function, got error."))(
+            session,
+            key,
+            key_length,
+            pickled,
+            pickled_length,
+        )
+    }
+    pub unsafe fn olm_unpickle_outbound_group_session(
+        &self,
+        session: *mut OlmOutboundGroupSession,
+        key: *const ::core::ffi::c_void,
+        key_length: usize,
+        pickled: *mut ::core::ffi::c_void,
+        pickled_length: usize,
+    ) -> usize {
+        (self
+            .olm_unpickle_outbound_group_session
+            .as_ref()
+            .expect("Expected function, got error."))(
+            session,
+            key,
+            key_length,
+            pickled,
+            pickled_length,
+        )
+    }
+    pub unsafe fn olm_init_outbound_group_session_random_length(
+        &self,
+        session: *const OlmOutboundGroupSession,
+    ) -> usize {
+        (self
+            .olm_init_outbound_group_session_random_length
+            .as_ref()
+            .expect("Expected function, got error."))(session)
+    }
+    pub unsafe fn olm_init_outbound_group_session(
+        &self,
+        session: *mut OlmOutboundGroupSession,
+        random: *mut u8,
+        random_length: usize,
+    ) -> usize {
+        (self
+            .olm_init_outbound_group_session
+            .as_ref()
+            .expect("Expected function, got error."))(session, random, random_length)
+    }
+    pub unsafe fn olm_group_encrypt_message_length(
+        &self,
+        session: *mut OlmOutboundGroupSession,
+        plaintext_length: usize,
+    ) -> usize {
+        (self
+            .olm_group_encrypt_message_length
+            .as_ref()
+            .expect("Expected function, got error."))(session, plaintext_length)
+    }
+    pub unsafe fn olm_group_encrypt(
+        &self,
+        session: *mut OlmOutboundGroupSession,
+        plaintext: *const u8,
+        plaintext_length: usize,
+        message: *mut u8,
+        message_length: usize,
+    ) -> usize {
+        (self
+            .olm_group_encrypt
+            .as_ref()
+            .expect("Expected function, got error."))(
+            session,
+            plaintext,
+            plaintext_length,
+            message,
+            message_length,
+        )
+    }
+    pub unsafe fn olm_outbound_group_session_id_length(
+        &self,
+        session: *const OlmOutboundGroupSession,
+    ) -> usize {
+        (self
+            .olm_outbound_group_session_id_length
+            .as_ref()
+            .expect("Expected function, got error."))(session)
+    }
+    pub unsafe fn olm_outbound_group_session_id(
+        &self,
+        session: *mut OlmOutboundGroupSession,
+        id: *mut u8,
+        id_length: usize,
+    ) -> usize {
+        (self
+            .olm_outbound_group_session_id
+            .as_ref()
+            .expect("Expected function, got error."))(session, id, id_length)
+    }
+    pub unsafe fn olm_outbound_group_session_message_index(
+        &self,
+        session: *mut OlmOutboundGroupSession,
+    ) -> u32 {
+        (self
+            .olm_outbound_group_session_message_index
+            .as_ref()
+            .expect("Expected function, got error."))(session)
+    }
+    pub unsafe fn olm_outbound_group_session_key_length(
+        &self,
+        session: *const OlmOutboundGroupSession,
+    ) -> usize {
+        (self
+            .olm_outbound_group_session_key_length
+            .as_ref()
+            .expect("Expected function, got error."))(session)
+    }
+    pub unsafe fn olm_outbound_group_session_key(
+        &self,
+        session: *mut OlmOutboundGroupSession,
+        key: *mut u8,
+        key_length: usize,
+    ) -> usize {
+        (self
+            .olm_outbound_group_session_key
+            .as_ref()
+            .expect("Expected function, got error."))(session, key, key_length)
+    }
+    pub unsafe fn olm_get_library_version(&self, major: *mut u8, minor: *mut u8, patch: *mut u8) {
+        (self
+            .olm_get_library_version
+            .as_ref()
+            .expect("Expected function, got error."))(major, minor, patch)
+    }
+    pub unsafe fn olm_account_size(&self) -> usize {
+        (self
+            .olm_account_size
+            .as_ref()
+            .expect("Expected function, got error."))()
+    }
+    pub unsafe fn olm_session_size(&self) -> usize {
+        (self
+            .olm_session_size
+            .as_ref()
+            .expect("Expected function, got error."))()
+    }
+    pub unsafe fn olm_utility_size(&self) -> usize {
+        (self
+            .olm_utility_size
+            .as_ref()
+            .expect("Expected function, got error."))()
+    }
+    pub unsafe fn olm_account(&self, memory: *mut ::core::ffi::c_void) -> *mut OlmAccount {
+        (self
+            .olm_account
+            .as_ref()
+            .expect("Expected function, got error."))(memory)
+    }
+    pub unsafe fn olm_session(&self, memory: *mut ::core::ffi::c_void) -> *mut OlmSession {
+        (self
+            .olm_session
+            .as_ref()
+            .expect("Expected function, got error."))(memory)
+    }
+    pub unsafe fn olm_utility(&self, memory: *mut ::core::ffi::c_void) -> *mut OlmUtility {
+        (self
+            .olm_utility
+            .as_ref()
+            .expect("Expected function, got error."))(memory)
+    }
+    pub unsafe fn olm_error(&self) -> usize {
+        (self
+            .olm_error
+            .as_ref()
+            .expect("Expected function, got error."))()
+    }
+    pub unsafe fn olm_account_last_error(
+        &self,
+        account: *const OlmAccount,
+    ) -> *const ::core::ffi::c_char {
+        (self
+            .olm_account_last_error
+            .as_ref()
+            .expect("Expected function, got error."))(account)
+    }
+    pub unsafe fn olm_account_last_error_code(&self, account: *const OlmAccount) -> OlmErrorCode {
+        (self
+            .olm_account_last_error_code
+            .as_ref()
+            .expect("Expected function, got error."))(account)
+    }
+    pub unsafe fn olm_session_last_error(
+        &self,
+        session: *const OlmSession,
+    ) -> *const ::core::ffi::c_char {
+        (self
+            .olm_session_last_error
+            .as_ref()
+            .expect("Expected function, got error."))(session)
+    }
+    pub unsafe fn olm_session_last_error_code(&self, session: *const OlmSession) -> OlmErrorCode {
+        (self
+            .olm_session_last_error_code
+            .as_ref()
+            .expect("Expected function, got error."))(session)
+    }
+    pub unsafe fn olm_utility_last_error(
+        &self,
+        utility: *const OlmUtility,
+    ) -> *const ::core::ffi::c_char {
+        (self
+            .olm_utility_last_error
+            .as_ref()
+            .expect("Expected function, got error."))(utility)
+    }
+    pub unsafe fn olm_utility_last_error_code(&self, utility: *const OlmUtility) -> OlmErrorCode {
+        (self
+            .olm_utility_last_error_code
+            .as_ref()
+            .expect("Expected function, got error."))(utility)
+    }
+    pub unsafe fn olm_clear_account(&self, account: *mut OlmAccount) -> usize {
+        (self
+            .olm_clear_account
+            .as_ref()
+            .expect("Expected function, got error."))(account)
+    }
+    pub unsafe fn olm_clear_session(&self, session: *mut OlmSession) -> usize {
+        (self
+            .olm_clear_session
+            .as_ref()
+            .expect("Expected function, got error."))(session)
+    }
+    pub unsafe fn olm_clear_utility(&self, utility: *mut OlmUtility) -> usize {
+        (self
+            .olm_clear_utility
+            .as_ref()
+            .expect("Expected function, got error."))(utility)
+    }
+    pub unsafe fn olm_pickle_account_length(&self, account: *const OlmAccount) -> usize {
+        (self
+            .olm_pickle_account_length
+            .as_ref()
+            .expect("Expected function, got error."))(account)
+    }
+    pub unsafe fn olm_pickle_session_length(&self, session: *const OlmSession) -> usize {
+        (self
+            .olm_pickle_session_length
+            .as_ref()
+            .expect("Expected function, got error."))(session)
+    }
+    pub unsafe fn olm_pickle_account(
+        &self,
+        account: *mut OlmAccount,
+        key: *const ::core::ffi::c_void,
+        key_length: usize,
+        pickled: *mut ::core::ffi::c_void,
+        pickled_length: usize,
+    ) -> usize {
+        (self
+            .olm_pickle_account
+            .as_ref()
+            .expect("Expected function, got error."))(
+            account,
+            key,
+            key_length,
+            pickled,
+            pickled_length,
+        )
+    }
+    pub unsafe fn olm_pickle_session(
+        &self,
+        session: *mut OlmSession,
+        key: *const ::core::ffi::c_void,
+        key_length: usize,
+        pickled: *mut ::core::ffi::c_void,
+        pickled_length: usize,
+    ) -> usize {
+        (self
+            .olm_pickle_session
+            .as_ref()
+            .expect("Expected function, got error."))(
+            session,
+            key,
+            key_length,
+            pickled,
+            pickled_length,
+        )
+    }
+    pub unsafe fn olm_unpickle_account(
+        &self,
+        account: *mut OlmAccount,
+        key: *const ::core::ffi::c_void,
+        key_length: usize,
+        pickled: *mut ::core::ffi::c_void,
+        pickled_length: usize,
+    ) -> usize {
+        (self
+            .olm_unpickle_account
+            .as_ref()
+            .expect("Expected function, got error."))(
+            account,
+            key,
+            key_length,
+            pickled,
+            pickled_length,
+        )
+    }
+    pub unsafe fn olm_unpickle_session(
+        &self,
+        session: *mut OlmSession,
+        key: *const ::core::ffi::c_void,
+        key_length: usize,
+        pickled: *mut ::core::ffi::c_void,
+        pickled_length: usize,
+    ) -> usize {
+        (self
+            .olm_unpickle_session
+            .as_ref()
+            .expect("Expected function, got error."))(
+            session,
+            key,
+            key_length,
+            pickled,
+            pickled_length,
+        )
+    }
+    pub unsafe fn olm_create_account_random_length(&self, account: *const OlmAccount) -> usize {
+        (self
+            .olm_create_account_random_length
+            .as_ref()
+            .expect("Expected function, got error."))(account)
+    }
+    pub unsafe fn olm_create_account(
+        &self,
+        account: *mut OlmAccount,
+        random: *mut ::core::ffi::c_void,
+        random_length: usize,
+    ) -> usize {
+        (self
+            .olm_create_account
+            .as_ref()
+            .expect("Expected function, got error."))(account, random, random_length)
+    }
+    pub unsafe fn olm_account_identity_keys_length(&self, account: *const OlmAccount) -> usize {
+        (self
+            .olm_account_identity_keys_length
+            .as_ref()
+            .expect("Expected function, got error."))(account)
+    }
+    pub unsafe fn olm_account_identity_keys(
+        &self,
+        account: *mut OlmAccount,
+        identity_keys: *mut ::core::ffi::c_void,
+        identity_key_length: usize,
+    ) -> usize {
+        (self
+            .olm_account_identity_keys
+            .as_ref()
+            .expect("Expected function, got error."))(
+            account, identity_keys, identity_key_length
+        )
+    }
+    pub unsafe fn olm_account_signature_length(&self, account: *const OlmAccount) -> usize {
+        (self
+            .olm_account_signature_length
+            .as_ref()
+            .expect("Expected function, got error."))(account)
+    }
+    pub unsafe fn olm_account_sign(
+        &self,
+        account: *mut OlmAccount,
+        message: *const ::core::ffi::c_void,
+        message_length: usize,
+        signature: *mut ::core::ffi::c_void,
+        signature_length: usize,
+    ) -> usize {
+        (self
+            .olm_account_sign
+            .as_ref()
+            .expect("Expected function, got error."))(
+            account,
+            message,
+            message_length,
+            signature,
+            signature_length,
+        )
+    }
+    pub unsafe fn olm_account_one_time_keys_length(&self, account: *const OlmAccount) -> usize {
+        (self
+            .olm_account_one_time_keys_length
+            .as_ref()
+            .expect("Expected function, got error."))(account)
+    }
+    pub unsafe fn olm_account_one_time_keys(
+        &self,
+        account: *mut OlmAccount,
+        one_time_keys: *mut ::core::ffi::c_void,
+        one_time_keys_length: usize,
+    ) -> usize {
+        (self
+            .olm_account_one_time_keys
+            .as_ref()
+            .expect("Expected function, got error."))(
+            account, one_time_keys, one_time_keys_length
+        )
+    }
+    pub unsafe fn olm_account_mark_keys_as_published(&self, account: *mut OlmAccount) -> usize {
+        (self
+            .olm_account_mark_keys_as_published
+            .as_ref()
+            .expect("Expected function, got error."))(account)
+    }
+    pub unsafe fn olm_account_max_number_of_one_time_keys(
+        &self,
+        account: *const OlmAccount,
+    ) -> usize {
+        (self
+            .olm_account_max_number_of_one_time_keys
+            .as_ref()
+            .expect("Expected function, got error."))(account)
+    }
+    pub unsafe fn olm_account_generate_one_time_keys_random_length(
+        &self,
+        account: *const OlmAccount,
+        number_of_keys: usize,
+    ) -> usize {
+        (self
+            .olm_account_generate_one_time_keys_random_length
+            .as_ref()
+            .expect("Expected function, got error."))(account, number_of_keys)
+    }
+    pub unsafe fn olm_account_generate_one_time_keys(
+        &self,
+        account: *mut OlmAccount,
+        number_of_keys: usize,
+        random: *mut ::core::ffi::c_void,
+        random_length: usize,
+    ) -> usize {
+        (self
+            .olm_account_generate_one_time_keys
+            .as_ref()
+            .expect("Expected function, got error."))(
+            account,
+            number_of_keys,
+            random,
+            random_length,
+        )
+    }
+    pub unsafe fn olm_account_generate_fallback_key_random_length(
+        &self,
+        account: *const OlmAccount,
+    ) -> usize {
+        (self
+            .olm_account_generate_fallback_key_random_length
+            .as_ref()
+            .expect("Expected function, got error."))(account)
+    }
+    pub unsafe fn olm_account_generate_fallback_key(
+        &self,
+        account: *mut OlmAccount,
+        random: *mut ::core::ffi::c_void,
+        random_length: usize,
+    ) -> usize {
+        (self
+            .olm_account_generate_fallback_key
+            .as_ref()
+            .expect("Expected function, got error."))(account, random, random_length)
+    }
+    pub unsafe fn olm_account_fallback_key_length(&self, account: *const OlmAccount) -> usize {
+        (self
+            .olm_account_fallback_key_length
+            .as_ref()
+            .expect("Expected function, got error."))(account)
+    }
+    pub unsafe fn olm_account_fallback_key(
+        &self,
+        account: *mut OlmAccount,
+        fallback_key: *mut ::core::ffi::c_void,
+        fallback_key_size: usize,
+    ) -> usize {
+        (self
+            .olm_account_fallback_key
+            .as_ref()
+            .expect("Expected function, got error."))(
+            account, fallback_key, fallback_key_size
+        )
+    }
+    pub unsafe fn olm_account_unpublished_fallback_key_length(
+        &self,
+        account: *const OlmAccount,
+    ) -> usize {
+        (self
+            .olm_account_unpublished_fallback_key_length
+            .as_ref()
+            .expect("Expected function, got error."))(account)
+    }
+    pub unsafe fn olm_account_unpublished_fallback_key(
+        &self,
+        account: *mut OlmAccount,
+        fallback_key: *mut ::core::ffi::c_void,
+        fallback_key_size: usize,
+    ) -> usize {
+        (self
+            .olm_account_unpublished_fallback_key
+            .as_ref()
+            .expect("Expected function, got error."))(
+            account, fallback_key, fallback_key_size
+        )
+    }
+    pub unsafe fn olm_account_forget_old_fallback_key(&self, account: *mut OlmAccount) {
+        (self
+            .olm_account_forget_old_fallback_key
+            .as_ref()
+            .expect("Expected function, got error."))(account)
+    }
+    pub unsafe fn olm_create_outbound_session_random_length(
+        &self,
+        session: *const OlmSession,
+    ) -> usize {
+        (self
+            .olm_create_outbound_session_random_length
+            .as_ref()
+            .expect("Expected function, got error."))(session)
+    }
+    pub unsafe fn olm_create_outbound_session(
+        &self,
+        session: *mut OlmSession,
+        account: *const OlmAccount,
+        their_identity_key: *const ::core::ffi::c_void,
+        their_identity_key_length: usize,
+        their_one_time_key: *const ::core::ffi::c_void,
+        their_one_time_key_length: usize,
+        random: *mut ::core::ffi::c_void,
+        random_length: usize,
+    ) -> usize {
+        (self
+            .olm_create_outbound_session
+            .as_ref()
+            .expect("Expected function, got error."))(
+            session,
+            account,
+            their_identity_key,
+            their_identity_key_length,
+            their_one_time_key,
+            their_one_time_key_length,
+            random,
+            random_length,
+        )
+    }
+    pub unsafe fn olm_create_inbound_session(
+        &self,
+        session: *mut OlmSession,
+        account: *mut OlmAccount,
+        one_time_key_message: *mut ::core::ffi::c_void,
+        message_length: usize,
+    ) -> usize {
+        (self
+            .olm_create_inbound_session
+            .as_ref()
+            .expect("Expected function, got error."))(
+            session,
+            account,
+            one_time_key_message,
+            message_length,
+        )
+    }
+    pub unsafe fn olm_create_inbound_session_from(
+        &self,
+        session: *mut OlmSession,
+        account: *mut OlmAccount,
+        their_identity_key: *const ::core::ffi::c_void,
+        their_identity_key_length: usize,
+        one_time_key_message: *mut ::core::ffi::c_void,
+        message_length: usize,
+    ) -> usize {
+        (self
+            .olm_create_inbound_session_from
+            .as_ref()
+            .expect("Expected function, got error."))(
+            session,
+            account,
+            their_identity_key,
+            their_identity_key_length,
+            one_time_key_message,
+            message_length,
+        )
+    }
+    pub unsafe fn olm_session_id_length(&self, session: *const OlmSession) -> usize {
+        (self
+            .olm_session_id_length
+            .as_ref()
+            .expect("Expected function, got error."))(session)
+    }
+    pub unsafe fn olm_session_id(
+        &self,
+        session: *mut OlmSession,
+        id: *mut ::core::ffi::c_void,
+        id_length: usize,
+    ) -> usize {
+        (self
+            .olm_session_id
+            .as_ref()
+            .expect("Expected function, got error."))(session, id, id_length)
+    }
+    pub unsafe fn olm_session_has_received_message(
+        &self,
+        session: *const OlmSession,
+    ) -> ::core::ffi::c_int {
+        (self
+            .olm_session_has_received_message
+            .as_ref()
+            .expect("Expected function, got error."))(session)
+    }
+    pub unsafe fn olm_session_describe(
+        &self,
+        session: *mut OlmSession,
+        buf: *mut ::core::ffi::c_char,
+        buflen: usize,
+    ) {
+        (self
+            .olm_session_describe
+            .as_ref()
+            .expect("Expected function, got error."))(session, buf, buflen)
+    }
+    pub unsafe fn olm_matches_inbound_session(
+        &self,
+        session: *mut OlmSession,
+        one_time_key_message: *mut ::core::ffi::c_void,
+        message_length: usize,
+    ) -> usize {
+        (self
+            .olm_matches_inbound_session
+            .as_ref()
+            .expect("Expected function, got error."))(
+            session, one_time_key_message, message_length
+        )
+    }
+    pub unsafe fn olm_matches_inbound_session_from(
+        &self,
+        session: *mut OlmSession,
+        their_identity_key: *const ::core::ffi::c_void,
+        their_identity_key_length: usize,
+        one_time_key_message: *mut ::core::ffi::c_void,
+        message_length: usize,
+    ) -> usize {
+        (self
+            .olm_matches_inbound_session_from
+            .as_ref()
+            .expect("Expected function, got error."))(
+            session,
+            their_identity_key,
+            their_identity_key_length,
+            one_time_key_message,
+            message_length,
+        )
+    }
+    pub unsafe fn olm_remove_one_time_keys(
+        &self,
+        account: *mut OlmAccount,
+        session: *mut OlmSession,
+    ) -> usize {
+        (self
+            .olm_remove_one_time_keys
+            .as_ref()
+            .expect("Expected function, got error."))(account, session)
+    }
+    pub unsafe fn olm_encrypt_message_type(&self, session: *const OlmSession) -> usize {
+        (self
+            .olm_encrypt_message_type
+            .as_ref()
+            .expect("Expected function, got error."))(session)
+    }
+    pub unsafe fn olm_encrypt_random_length(&self, session: *const OlmSession) -> usize {
+        (self
+            .olm_encrypt_random_length
+            .as_ref()
+            .expect("Expected function, got error."))(session)
+    }
+    pub unsafe fn olm_encrypt_message_length(
+        &self,
+        session: *const OlmSession,
+        plaintext_length: usize,
+    ) -> usize {
+        (self
+            .olm_encrypt_message_length
+            .as_ref()
+            .expect("Expected function, got error."))(session, plaintext_length)
+    }
+    pub unsafe fn olm_encrypt(
+        &self,
+        session: *mut OlmSession,
+        plaintext: *const ::core::ffi::c_void,
+        plaintext_length: usize,
+        random: *mut ::core::ffi::c_void,
+        random_length: usize,
+        message: *mut ::core::ffi::c_void,
+        message_length: usize,
+    ) -> usize {
+        (self
+            .olm_encrypt
+            .as_ref()
+            .expect("Expected function, got error."))(
+            session,
+            plaintext,
+            plaintext_length,
+            random,
+            random_length,
+            message,
+            message_length,
+        )
+    }
+    pub unsafe fn olm_decrypt_max_plaintext_length(
+        &self,
+        session: *mut OlmSession,
+        message_type: usize,
+        message: *mut ::core::ffi::c_void,
+        message_length: usize,
+    ) -> usize {
+        (self
+            .olm_decrypt_max_plaintext_length
+            .as_ref()
+            .expect("Expected function, got error."))(
+            session,
+            message_type,
+            message,
+            message_length,
+        )
+    }
+    pub unsafe fn olm_decrypt(
+        &self,
+        session: *mut OlmSession,
+        message_type: usize,
+        message: *mut ::core::ffi::c_void,
+        message_length: usize,
+        plaintext: *mut ::core::ffi::c_void,
+        max_plaintext_length: usize,
+    ) -> usize {
+        (self
+            .olm_decrypt
+            .as_ref()
+            .expect("Expected function, got error."))(
+            session,
+            message_type,
+            message,
+            message_length,
+            plaintext,
+            max_plaintext_length,
+        )
+    }
+    pub unsafe fn olm_sha256_length(&self, utility: *const OlmUtility) -> usize {
+        (self
+            .olm_sha256_length
+            .as_ref()
+            .expect("Expected function, got error."))(utility)
+    }
+    pub unsafe fn olm_sha256(
+        &self,
+        utility: *mut OlmUtility,
+        input: *const ::core::ffi::c_void,
+        input_length: usize,
+        output: *mut ::core::ffi::c_void,
+        output_length: usize,
+    ) -> usize {
+        (self
+            .olm_sha256
+            .as_ref()
+            .expect("Expected function, got error."))(
+            utility,
+            input,
+            input_length,
+            output,
+            output_length,
+        )
+    }
+    pub unsafe fn olm_ed25519_verify(
+        &self,
+        utility: *mut OlmUtility,
+        key: *const ::core::ffi::c_void,
+        key_length: usize,
+        message: *const ::core::ffi::c_void,
+        message_length: usize,
+        signature: *mut ::core::ffi::c_void,
+        signature_length: usize,
+    ) -> usize {
+        (self
+            .olm_ed25519_verify
+            .as_ref()
+            .expect("Expected function, got error."))(
+            utility,
+            key,
+            key_length,
+            message,
+            message_length,
+            signature,
+            signature_length,
+        )
+    }
+    pub unsafe fn olm_sas_last_error(&self, sas: *const OlmSAS) -> *const ::core::ffi::c_char {
+        (self
+            .olm_sas_last_error
+            .as_ref()
+            .expect("Expected function, got error."))(sas)
+    }
+    pub unsafe fn olm_sas_last_error_code(&self, sas: *const OlmSAS) -> OlmErrorCode {
+        (self
+            .olm_sas_last_error_code
+            .as_ref()
+            .expect("Expected function, got error."))(sas)
+    }
+    pub unsafe fn olm_sas_size(&self) -> usize {
+        (self
+            .olm_sas_size
+            .as_ref()
+            .expect("Expected function, got error."))()
+    }
+    pub unsafe fn olm_sas(&self, memory: *mut ::core::ffi::c_void) -> *mut OlmSAS {
+        (self
+            .olm_sas
+            .as_ref()
+            .expect("Expected function, got error."))(memory)
+    }
+    pub unsafe fn olm_clear_sas(&self, sas: *mut OlmSAS) -> usize {
+        (self
+            .olm_clear_sas
+            .as_ref()
+            .expect("Expected function, got error."))(sas)
+    }
+    pub unsafe fn olm_create_sas_random_length(&self, sas: *const OlmSAS) -> usize {
+        (self
+            .olm_create_sas_random_length
+            .as_ref()
+            .expect("Expected function, got error."))(sas)
+    }
+    pub unsafe fn olm_create_sas(
+        &self,
+        sas: *mut OlmSAS,
+        random: *mut ::core::ffi::c_void,
+        random_length: usize,
+    ) -> usize {
+        (self
+            .olm_create_sas
+            .as_ref()
+            .expect("Expected function, got error."))(sas, random, random_length)
+    }
+    pub unsafe fn olm_sas_pubkey_length(&self, sas: *const OlmSAS) -> usize {
+        (self
+            .olm_sas_pubkey_length
+            .as_ref()
+            .expect("Expected function, got error."))(sas)
+    }
+    pub unsafe fn olm_sas_get_pubkey(
+        &self,
+        sas: *mut OlmSAS,
+        pubkey: *mut ::core::ffi::c_void,
+        pubkey_length: usize,
+    ) -> usize {
+        (self
+            .olm_sas_get_pubkey
+            .as_ref()
+            .expect("Expected function, got error."))(sas, pubkey, pubkey_length)
+    }
+    pub unsafe fn olm_sas_set_their_key(
+        &self,
+        sas: *mut OlmSAS,
+        their_key: *mut ::core::ffi::c_void,
+        their_key_length: usize,
+    ) -> usize {
+        (self
+            .olm_sas_set_their_key
+            .as_ref()
+            .expect("Expected function, got error."))(sas, their_key, their_key_length)
+    }
+    pub unsafe fn olm_sas_is_their_key_set(&self, sas: *const OlmSAS) -> ::core::ffi::c_int {
+        (self
+            .olm_sas_is_their_key_set
+            .as_ref()
+            .expect("Expected function, got error."))(sas)
+    }
+    pub unsafe fn olm_sas_generate_bytes(
+        &self,
+        sas: *mut OlmSAS,
+        info: *const ::core::ffi::c_void,
+        info_length: usize,
+        output: *mut ::core::ffi::c_void,
+        output_length: usize,
+    ) -> usize {
+        (self
+            .olm_sas_generate_bytes
+            .as_ref()
+            .expect("Expected function, got error."))(
+            sas, info, info_length, output, output_length
+        )
+    }
+    pub unsafe fn olm_sas_mac_length(&self, sas: *const OlmSAS) -> usize {
+        (self
+            .olm_sas_mac_length
+            .as_ref()
+            .expect("Expected function, got error."))(sas)
+    }
+    pub unsafe fn olm_sas_calculate_mac(
+        &self,
+        sas: *mut OlmSAS,
+        input: *const ::core::ffi::c_void,
+        input_length: usize,
+        info: *const ::core::ffi::c_void,
+        info_length: usize,
+        mac: *mut ::core::ffi::c_void,
+        mac_length: usize,
+    ) -> usize {
+        (self
+            .olm_sas_calculate_mac
+            .as_ref()
+            .expect("Expected function, got error."))(
+            sas,
+            input,
+            input_length,
+            info,
+            info_length,
+            mac,
+            mac_length,
+        )
+    }
+    pub unsafe fn olm_sas_calculate_mac_fixed_base64(
+        &self,
+        sas: *mut OlmSAS,
+        input: *const ::core::ffi::c_void,
+        input_length: usize,
+        info: *const ::core::ffi::c_void,
+        info_length: usize,
+        mac: *mut ::core::ffi::c_void,
+        mac_length: usize,
+    ) -> usize {
+        (self
+            .olm_sas_calculate_mac_fixed_base64
+            .as_ref()
+            .expect("Expected function, got error."))(
+            sas,
+            input,
+            input_length,
+            info,
+            info_length,
+            mac,
+            mac_length,
+        )
+    }
+    pub unsafe fn olm_sas_calculate_mac_long_kdf(
+        &self,
+        sas: *mut OlmSAS,
+        input: *const ::core::ffi::c_void,
+        input_length: usize,
+        info: *const ::core::ffi::c_void,
+        info_length: usize,
+        mac: *mut ::core::ffi::c_void,
+        mac_length: usize,
+    ) -> usize {
+        (self
+            .olm_sas_calculate_mac_long_kdf
+            .as_ref()
+            .expect("Expected function, got error."))(
+            sas,
+            input,
+            input_length,
+            info,
+            info_length,
+            mac,
+            mac_length,
+        )
+    }
+    pub unsafe fn olm_pk_encryption_size(&self) -> usize {
+        (self
+            .olm_pk_encryption_size
+            .as_ref()
+            .expect("Expected function, got error."))()
+    }
+    pub unsafe fn olm_pk_encryption(
+        &self,
+        memory: *mut ::core::ffi::c_void,
+    ) -> *mut OlmPkEncryption {
+        (self
+            .olm_pk_encryption
+            .as_ref()
+            .expect("Expected function, got error."))(memory)
+    }
+    pub unsafe fn olm_pk_encryption_last_error(
+        &self,
+        encryption: *const OlmPkEncryption,
+    ) -> *const ::core::ffi::c_char {
+        (self
+            .olm_pk_encryption_last_error
+            .as_ref()
+            .expect("Expected function, got error."))(encryption)
+    }
+    pub unsafe fn olm_pk_encryption_last_error_code(
+        &self,
+        encryption: *const OlmPkEncryption,
+    ) -> OlmErrorCode {
+        (self
+            .olm_pk_encryption_last_error_code
+            .as_ref()
+            .expect("Expected function, got error."))(encryption)
+    }
+    pub unsafe fn olm_clear_pk_encryption(&self, encryption: *mut OlmPkEncryption) -> usize {
+        (self
+            .olm_clear_pk_encryption
+            .as_ref()
+            .expect("Expected function, got error."))(encryption)
+    }
+    pub unsafe fn olm_pk_encryption_set_recipient_key(
+        &self,
+        encryption: *mut OlmPkEncryption,
+        public_key: *const ::core::ffi::c_void,
+        public_key_length: usize,
+    ) -> usize {
+        (self
+            .olm_pk_encryption_set_recipient_key
+            .as_ref()
+            .expect("Expected function, got error."))(
+            encryption, public_key, public_key_length
+        )
+    }
+    pub unsafe fn olm_pk_ciphertext_length(
+        &self,
+        encryption: *const OlmPkEncryption,
+        plaintext_length: usize,
+    ) -> usize {
+        (self
+            .olm_pk_ciphertext_length
+            .as_ref()
+            .expect("Expected function, got error."))(encryption, plaintext_length)
+    }
+    pub unsafe fn olm_pk_mac_length(&self, encryption: *const OlmPkEncryption) -> usize {
+        (self
+            .olm_pk_mac_length
+            .as_ref()
+            .expect("Expected function, got error."))(encryption)
+    }
+    pub unsafe fn olm_pk_key_length(&self) -> usize {
+        (self
+            .olm_pk_key_length
+            .as_ref()
+            .expect("Expected function, got error."))()
+    }
+    pub unsafe fn olm_pk_encrypt_random_length(&self, encryption: *const OlmPkEncryption) -> usize {
+        (self
+            .olm_pk_encrypt_random_length
+            .as_ref()
+            .expect("Expected function, got error."))(encryption)
+    }
+    pub unsafe fn olm_pk_encrypt(
+        &self,
+        encryption: *mut OlmPkEncryption,
+        plaintext: *const ::core::ffi::c_void,
+        plaintext_length: usize,
+        ciphertext: *mut ::core::ffi::c_void,
+        ciphertext_length: usize,
+        mac: *mut ::core::ffi::c_void,
+        mac_length: usize,
+        ephemeral_key: *mut ::core::ffi::c_void,
+        ephemeral_key_size: usize,
+        random: *const ::core::ffi::c_void,
+        random_length: usize,
+    ) -> usize {
+        (self
+            .olm_pk_encrypt
+            .as_ref()
+            .expect("Expected function, got error."))(
+            encryption,
+            plaintext,
+            plaintext_length,
+            ciphertext,
+            ciphertext_length,
+            mac,
+            mac_length,
+            ephemeral_key,
+            ephemeral_key_size,
+            random,
+            random_length,
+        )
+    }
+    pub unsafe fn olm_pk_decryption_size(&self) -> usize {
+        (self
+            .olm_pk_decryption_size
+            .as_ref()
+            .expect("Expected function, got error."))()
+    }
+    pub unsafe fn olm_pk_decryption(
+        &self,
+        memory: *mut ::core::ffi::c_void,
+    ) -> *mut OlmPkDecryption {
+        (self
+            .olm_pk_decryption
+            .as_ref()
+            .expect("Expected function, got error."))(memory)
+    }
+    pub unsafe fn olm_pk_decryption_last_error(
+        &self,
+        decryption: *const OlmPkDecryption,
+    ) -> *const ::core::ffi::c_char {
+        (self
+            .olm_pk_decryption_last_error
+            .as_ref()
+            .expect("Expected function, got error."))(decryption)
+    }
+    pub unsafe fn olm_pk_decryption_last_error_code(
+        &self,
+        decryption: *const OlmPkDecryption,
+    ) -> OlmErrorCode {
+        (self
+            .olm_pk_decryption_last_error_code
+            .as_ref()
+            .expect("Expected function, got error."))(decryption)
+    }
+    pub unsafe fn olm_clear_pk_decryption(&self, decryption: *mut OlmPkDecryption) -> usize {
+        (self
+            .olm_clear_pk_decryption
+            .as_ref()
+            .expect("Expected function, got error."))(decryption)
+    }
+    pub unsafe fn olm_pk_private_key_length(&self) -> usize {
+        (self
+            .olm_pk_private_key_length
+            .as_ref()
+            .expect("Expected function, got error."))()
+    }
+    pub unsafe fn olm_pk_generate_key_random_length(&self) -> usize {
+        (self
+            .olm_pk_generate_key_random_length
+            .as_ref()
+            .expect("Expected function, got error."))()
+    }
+    pub unsafe fn olm_pk_key_from_private(
+        &self,
+        decryption: *mut OlmPkDecryption,
+        pubkey: *mut ::core::ffi::c_void,
+        pubkey_length: usize,
+        privkey: *const ::core::ffi::c_void,
+        privkey_length: usize,
+    ) -> usize {
+        (self
+            .olm_pk_key_from_private
+            .as_ref()
+            .expect("Expected function, got error."))(
+            decryption,
+            pubkey,
+            pubkey_length,
+            privkey,
+            privkey_length,
+        )
+    }
+    pub unsafe fn olm_pk_generate_key(
+        &self,
+        decryption: *mut OlmPkDecryption,
+        pubkey: *mut ::core::ffi::c_void,
+        pubkey_length: usize,
+        privkey: *const ::core::ffi::c_void,
+        privkey_length: usize,
+    ) -> usize {
+        (self
+            .olm_pk_generate_key
+            .as_ref()
+            .expect("Expected function, got error."))(
+            decryption,
+            pubkey,
+            pubkey_length,
+            privkey,
+            privkey_length,
+        )
+    }
+    pub unsafe fn olm_pickle_pk_decryption_length(
+        &self,
+        decryption: *const OlmPkDecryption,
+    ) -> usize {
+        (self
+            .olm_pickle_pk_decryption_length
+            .as_ref()
+            .expect("Expected function, got error."))(decryption)
+    }
+    pub unsafe fn olm_pickle_pk_decryption(
+        &self,
+        decryption: *mut OlmPkDecryption,
+        key: *const ::core::ffi::c_void,
+        key_length: usize,
+        pickled: *mut ::core::ffi::c_void,
+        pickled_length: usize,
+    ) -> usize {
+        (self
+            .olm_pickle_pk_decryption
+            .as_ref()
+            .expect("Expected function, got error."))(
+            decryption,
+            key,
+            key_length,
+            pickled,
+            pickled_length,
+        )
+    }
+    pub unsafe fn olm_unpickle_pk_decryption(
+        &self,
+        decryption: *mut OlmPkDecryption,
+        key: *const ::core::ffi::c_void,
+        key_length: usize,
+        pickled: *mut ::core::ffi::c_void,
+        pickled_length: usize,
+        pubkey: *mut ::core::ffi::c_void,
+        pubkey_length: usize,
+    ) -> usize {
+        (self
+            .olm_unpickle_pk_decryption
+            .as_ref()
+            .expect("Expected function, got error."))(
+            decryption,
+            key,
+            key_length,
+            pickled,
+            pickled_length,
+            pubkey,
+            pubkey_length,
+        )
+    }
+    pub unsafe fn olm_pk_max_plaintext_length(
+        &self,
+        decryption: *const OlmPkDecryption,
+        ciphertext_length: usize,
+    ) -> usize {
+        (self
+            .olm_pk_max_plaintext_length
+            .as_ref()
+            .expect("Expected function, got error."))(decryption, ciphertext_length)
+    }
+    pub unsafe fn olm_pk_decrypt(
+        &self,
+        decryption: *mut OlmPkDecryption,
+        ephemeral_key: *const ::core::ffi::c_void,
+        ephemeral_key_length: usize,
+        mac: *const ::core::ffi::c_void,
+        mac_length: usize,
+        ciphertext: *mut ::core::ffi::c_void,
+        ciphertext_length: usize,
+        plaintext: *mut ::core::ffi::c_void,
+        max_plaintext_length: usize,
+    ) -> usize {
+        (self
+            .olm_pk_decrypt
+            .as_ref()
+            .expect("Expected function, got error."))(
+            decryption,
+            ephemeral_key,
+            ephemeral_key_length,
+            mac,
+            mac_length,
+            ciphertext,
+            ciphertext_length,
+            plaintext,
+            max_plaintext_length,
+        )
+    }
+    pub unsafe fn olm_pk_get_private_key(
+        &self,
+        decryption: *mut OlmPkDecryption,
+        private_key: *mut ::core::ffi::c_void,
+        private_key_length: usize,
+    ) -> usize {
+        (self
+            .olm_pk_get_private_key
+            .as_ref()
+            .expect("Expected function, got error."))(
+            decryption, private_key, private_key_length
+        )
+    }
+    pub unsafe fn olm_pk_signing_size(&self) -> usize {
+        (self
+            .olm_pk_signing_size
+            .as_ref()
+            .expect("Expected function, got error."))()
+    }
+    pub unsafe fn olm_pk_signing(&self, memory: *mut ::core::ffi::c_void) -> *mut OlmPkSigning {
+        (self
+            .olm_pk_signing
+            .as_ref()
+            .expect("Expected function, got error."))(memory)
+    }
+    pub unsafe fn olm_pk_signing_last_error(
+        &self,
+        sign: *const OlmPkSigning,
+    ) -> *const ::core::ffi::c_char {
+        (self
+            .olm_pk_signing_last_error
+            .as_ref()
+            .expect("Expected function, got error."))(sign)
+    }
+    pub unsafe fn olm_pk_signing_last_error_code(&self, sign: *const OlmPkSigning) -> OlmErrorCode {
+        (self
+            .olm_pk_signing_last_error_code
+            .as_ref()
+            .expect("Expected function, got error."))(sign)
+    }
+    pub unsafe fn olm_clear_pk_signing(&self, sign: *mut OlmPkSigning) -> usize {
+        (self
+            .olm_clear_pk_signing
+            .as_ref()
+            .expect("Expected function, got error."))(sign)
+    }
+    pub unsafe fn olm_pk_signing_key_from_seed(
+        &self,
+        sign: *mut OlmPkSigning,
+        pubkey: *mut ::core::ffi::c_void,
+        pubkey_length: usize,
+        seed: *const ::core::ffi::c_void,
+        seed_length: usize,
+    ) -> usize {
+        (self
+            .olm_pk_signing_key_from_seed
+            .as_ref()
+            .expect("Expected function, got error."))(
+            sign,
+            pubkey,
+            pubkey_length,
+            seed,
+            seed_length,
+        )
+    }
+    pub unsafe fn olm_pk_signing_seed_length(&self) -> usize {
+        (self
+            .olm_pk_signing_seed_length
+            .as_ref()
+            .expect("Expected function, got error."))()
+    }
+    pub unsafe fn olm_pk_signing_public_key_length(&self) -> usize {
+        (self
+            .olm_pk_signing_public_key_length
+            .as_ref()
+            .expect("Expected function, got error."))()
+    }
+    pub unsafe fn olm_pk_signature_length(&self) -> usize {
+        (self
+            .olm_pk_signature_length
+            .as_ref()
+            .expect("Expected function, got error."))()
+    }
+    pub unsafe fn olm_pk_sign(
+        &self,
+        sign: *mut OlmPkSigning,
+        message: *const u8,
+        message_length: usize,
+        signature: *mut u8,
+        signature_length: usize,
+    ) -> usize {
+        (self
+            .olm_pk_sign
+            .as_ref()
+            .expect("Expected function, got error."))(
+            sign,
+            message,
+            message_length,
+            signature,
+            signature_length,
+        )
+    }
+    pub unsafe fn _olm_encode_group_message_length(
+        &self,
+        chain_index: u32,
+        ciphertext_length: usize,
+        mac_length: usize,
+        signature_length: usize,
+    ) -> usize {
+        (self
+            ._olm_encode_group_message_length
+            .as_ref()
+            .expect("Expected function, got error."))(
+            chain_index,
+            ciphertext_length,
+            mac_length,
+            signature_length,
+        )
+    }
+    pub unsafe fn _olm_encode_group_message(
+        &self,
+        version: u8,
+        message_index: u32,
+        ciphertext_length: usize,
+        output: *mut u8,
+        ciphertext_ptr: *mut *mut u8,
+    ) -> usize {
+        (self
+            ._olm_encode_group_message
+            .as_ref()
+            .expect("Expected function, got error."))(
+            version,
+            message_index,
+            ciphertext_length,
+            output,
+            ciphertext_ptr,
+        )
+    }
+    pub unsafe fn _olm_decode_group_message(
+        &self,
+        input: *const u8,
+        input_length: usize,
+        mac_length: usize,
+        signature_length: usize,
+        results: *mut _OlmDecodeGroupMessageResults,
+    ) {
+        (self
+            ._olm_decode_group_message
+            .as_ref()
+            .expect("Expected function, got error."))(
+            input,
+            input_length,
+            mac_length,
+            signature_length,
+            results,
+        )
+    }
+    pub unsafe fn _olm_crypto_aes_encrypt_cbc_length(&self, input_length: usize) -> usize {
+        (self
+            ._olm_crypto_aes_encrypt_cbc_length
+            .as_ref()
+            .expect("Expected function, got error."))(input_length)
+    }
+    pub unsafe fn _olm_crypto_aes_encrypt_cbc(
+        &self,
+        key: *const _olm_aes256_key,
+        iv: *const _olm_aes256_iv,
+        input: *const u8,
+        input_length: usize,
+        output: *mut u8,
+    ) {
+        (self
+            ._olm_crypto_aes_encrypt_cbc
+            .as_ref()
+            .expect("Expected function, got error."))(key, iv, input, input_length, output)
+    }
+    pub unsafe fn _olm_crypto_aes_decrypt_cbc(
+        &self,
+        key: *const _olm_aes256_key,
+        iv: *const _olm_aes256_iv,
+        input: *const u8,
+        input_length: usize,
+        output: *mut u8,
+    ) -> usize {
+        (self
+            ._olm_crypto_aes_decrypt_cbc
+            .as_ref()
+            .expect("Expected function, got error."))(key, iv, input, input_length, output)
+    }
+    pub unsafe fn _olm_crypto_sha256(
+        &self,
+        input: *const u8,
+        input_length: usize,
+        output: *mut u8,
+    ) {
+        (self
+            ._olm_crypto_sha256
+            .as_ref()
+            .expect("Expected function, got error."))(input, input_length, output)
+    }
+    pub unsafe fn _olm_crypto_hmac_sha256(
+        &self,
+        key: *const u8,
+        key_length: usize,
+        input: *const u8,
+        input_length: usize,
+        output: *mut u8,
+    ) {
+        (self
+            ._olm_crypto_hmac_sha256
+            .as_ref()
+            .expect("Expected function, got error."))(
+            key, key_length, input, input_length, output
+        )
+    }
+    pub unsafe fn _olm_crypto_hkdf_sha256(
+        &self,
+        input: *const u8,
+        input_length: usize,
+        info: *const u8,
+        info_length: usize,
+        salt: *const u8,
+        salt_length: usize,
+        output: *mut u8,
+        output_length: usize,
+    ) {
+        (self
+            ._olm_crypto_hkdf_sha256
+            .as_ref()
+            .expect("Expected function, got error."))(
+            input,
+            input_length,
+            info,
+            info_length,
+            salt,
+            salt_length,
+            output,
+            output_length,
+        )
+    }
+    pub unsafe fn _olm_crypto_curve25519_generate_key(
+        &self,
+        random_32_bytes: *const u8,
+        output: *mut _olm_curve25519_key_pair,
+    ) {
+        (self
+            ._olm_crypto_curve25519_generate_key
+            .as_ref()
+            .expect("Expected function, got error."))(random_32_bytes, output)
+    }
+    pub unsafe fn _olm_crypto_curve25519_shared_secret(
+        &self,
+        our_key: *const _olm_curve25519_key_pair,
+        their_key: *const _olm_curve25519_public_key,
+        output: *mut u8,
+    ) {
+        (self
+            ._olm_crypto_curve25519_shared_secret
+            .as_ref()
+            .expect("Expected function, got error."))(our_key, their_key, output)
+    }
+    pub unsafe fn _olm_crypto_ed25519_generate_key(
+        &self,
+        random_bytes: *const u8,
+        output: *mut _olm_ed25519_key_pair,
+    ) {
+        (self
+            ._olm_crypto_ed25519_generate_key
+            .as_ref()
+            .expect("Expected function, got error."))(random_bytes, output)
+    }
+    pub unsafe fn _olm_crypto_ed25519_sign(
+        &self,
+        our_key: *const _olm_ed25519_key_pair,
+        message: *const u8,
+        message_length: usize,
+        output: *mut u8,
+    ) {
+        (self
+            ._olm_crypto_ed25519_sign
+            .as_ref()
+            .expect("Expected function, got error."))(
+            our_key, message, message_length, output
+        )
+    }
+    pub unsafe fn _olm_crypto_ed25519_verify(
+        &self,
+        their_key: *const _olm_ed25519_public_key,
+        message: *const u8,
+        message_length: usize,
+        signature: *const u8,
+    ) -> ::core::ffi::c_int {
+        (self
+            ._olm_crypto_ed25519_verify
+            .as_ref()
+            .expect("Expected function, got error."))(
+            their_key, message, message_length, signature
+        )
+    }
+    pub unsafe fn _olm_enc_output_length(&self, raw_length: usize) -> usize {
+        (self
+            ._olm_enc_output_length
+            .as_ref()
+            .expect("Expected function, got error."))(raw_length)
+    }
+    pub unsafe fn _olm_enc_output_pos(&self, output: *mut u8, raw_length: usize) -> *mut u8 {
+        (self
+            ._olm_enc_output_pos
+            .as_ref()
+            .expect("Expected function, got error."))(output, raw_length)
+    }
+    pub unsafe fn _olm_enc_output(
+        &self,
+        key: *const u8,
+        key_length: usize,
+        pickle: *mut u8,
+        raw_length: usize,
+    ) -> usize {
+        (self
+            ._olm_enc_output
+            .as_ref()
+            .expect("Expected function, got error."))(key, key_length, pickle, raw_length)
+    }
+    pub unsafe fn _olm_enc_input(
+        &self,
+        key: *const u8,
+        key_length: usize,
+        input: *mut u8,
+        b64_length: usize,
+        last_error: *mut OlmErrorCode,
+    ) -> usize {
+        (self
+            ._olm_enc_input
+            .as_ref()
+            .expect("Expected function, got error."))(
+            key, key_length, input, b64_length, last_error,
+        )
+    }
+    pub unsafe fn _olm_encode_base64_length(&self, input_length: usize) -> usize {
+        (self
+            ._olm_encode_base64_length
+            .as_ref()
+            .expect("Expected function, got error."))(input_length)
+    }
+    pub unsafe fn _olm_encode_base64(
+        &self,
+        input: *const u8,
+        input_length: usize,
+        output: *mut u8,
+    ) -> usize {
+        (self
+            ._olm_encode_base64
+            .as_ref()
+            .expect("Expected function, got error."))(input, input_length, output)
+    }
+    pub unsafe fn _olm_decode_base64_length(&self, input_length: usize) -> usize {
+        (self
+            ._olm_decode_base64_length
+            .as_ref()
+            .expect("Expected function, got error."))(input_length)
+    }
+    pub unsafe fn _olm_decode_base64(
+        &self,
+        input: *const u8,
+        input_length: usize,
+        output: *mut u8,
+    ) -> usize {
+        (self
+            ._olm_decode_base64
+            .as_ref()
+            .expect("Expected function, got error."))(input, input_length, output)
+    }
+}
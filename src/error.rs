@@ -0,0 +1,100 @@
+// Copyright 2020 Johannes Hayeß
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Errors reported by `libolm` to the safe wrappers.
+
+use core::fmt;
+
+use crate::*;
+
+/// An error `libolm` reported, see `OlmErrorCode`.
+///
+/// The safe wrappers read these with the `olm_*_last_error_code(…)`
+/// functions, so they need a `libolm` of at least 3.2.5.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct OlmError {
+    code: OlmErrorCode,
+}
+
+impl OlmError {
+    pub(crate) fn new(code: OlmErrorCode) -> Self {
+        OlmError { code }
+    }
+
+    /// The `OlmErrorCode_OLM_*` constant of this error.
+    pub fn code(self) -> OlmErrorCode {
+        self.code
+    }
+
+    /// The name `libolm` gives this error, e.g. `"BAD_MESSAGE_MAC"`.
+    ///
+    /// These are the names of `_olm_error_to_string(…)`, which libraries built
+    /// with libolm's Makefile don't export.
+    pub fn name(self) -> &'static str {
+        #[allow(non_upper_case_globals)]
+        match self.code {
+            OlmErrorCode_OLM_SUCCESS => "SUCCESS",
+            OlmErrorCode_OLM_NOT_ENOUGH_RANDOM => "NOT_ENOUGH_RANDOM",
+            OlmErrorCode_OLM_OUTPUT_BUFFER_TOO_SMALL => "OUTPUT_BUFFER_TOO_SMALL",
+            OlmErrorCode_OLM_BAD_MESSAGE_VERSION => "BAD_MESSAGE_VERSION",
+            OlmErrorCode_OLM_BAD_MESSAGE_FORMAT => "BAD_MESSAGE_FORMAT",
+            OlmErrorCode_OLM_BAD_MESSAGE_MAC => "BAD_MESSAGE_MAC",
+            OlmErrorCode_OLM_BAD_MESSAGE_KEY_ID => "BAD_MESSAGE_KEY_ID",
+            OlmErrorCode_OLM_INVALID_BASE64 => "INVALID_BASE64",
+            OlmErrorCode_OLM_BAD_ACCOUNT_KEY => "BAD_ACCOUNT_KEY",
+            OlmErrorCode_OLM_UNKNOWN_PICKLE_VERSION => "UNKNOWN_PICKLE_VERSION",
+            OlmErrorCode_OLM_CORRUPTED_PICKLE => "CORRUPTED_PICKLE",
+            OlmErrorCode_OLM_BAD_SESSION_KEY => "BAD_SESSION_KEY",
+            OlmErrorCode_OLM_UNKNOWN_MESSAGE_INDEX => "UNKNOWN_MESSAGE_INDEX",
+            OlmErrorCode_OLM_BAD_LEGACY_ACCOUNT_PICKLE => "BAD_LEGACY_ACCOUNT_PICKLE",
+            OlmErrorCode_OLM_BAD_SIGNATURE => "BAD_SIGNATURE",
+            // libolm names the newer errors with their prefix
+            OlmErrorCode_OLM_INPUT_BUFFER_TOO_SMALL => "OLM_INPUT_BUFFER_TOO_SMALL",
+            OlmErrorCode_OLM_SAS_THEIR_KEY_NOT_SET => "OLM_SAS_THEIR_KEY_NOT_SET",
+            OlmErrorCode_OLM_PICKLE_EXTRA_DATA => "OLM_PICKLE_EXTRA_DATA",
+            _ => "UNKNOWN_ERROR",
+        }
+    }
+}
+
+impl fmt::Debug for OlmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "OlmError({}: {})", self.code, self.name())
+    }
+}
+
+impl fmt::Display for OlmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "libolm error {}", self.name())
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for OlmError {}
+
+#[cfg(all(test, not(feature = "runtime-dlopen")))]
+mod tests {
+    use super::*;
+
+    use core::ffi::CStr;
+
+    #[test]
+    fn names_match_libolm() {
+        // One past the last error is unknown to both
+        for code in OlmErrorCode_OLM_SUCCESS..=OlmErrorCode_OLM_PICKLE_EXTRA_DATA + 1 {
+            let name = unsafe { CStr::from_ptr(_olm_error_to_string(code)) };
+            assert_eq!(OlmError::new(code).name(), name.to_str().unwrap());
+        }
+    }
+}
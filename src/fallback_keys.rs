@@ -0,0 +1,304 @@
+// Copyright 2020 Johannes Hayeß
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! When the fallback keys of an account were published and first used.
+//!
+//! `libolm` keeps the current fallback key and the one it replaced, but not
+//! how old they are. [`FallbackKeys`] records that next to the account, and
+//! pickles into its own blob to store alongside the account's pickle. Its
+//! [`FallbackKeys::should_rotate`] and [`FallbackKeys::can_forget_old`]
+//! decide, on a [`FallbackKeySchedule`], when to replace the current key
+//! and when to drop the previous one, so that neither stays usable forever.
+//!
+//! Times are given by the caller, as the time since the Unix epoch.
+
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use core::time::Duration;
+
+use serde_json::Value;
+
+use crate::account::Account;
+use crate::error::OlmError;
+use crate::pickle::Reader;
+use crate::random::RandomSource;
+use crate::OlmErrorCode_OLM_CORRUPTED_PICKLE;
+
+/// Version of [`FallbackKeys::pickle`]'s format.
+const PICKLE_VERSION: u32 = 1;
+
+/// How often the fallback key is replaced, and how long the previous one is
+/// kept for messages that were already on their way.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FallbackKeySchedule {
+    /// Age at which the fallback key is replaced, counted from when it was
+    /// published.
+    pub rotation_period: Duration,
+    /// How soon the fallback key is replaced once a session was created
+    /// with it, as anyone who claimed it can reuse it until then.
+    pub rotate_after_use: Duration,
+    /// How long the previous fallback key is kept once its replacement was
+    /// published, or generated if it never was.
+    pub forget_old_after: Duration,
+}
+
+impl Default for FallbackKeySchedule {
+    /// Weekly rotation, or an hour after the key was first used, keeping the
+    /// previous key for a day.
+    fn default() -> Self {
+        FallbackKeySchedule {
+            rotation_period: Duration::from_secs(7 * 24 * 60 * 60),
+            rotate_after_use: Duration::from_secs(60 * 60),
+            forget_old_after: Duration::from_secs(24 * 60 * 60),
+        }
+    }
+}
+
+/// The lifetime of one fallback key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FallbackKey {
+    public_key: String,
+    generated_at: Duration,
+    published_at: Option<Duration>,
+    first_used_at: Option<Duration>,
+}
+
+impl FallbackKey {
+    /// The base64 Curve25519 public key.
+    pub fn public_key(&self) -> &str {
+        &self.public_key
+    }
+
+    /// When the key was generated.
+    pub fn generated_at(&self) -> Duration {
+        self.generated_at
+    }
+
+    /// When the key was first published, if it was.
+    pub fn published_at(&self) -> Option<Duration> {
+        self.published_at
+    }
+
+    /// When the first session was created with the key, if one was.
+    pub fn first_used_at(&self) -> Option<Duration> {
+        self.first_used_at
+    }
+
+    /// When the key started being handed out, or would have.
+    fn live_since(&self) -> Duration {
+        self.published_at.unwrap_or(self.generated_at)
+    }
+}
+
+/// The current and previous fallback keys of an account, with their
+/// lifetimes.
+///
+/// Fallback keys have to be generated, published and forgotten through
+/// this, so that it stays in step with the account.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FallbackKeys {
+    current: Option<FallbackKey>,
+    previous: Option<FallbackKey>,
+}
+
+impl FallbackKeys {
+    /// Tracks an account without fallback keys.
+    pub fn new() -> Self {
+        FallbackKeys::default()
+    }
+
+    /// The current fallback key.
+    pub fn current(&self) -> Option<&FallbackKey> {
+        self.current.as_ref()
+    }
+
+    /// The fallback key the current one replaced, until it's forgotten.
+    pub fn previous(&self) -> Option<&FallbackKey> {
+        self.previous.as_ref()
+    }
+
+    /// Generates a new fallback key for `account`, which keeps the current
+    /// one as its previous key. An older previous key is dropped.
+    pub fn generate(
+        &mut self,
+        account: &mut Account,
+        now: Duration,
+        random: &mut impl RandomSource,
+    ) {
+        account.generate_fallback_key(random);
+        let keys: Value = serde_json::from_str(&account.unpublished_fallback_key())
+            .expect("libolm wrote valid JSON");
+        let public_key = keys["curve25519"]
+            .as_object()
+            .and_then(|keys| keys.values().next())
+            .and_then(Value::as_str)
+            .expect("the new fallback key is unpublished");
+
+        self.previous = self.current.take();
+        self.current = Some(FallbackKey {
+            public_key: public_key.to_owned(),
+            generated_at: now,
+            published_at: None,
+            first_used_at: None,
+        });
+    }
+
+    /// Marks `account`'s keys as published with
+    /// [`Account::mark_keys_as_published`], recording when the current
+    /// fallback key was first published.
+    pub fn mark_published(&mut self, account: &mut Account, now: Duration) {
+        account.mark_keys_as_published();
+        if let Some(current) = &mut self.current {
+            current.published_at.get_or_insert(now);
+        }
+    }
+
+    /// Records that a session was created with `one_time_key`, the
+    /// [`PreKeyMessage::one_time_key`](crate::message::PreKeyMessage::one_time_key)
+    /// of its first message. Returns whether it was one of the fallback keys.
+    pub fn record_use(&mut self, one_time_key: &str, now: Duration) -> bool {
+        let key = [&mut self.current, &mut self.previous]
+            .into_iter()
+            .flatten()
+            .find(|key| key.public_key == one_time_key);
+
+        match key {
+            Some(key) => {
+                key.first_used_at.get_or_insert(now);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Forgets the previous fallback key, in `account` too.
+    pub fn forget_old(&mut self, account: &mut Account) {
+        account.forget_old_fallback_key();
+        self.previous = None;
+    }
+
+    /// Whether the current fallback key is due to be replaced: there's
+    /// none, it's been live for the schedule's rotation period, or it was
+    /// used long enough ago.
+    pub fn should_rotate(&self, now: Duration, schedule: &FallbackKeySchedule) -> bool {
+        let Some(current) = &self.current else {
+            return true;
+        };
+
+        is_due(now, current.live_since(), schedule.rotation_period)
+            || current
+                .first_used_at
+                .is_some_and(|used_at| is_due(now, used_at, schedule.rotate_after_use))
+    }
+
+    /// Whether the previous fallback key has been kept long enough after
+    /// its replacement went live.
+    pub fn can_forget_old(&self, now: Duration, schedule: &FallbackKeySchedule) -> bool {
+        match (&self.current, &self.previous) {
+            (Some(current), Some(_)) => {
+                is_due(now, current.live_since(), schedule.forget_old_after)
+            }
+            _ => false,
+        }
+    }
+
+    /// Encrypts the lifetimes with `key` into a base64 pickle, to store
+    /// alongside the account's.
+    pub fn pickle(&self, key: &[u8]) -> String {
+        let mut raw = Vec::new();
+        raw.extend_from_slice(&PICKLE_VERSION.to_be_bytes());
+        for fallback_key in [&self.current, &self.previous] {
+            match fallback_key {
+                Some(fallback_key) => {
+                    raw.push(1);
+                    crate::pickle::write_string(&mut raw, &fallback_key.public_key);
+                    write_duration(&mut raw, fallback_key.generated_at);
+                    write_time(&mut raw, fallback_key.published_at);
+                    write_time(&mut raw, fallback_key.first_used_at);
+                }
+                None => raw.push(0),
+            }
+        }
+
+        crate::pickle::encrypt(key, &raw)
+    }
+
+    /// Restores the lifetimes from a pickle made by [`FallbackKeys::pickle`].
+    ///
+    /// Fails with `OLM_BAD_ACCOUNT_KEY` if `key` is wrong.
+    pub fn unpickle(key: &[u8], pickle: &str) -> Result<Self, OlmError> {
+        let raw = crate::pickle::decrypt(key, pickle)?;
+        let mut reader = Reader::new(&raw);
+        reader.version(PICKLE_VERSION)?;
+
+        let mut read_key = || -> Result<Option<FallbackKey>, OlmError> {
+            if reader.u8()? == 0 {
+                return Ok(None);
+            }
+            Ok(Some(FallbackKey {
+                public_key: reader.string()?.to_owned(),
+                generated_at: read_duration(&mut reader)?,
+                published_at: read_time(&mut reader)?,
+                first_used_at: read_time(&mut reader)?,
+            }))
+        };
+        let fallback_keys = FallbackKeys {
+            current: read_key()?,
+            previous: read_key()?,
+        };
+        reader.finish()?;
+
+        Ok(fallback_keys)
+    }
+}
+
+/// Whether `period` has passed since `since`. A period too long to add,
+/// like `Duration::MAX`, never passes.
+fn is_due(now: Duration, since: Duration, period: Duration) -> bool {
+    since.checked_add(period).is_some_and(|due| now >= due)
+}
+
+fn write_time(raw: &mut Vec<u8>, time: Option<Duration>) {
+    match time {
+        Some(time) => {
+            raw.push(1);
+            write_duration(raw, time);
+        }
+        None => raw.push(0),
+    }
+}
+
+fn write_duration(raw: &mut Vec<u8>, duration: Duration) {
+    raw.extend_from_slice(&duration.as_secs().to_be_bytes());
+    raw.extend_from_slice(&duration.subsec_nanos().to_be_bytes());
+}
+
+fn read_time(reader: &mut Reader<'_>) -> Result<Option<Duration>, OlmError> {
+    match reader.u8()? {
+        0 => Ok(None),
+        _ => read_duration(reader).map(Some),
+    }
+}
+
+fn read_duration(reader: &mut Reader<'_>) -> Result<Duration, OlmError> {
+    let seconds = reader.u64()?;
+    let nanos = reader.u32()?;
+    if nanos >= 1_000_000_000 {
+        return Err(OlmError::new(OlmErrorCode_OLM_CORRUPTED_PICKLE));
+    }
+
+    Ok(Duration::new(seconds, nanos))
+}
@@ -0,0 +1,386 @@
+// Copyright 2020 Johannes Hayeß
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The C library items that the bindings used to include.
+//!
+//! bindgen used to emit everything `wrapper.h` pulls in from the C library's
+//! headers too. These are those items as they were generated, on 64 bit
+//! glibc, whatever the target. They have nothing to do with `libolm`
+//! and are only kept so that code using them still compiles, until the next
+//! major version removes them.
+
+// Some of the aliases refer to others
+#![allow(deprecated)]
+
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const _STDINT_H: u32 = 1;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const _FEATURES_H: u32 = 1;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const _DEFAULT_SOURCE: u32 = 1;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const __GLIBC_USE_ISOC2X: u32 = 0;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const __USE_ISOC11: u32 = 1;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const __USE_ISOC99: u32 = 1;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const __USE_ISOC95: u32 = 1;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const __USE_POSIX_IMPLICITLY: u32 = 1;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const _POSIX_SOURCE: u32 = 1;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const _POSIX_C_SOURCE: u32 = 200809;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const __USE_POSIX: u32 = 1;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const __USE_POSIX2: u32 = 1;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const __USE_POSIX199309: u32 = 1;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const __USE_POSIX199506: u32 = 1;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const __USE_XOPEN2K: u32 = 1;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const __USE_XOPEN2K8: u32 = 1;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const _ATFILE_SOURCE: u32 = 1;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const __USE_MISC: u32 = 1;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const __USE_ATFILE: u32 = 1;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const __USE_FORTIFY_LEVEL: u32 = 0;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const __GLIBC_USE_DEPRECATED_GETS: u32 = 0;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const __GLIBC_USE_DEPRECATED_SCANF: u32 = 0;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const _STDC_PREDEF_H: u32 = 1;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const __STDC_IEC_559__: u32 = 1;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const __STDC_IEC_559_COMPLEX__: u32 = 1;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const __STDC_ISO_10646__: u32 = 201706;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const __GNU_LIBRARY__: u32 = 6;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const __GLIBC__: u32 = 2;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const __GLIBC_MINOR__: u32 = 33;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const _SYS_CDEFS_H: u32 = 1;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const __glibc_c99_flexarr_available: u32 = 1;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const __WORDSIZE: u32 = 64;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const __WORDSIZE_TIME64_COMPAT32: u32 = 1;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const __SYSCALL_WORDSIZE: u32 = 64;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const __LDOUBLE_REDIRECTS_TO_FLOAT128_ABI: u32 = 0;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const __HAVE_GENERIC_SELECTION: u32 = 1;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const __GLIBC_USE_LIB_EXT2: u32 = 0;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const __GLIBC_USE_IEC_60559_BFP_EXT: u32 = 0;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const __GLIBC_USE_IEC_60559_BFP_EXT_C2X: u32 = 0;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const __GLIBC_USE_IEC_60559_FUNCS_EXT: u32 = 0;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const __GLIBC_USE_IEC_60559_FUNCS_EXT_C2X: u32 = 0;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const __GLIBC_USE_IEC_60559_TYPES_EXT: u32 = 0;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const _BITS_TYPES_H: u32 = 1;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const __TIMESIZE: u32 = 64;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const _BITS_TYPESIZES_H: u32 = 1;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const __OFF_T_MATCHES_OFF64_T: u32 = 1;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const __INO_T_MATCHES_INO64_T: u32 = 1;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const __RLIM_T_MATCHES_RLIM64_T: u32 = 1;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const __STATFS_MATCHES_STATFS64: u32 = 1;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const __KERNEL_OLD_TIMEVAL_MATCHES_TIMEVAL64: u32 = 1;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const __FD_SETSIZE: u32 = 1024;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const _BITS_TIME64_H: u32 = 1;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const _BITS_WCHAR_H: u32 = 1;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const _BITS_STDINT_INTN_H: u32 = 1;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const _BITS_STDINT_UINTN_H: u32 = 1;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const INT8_MIN: i32 = -128;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const INT16_MIN: i32 = -32768;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const INT32_MIN: i32 = -2147483648;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const INT8_MAX: u32 = 127;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const INT16_MAX: u32 = 32767;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const INT32_MAX: u32 = 2147483647;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const UINT8_MAX: u32 = 255;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const UINT16_MAX: u32 = 65535;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const UINT32_MAX: u32 = 4294967295;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const INT_LEAST8_MIN: i32 = -128;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const INT_LEAST16_MIN: i32 = -32768;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const INT_LEAST32_MIN: i32 = -2147483648;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const INT_LEAST8_MAX: u32 = 127;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const INT_LEAST16_MAX: u32 = 32767;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const INT_LEAST32_MAX: u32 = 2147483647;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const UINT_LEAST8_MAX: u32 = 255;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const UINT_LEAST16_MAX: u32 = 65535;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const UINT_LEAST32_MAX: u32 = 4294967295;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const INT_FAST8_MIN: i32 = -128;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const INT_FAST16_MIN: i64 = -9223372036854775808;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const INT_FAST32_MIN: i64 = -9223372036854775808;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const INT_FAST8_MAX: u32 = 127;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const INT_FAST16_MAX: u64 = 9223372036854775807;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const INT_FAST32_MAX: u64 = 9223372036854775807;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const UINT_FAST8_MAX: u32 = 255;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const UINT_FAST16_MAX: i32 = -1;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const UINT_FAST32_MAX: i32 = -1;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const INTPTR_MIN: i64 = -9223372036854775808;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const INTPTR_MAX: u64 = 9223372036854775807;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const UINTPTR_MAX: i32 = -1;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const PTRDIFF_MIN: i64 = -9223372036854775808;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const PTRDIFF_MAX: u64 = 9223372036854775807;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const SIG_ATOMIC_MIN: i32 = -2147483648;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const SIG_ATOMIC_MAX: u32 = 2147483647;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const SIZE_MAX: i32 = -1;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const WINT_MIN: u32 = 0;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub const WINT_MAX: u32 = 4294967295;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type wchar_t = ::core::ffi::c_int;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+#[repr(C)]
+#[repr(align(16))]
+#[derive(Debug, Copy, Clone)]
+pub struct max_align_t {
+    pub __clang_max_align_nonce1: ::core::ffi::c_longlong,
+    pub __bindgen_padding_0: u64,
+    pub __clang_max_align_nonce2: u128,
+}
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type __u_char = ::core::ffi::c_uchar;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type __u_short = ::core::ffi::c_ushort;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type __u_int = ::core::ffi::c_uint;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type __u_long = ::core::ffi::c_ulong;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type __int8_t = ::core::ffi::c_schar;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type __uint8_t = ::core::ffi::c_uchar;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type __int16_t = ::core::ffi::c_short;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type __uint16_t = ::core::ffi::c_ushort;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type __int32_t = ::core::ffi::c_int;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type __uint32_t = ::core::ffi::c_uint;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type __int64_t = ::core::ffi::c_long;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type __uint64_t = ::core::ffi::c_ulong;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type __int_least8_t = __int8_t;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type __uint_least8_t = __uint8_t;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type __int_least16_t = __int16_t;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type __uint_least16_t = __uint16_t;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type __int_least32_t = __int32_t;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type __uint_least32_t = __uint32_t;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type __int_least64_t = __int64_t;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type __uint_least64_t = __uint64_t;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type __quad_t = ::core::ffi::c_long;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type __u_quad_t = ::core::ffi::c_ulong;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type __intmax_t = ::core::ffi::c_long;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type __uintmax_t = ::core::ffi::c_ulong;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type __dev_t = ::core::ffi::c_ulong;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type __uid_t = ::core::ffi::c_uint;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type __gid_t = ::core::ffi::c_uint;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type __ino_t = ::core::ffi::c_ulong;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type __ino64_t = ::core::ffi::c_ulong;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type __mode_t = ::core::ffi::c_uint;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type __nlink_t = ::core::ffi::c_ulong;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type __off_t = ::core::ffi::c_long;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type __off64_t = ::core::ffi::c_long;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type __pid_t = ::core::ffi::c_int;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct __fsid_t {
+    pub __val: [::core::ffi::c_int; 2usize],
+}
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type __clock_t = ::core::ffi::c_long;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type __rlim_t = ::core::ffi::c_ulong;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type __rlim64_t = ::core::ffi::c_ulong;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type __id_t = ::core::ffi::c_uint;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type __time_t = ::core::ffi::c_long;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type __useconds_t = ::core::ffi::c_uint;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type __suseconds_t = ::core::ffi::c_long;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type __suseconds64_t = ::core::ffi::c_long;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type __daddr_t = ::core::ffi::c_int;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type __key_t = ::core::ffi::c_int;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type __clockid_t = ::core::ffi::c_int;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type __timer_t = *mut ::core::ffi::c_void;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type __blksize_t = ::core::ffi::c_long;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type __blkcnt_t = ::core::ffi::c_long;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type __blkcnt64_t = ::core::ffi::c_long;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type __fsblkcnt_t = ::core::ffi::c_ulong;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type __fsblkcnt64_t = ::core::ffi::c_ulong;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type __fsfilcnt_t = ::core::ffi::c_ulong;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type __fsfilcnt64_t = ::core::ffi::c_ulong;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type __fsword_t = ::core::ffi::c_long;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type __ssize_t = ::core::ffi::c_long;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type __syscall_slong_t = ::core::ffi::c_long;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type __syscall_ulong_t = ::core::ffi::c_ulong;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type __loff_t = __off64_t;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type __caddr_t = *mut ::core::ffi::c_char;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type __intptr_t = ::core::ffi::c_long;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type __socklen_t = ::core::ffi::c_uint;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type __sig_atomic_t = ::core::ffi::c_int;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type int_least8_t = __int_least8_t;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type int_least16_t = __int_least16_t;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type int_least32_t = __int_least32_t;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type int_least64_t = __int_least64_t;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type uint_least8_t = __uint_least8_t;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type uint_least16_t = __uint_least16_t;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type uint_least32_t = __uint_least32_t;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type uint_least64_t = __uint_least64_t;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type int_fast8_t = ::core::ffi::c_schar;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type int_fast16_t = ::core::ffi::c_long;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type int_fast32_t = ::core::ffi::c_long;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type int_fast64_t = ::core::ffi::c_long;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type uint_fast8_t = ::core::ffi::c_uchar;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type uint_fast16_t = ::core::ffi::c_ulong;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type uint_fast32_t = ::core::ffi::c_ulong;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type uint_fast64_t = ::core::ffi::c_ulong;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type intmax_t = __intmax_t;
+#[deprecated(note = "generated from glibc's headers, use `core::ffi` or the `libc` crate")]
+pub type uintmax_t = __uintmax_t;
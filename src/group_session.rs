@@ -0,0 +1,224 @@
+// Copyright 2020 Johannes Hayeß
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A safe wrapper around `OlmInboundGroupSession`.
+
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use core::ffi::c_void;
+
+use crate::backend::olm;
+use crate::error::OlmError;
+use crate::memory::{into_string, object_memory};
+use crate::OlmInboundGroupSession;
+
+use zeroize::Zeroizing;
+
+/// The receiving side of a Megolm session, which decrypts the messages of a
+/// room's sender.
+///
+/// The session is wiped when it's dropped.
+pub struct InboundGroupSession {
+    session: *mut OlmInboundGroupSession,
+    // Backs `session`, so it has to live as long as the session does
+    _memory: Box<[u64]>,
+}
+
+// `libolm` keeps no global state, so a session can move between threads.
+unsafe impl Send for InboundGroupSession {}
+
+impl InboundGroupSession {
+    fn allocate() -> Self {
+        let mut memory = object_memory(unsafe { olm!(olm_inbound_group_session_size()) });
+        let session =
+            unsafe { olm!(olm_inbound_group_session(memory.as_mut_ptr() as *mut c_void)) };
+
+        InboundGroupSession {
+            session,
+            _memory: memory,
+        }
+    }
+
+    /// Starts a session from the signed session key an
+    /// `OlmOutboundGroupSession` shares, e.g. in an `m.room_key` event.
+    pub fn new(session_key: &str) -> Result<Self, OlmError> {
+        let session = InboundGroupSession::allocate();
+        let result = unsafe {
+            olm!(olm_init_inbound_group_session(
+                session.session,
+                session_key.as_ptr(),
+                session_key.len(),
+            ))
+        };
+        session.check(result)?;
+
+        Ok(session)
+    }
+
+    /// Starts a session from a key exported with
+    /// [`InboundGroupSession::export_at`], e.g. from a key backup.
+    pub fn import(exported_key: &str) -> Result<Self, OlmError> {
+        let session = InboundGroupSession::allocate();
+        let result = unsafe {
+            olm!(olm_import_inbound_group_session(
+                session.session,
+                exported_key.as_ptr(),
+                exported_key.len(),
+            ))
+        };
+        session.check(result)?;
+
+        Ok(session)
+    }
+
+    /// Restores a session from a pickle made by
+    /// [`InboundGroupSession::pickle`].
+    pub fn unpickle(key: &[u8], pickle: &str) -> Result<Self, OlmError> {
+        let session = InboundGroupSession::allocate();
+        // Unpickling decrypts in place, leaving the ratchet in the buffer
+        let mut pickle = Zeroizing::new(pickle.as_bytes().to_vec());
+        let result = unsafe {
+            olm!(olm_unpickle_inbound_group_session(
+                session.session,
+                key.as_ptr() as *const c_void,
+                key.len(),
+                pickle.as_mut_ptr() as *mut c_void,
+                pickle.len(),
+            ))
+        };
+        session.check(result)?;
+
+        Ok(session)
+    }
+
+    /// Encrypts the session with `key` into a base64 pickle.
+    pub fn pickle(&self, key: &[u8]) -> String {
+        let length = unsafe { olm!(olm_pickle_inbound_group_session_length(self.session)) };
+        let mut pickle = vec![0; length];
+        let result = unsafe {
+            olm!(olm_pickle_inbound_group_session(
+                self.session,
+                key.as_ptr() as *const c_void,
+                key.len(),
+                pickle.as_mut_ptr() as *mut c_void,
+                pickle.len(),
+            ))
+        };
+        self.check(result).expect("libolm sized the pickle");
+
+        into_string(pickle)
+    }
+
+    /// The base64 session ID, which is the sender's Ed25519 session key.
+    pub fn session_id(&self) -> String {
+        let mut id = vec![0; unsafe { olm!(olm_inbound_group_session_id_length(self.session)) }];
+        let result = unsafe {
+            olm!(olm_inbound_group_session_id(
+                self.session,
+                id.as_mut_ptr(),
+                id.len(),
+            ))
+        };
+        self.check(result).expect("libolm sized the session ID");
+
+        into_string(id)
+    }
+
+    /// The index of the first message the session can decrypt.
+    pub fn first_known_index(&self) -> u32 {
+        unsafe { olm!(olm_inbound_group_session_first_known_index(self.session)) }
+    }
+
+    /// Whether the session was started from a signed session key or has
+    /// decrypted a message since, rather than just being imported.
+    pub fn is_verified(&self) -> bool {
+        unsafe { olm!(olm_inbound_group_session_is_verified(self.session)) != 0 }
+    }
+
+    /// Exports the session as of `message_index`, so that whoever imports it
+    /// can decrypt that and all later messages.
+    ///
+    /// Fails with `OLM_UNKNOWN_MESSAGE_INDEX` if the index is before
+    /// [`InboundGroupSession::first_known_index`].
+    pub fn export_at(&self, message_index: u32) -> Result<String, OlmError> {
+        let length = unsafe { olm!(olm_export_inbound_group_session_length(self.session)) };
+        let mut key = vec![0; length];
+        let result = unsafe {
+            olm!(olm_export_inbound_group_session(
+                self.session,
+                key.as_mut_ptr(),
+                key.len(),
+                message_index,
+            ))
+        };
+        self.check(result)?;
+
+        Ok(into_string(key))
+    }
+
+    /// Decrypts the base64 `message`, returning the plaintext and the
+    /// message's index.
+    pub fn decrypt(&mut self, message: &str) -> Result<(Vec<u8>, u32), OlmError> {
+        // Both functions decode the message in place
+        let mut buffer = message.as_bytes().to_vec();
+        let max_length = unsafe {
+            olm!(olm_group_decrypt_max_plaintext_length(
+                self.session,
+                buffer.as_mut_ptr(),
+                buffer.len(),
+            ))
+        };
+        let max_length = self.check(max_length)?;
+
+        let mut buffer = message.as_bytes().to_vec();
+        let mut plaintext = vec![0; max_length];
+        let mut message_index = 0;
+        let length = unsafe {
+            olm!(olm_group_decrypt(
+                self.session,
+                buffer.as_mut_ptr(),
+                buffer.len(),
+                plaintext.as_mut_ptr(),
+                plaintext.len(),
+                &mut message_index,
+            ))
+        };
+        plaintext.truncate(self.check(length)?);
+
+        Ok((plaintext, message_index))
+    }
+
+    /// The underlying `OlmInboundGroupSession`, for use with the raw bindings.
+    pub fn as_ptr(&self) -> *mut OlmInboundGroupSession {
+        self.session
+    }
+
+    fn check(&self, result: usize) -> Result<usize, OlmError> {
+        if result == unsafe { olm!(olm_error()) } {
+            let code = unsafe { olm!(olm_inbound_group_session_last_error_code(self.session)) };
+            Err(OlmError::new(code))
+        } else {
+            Ok(result)
+        }
+    }
+}
+
+impl Drop for InboundGroupSession {
+    fn drop(&mut self) {
+        unsafe { olm!(olm_clear_inbound_group_session(self.session)) };
+    }
+}
@@ -0,0 +1,339 @@
+// Copyright 2020 Johannes Hayeß
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The Matrix "Megolm session export" file format, for moving room keys
+//! between clients with a passphrase.
+//!
+//! The file is a JSON array of [`ExportedRoomKey`]s, encrypted with
+//! AES-256-CTR and authenticated with HMAC-SHA-256 under keys derived from the
+//! passphrase with PBKDF2-HMAC-SHA-512, then armoured as base64:
+//!
+//! ```text
+//! -----BEGIN MEGOLM SESSION DATA-----
+//! AXNhbHQ…
+//! -----END MEGOLM SESSION DATA-----
+//! ```
+//!
+//! ```no_run
+//! use olm_sys::group_session::InboundGroupSession;
+//! use olm_sys::key_export::{export_room_keys, import_room_keys, ExportedRoomKey};
+//! # use olm_sys::random::RandomSource;
+//! # struct Random;
+//! # impl RandomSource for Random {
+//! #     fn fill_bytes(&mut self, buffer: &mut [u8]) {}
+//! # }
+//! # fn main() -> Result<(), olm_sys::key_export::KeyExportError> {
+//! # let session = InboundGroupSession::new("").unwrap();
+//!
+//! let key = ExportedRoomKey::new("!room:example.org", "<sender curve25519>", &session);
+//! let file = export_room_keys(&[key], "passphrase", 500_000, &mut Random);
+//!
+//! for key in import_room_keys(&file, "passphrase")? {
+//!     let session = key.session().expect("Element exports valid session keys");
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use alloc::borrow::ToOwned;
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use core::fmt;
+
+use serde_json::{json, Map, Value};
+use zeroize::Zeroizing;
+
+use crate::crypto::{self, AES_BLOCK_LENGTH, SHA256_LENGTH};
+use crate::error::OlmError;
+use crate::group_session::InboundGroupSession;
+use crate::random::RandomSource;
+
+/// The only algorithm room keys are exported for.
+pub const MEGOLM_ALGORITHM: &str = "m.megolm.v1.aes-sha2";
+
+const HEADER: &str = "-----BEGIN MEGOLM SESSION DATA-----";
+const FOOTER: &str = "-----END MEGOLM SESSION DATA-----";
+const VERSION: u8 = 1;
+const SALT_LENGTH: usize = 16;
+// The version, salt, IV and number of rounds
+const PREFIX_LENGTH: usize = 1 + SALT_LENGTH + AES_BLOCK_LENGTH + 4;
+// Element wraps its exports at the same width
+const LINE_LENGTH: usize = 96;
+
+/// A room key as it appears in an export file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExportedRoomKey {
+    /// The room the session encrypts messages for.
+    pub room_id: String,
+    /// The Curve25519 identity key of the device that created the session.
+    pub sender_key: String,
+    /// The ID of the session, see [`InboundGroupSession::session_id`].
+    pub session_id: String,
+    /// The session exported at its first known index, see
+    /// [`InboundGroupSession::export_at`].
+    pub session_key: String,
+    /// Keys the sending device claims to own, usually just its `ed25519` key.
+    pub sender_claimed_keys: BTreeMap<String, String>,
+    /// The Curve25519 keys of the devices the session was forwarded through.
+    pub forwarding_curve25519_key_chain: Vec<String>,
+}
+
+impl ExportedRoomKey {
+    /// Exports `session` from its first known index, without claimed keys or
+    /// forwarding chain.
+    pub fn new(room_id: &str, sender_key: &str, session: &InboundGroupSession) -> Self {
+        let session_key = session
+            .export_at(session.first_known_index())
+            .expect("the first known index can be exported");
+
+        ExportedRoomKey {
+            room_id: room_id.to_owned(),
+            sender_key: sender_key.to_owned(),
+            session_id: session.session_id(),
+            session_key,
+            sender_claimed_keys: BTreeMap::new(),
+            forwarding_curve25519_key_chain: Vec::new(),
+        }
+    }
+
+    /// Imports the session key into a new session.
+    pub fn session(&self) -> Result<InboundGroupSession, OlmError> {
+        InboundGroupSession::import(&self.session_key)
+    }
+
+    fn to_json(&self) -> Value {
+        json!({
+            "algorithm": MEGOLM_ALGORITHM,
+            "forwarding_curve25519_key_chain": self.forwarding_curve25519_key_chain,
+            "room_id": self.room_id,
+            "sender_claimed_keys": self.sender_claimed_keys,
+            "sender_key": self.sender_key,
+            "session_id": self.session_id,
+            "session_key": self.session_key,
+        })
+    }
+
+    fn from_json(key: &Map<String, Value>) -> Option<Self> {
+        let string = |field: &str| key.get(field)?.as_str().map(str::to_owned);
+
+        let (sender_claimed_keys, forwarding_curve25519_key_chain) = parse_provenance(key)?;
+
+        Some(ExportedRoomKey {
+            room_id: string("room_id")?,
+            sender_key: string("sender_key")?,
+            session_id: string("session_id")?,
+            session_key: string("session_key")?,
+            sender_claimed_keys,
+            forwarding_curve25519_key_chain,
+        })
+    }
+}
+
+/// Parses the optional `sender_claimed_keys` and
+/// `forwarding_curve25519_key_chain` of a room key, which backed up keys have
+/// too.
+pub(crate) fn parse_provenance(
+    key: &Map<String, Value>,
+) -> Option<(BTreeMap<String, String>, Vec<String>)> {
+    let sender_claimed_keys = match key.get("sender_claimed_keys") {
+        None => BTreeMap::new(),
+        Some(keys) => keys
+            .as_object()?
+            .iter()
+            .map(|(algorithm, key)| Some((algorithm.clone(), key.as_str()?.to_owned())))
+            .collect::<Option<_>>()?,
+    };
+    let forwarding_curve25519_key_chain = match key.get("forwarding_curve25519_key_chain") {
+        None => Vec::new(),
+        Some(chain) => chain
+            .as_array()?
+            .iter()
+            .map(|key| key.as_str().map(str::to_owned))
+            .collect::<Option<_>>()?,
+    };
+
+    Some((sender_claimed_keys, forwarding_curve25519_key_chain))
+}
+
+/// Why an export file couldn't be imported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KeyExportError {
+    /// The file isn't armoured base64 or is too short.
+    InvalidFormat,
+    /// The file has a version other than 1.
+    UnsupportedVersion(u8),
+    /// The MAC doesn't match, usually because the passphrase is wrong.
+    BadMac,
+    /// The decrypted file isn't an array of room keys.
+    InvalidJson,
+}
+
+impl fmt::Display for KeyExportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeyExportError::InvalidFormat => write!(f, "not a Megolm session export"),
+            KeyExportError::UnsupportedVersion(version) => {
+                write!(f, "unsupported session export version {}", version)
+            }
+            KeyExportError::BadMac => write!(f, "wrong passphrase or corrupted export"),
+            KeyExportError::InvalidJson => write!(f, "invalid room keys in the export"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for KeyExportError {}
+
+/// Encrypts `keys` with `passphrase` into an export file.
+///
+/// `rounds` PBKDF2 iterations protect the passphrase against brute force, the
+/// Matrix clients use several hundred thousand.
+///
+/// # Panics
+///
+/// If `rounds` is 0.
+pub fn export_room_keys(
+    keys: &[ExportedRoomKey],
+    passphrase: &str,
+    rounds: u32,
+    random: &mut impl RandomSource,
+) -> String {
+    assert!(rounds > 0, "PBKDF2 needs at least one round");
+
+    let mut salt = [0; SALT_LENGTH];
+    let mut iv = [0; AES_BLOCK_LENGTH];
+    random.fill_bytes(&mut salt);
+    random.fill_bytes(&mut iv);
+    // Leaves room for 2^63 blocks before the counter wraps, which some
+    // implementations of AES-CTR don't handle
+    iv[8] &= 0x7f;
+
+    let (aes_key, hmac_key) = derive_keys(passphrase, &salt, rounds);
+    let mut plaintext = Zeroizing::new(
+        serde_json::to_vec(
+            &keys
+                .iter()
+                .map(ExportedRoomKey::to_json)
+                .collect::<Vec<_>>(),
+        )
+        .expect("room keys serialize to JSON"),
+    );
+    crypto::aes256_ctr(&aes_key, &iv, &mut plaintext);
+
+    let mut data = Vec::with_capacity(PREFIX_LENGTH + plaintext.len() + SHA256_LENGTH);
+    data.push(VERSION);
+    data.extend_from_slice(&salt);
+    data.extend_from_slice(&iv);
+    data.extend_from_slice(&rounds.to_be_bytes());
+    data.extend_from_slice(&plaintext);
+    let mac = crypto::hmac_sha256(&hmac_key[..], &data);
+    data.extend_from_slice(&mac);
+
+    let encoded = crate::base64::encode_padded(&data);
+
+    let mut file = String::from(HEADER);
+    file.push('\n');
+    for line in encoded.as_bytes().chunks(LINE_LENGTH) {
+        file.push_str(core::str::from_utf8(line).unwrap());
+        file.push('\n');
+    }
+    file.push_str(FOOTER);
+    file.push('\n');
+
+    file
+}
+
+/// Decrypts an export file made by [`export_room_keys`] or another Matrix
+/// client.
+///
+/// Keys of algorithms other than [`MEGOLM_ALGORITHM`] are skipped.
+pub fn import_room_keys(
+    file: &str,
+    passphrase: &str,
+) -> Result<Vec<ExportedRoomKey>, KeyExportError> {
+    let body = file
+        .trim()
+        .strip_prefix(HEADER)
+        .and_then(|body| body.strip_suffix(FOOTER))
+        .ok_or(KeyExportError::InvalidFormat)?;
+    let encoded: String = body.split_whitespace().collect();
+    let data = crate::base64::decode(&encoded).ok_or(KeyExportError::InvalidFormat)?;
+
+    if data.len() < PREFIX_LENGTH + SHA256_LENGTH {
+        return Err(KeyExportError::InvalidFormat);
+    }
+    if data[0] != VERSION {
+        return Err(KeyExportError::UnsupportedVersion(data[0]));
+    }
+
+    let (authenticated, mac) = data.split_at(data.len() - SHA256_LENGTH);
+    let salt = &authenticated[1..1 + SALT_LENGTH];
+    let iv: [u8; AES_BLOCK_LENGTH] = authenticated[1 + SALT_LENGTH..PREFIX_LENGTH - 4]
+        .try_into()
+        .unwrap();
+    let rounds = u32::from_be_bytes(
+        authenticated[PREFIX_LENGTH - 4..PREFIX_LENGTH]
+            .try_into()
+            .unwrap(),
+    );
+    if rounds == 0 {
+        return Err(KeyExportError::InvalidFormat);
+    }
+
+    let (aes_key, hmac_key) = derive_keys(passphrase, salt, rounds);
+    if !crypto::mac_matches(&crypto::hmac_sha256(&hmac_key[..], authenticated), mac) {
+        return Err(KeyExportError::BadMac);
+    }
+
+    let mut plaintext = Zeroizing::new(authenticated[PREFIX_LENGTH..].to_vec());
+    crypto::aes256_ctr(&aes_key, &iv, &mut plaintext);
+
+    let keys: Value =
+        serde_json::from_slice(&plaintext).map_err(|_| KeyExportError::InvalidJson)?;
+    keys.as_array()
+        .ok_or(KeyExportError::InvalidJson)?
+        .iter()
+        .map(|key| key.as_object().ok_or(KeyExportError::InvalidJson))
+        .filter(|key| {
+            key.map_or(true, |key| {
+                key.get("algorithm").and_then(Value::as_str) == Some(MEGOLM_ALGORITHM)
+            })
+        })
+        .map(|key| ExportedRoomKey::from_json(key?).ok_or(KeyExportError::InvalidJson))
+        .collect()
+}
+
+/// The AES and HMAC keys for `passphrase`.
+fn derive_keys(
+    passphrase: &str,
+    salt: &[u8],
+    rounds: u32,
+) -> (Zeroizing<[u8; 32]>, Zeroizing<[u8; 32]>) {
+    let mut keys = Zeroizing::new([0; 64]);
+    crypto::pbkdf2(
+        crypto::hmac_sha512,
+        passphrase.as_bytes(),
+        salt,
+        rounds,
+        keys.as_mut(),
+    );
+
+    let (aes_key, hmac_key) = keys.split_at(32);
+    (
+        Zeroizing::new(aes_key.try_into().unwrap()),
+        Zeroizing::new(hmac_key.try_into().unwrap()),
+    )
+}
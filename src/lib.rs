@@ -17,7 +17,7 @@
 //! ## Supported Platforms
 //!
 //! - Android
-//! - Linux
+//! - Linux (glibc and musl)
 //! - macOS
 //! - Windows
 //! - FreeBSD
@@ -34,9 +34,100 @@
 //! #### Build dependencies
 //!
 //! - `libstdc++`/`libc++`
-//! - cmake
-//! - GNU make or a compatible variant (WebAssembly only)
-//! - Emscripten (WebAssembly only)
+//! - cmake ([requires v3.12](https://github.com/alexcrichton/cmake-rs/issues/131))
+//! - clang with the WebAssembly backend (`wasm32-unknown-unknown` and `wasm32-wasi` only)
+//! - GNU make or a compatible variant (`wasm32-unknown-emscripten` only)
+//! - Emscripten (`wasm32-unknown-emscripten` only)
+//!
+//! #### C++ standard library
+//!
+//! Parts of `libolm` are written in C++, so the static build links the C++ standard
+//! library of the target: `libstdc++` on Linux, Android and illumos, `libc++` on
+//! macOS, iOS and FreeBSD. On `*-linux-musl` targets `libstdc++` is linked
+//! statically, so the resulting binaries stay fully static; the `libstdc++.a`
+//! of the C++ compiler used for the target (`CXX_<target>`) is picked.
+//!
+//! To use a different library set `OLM_CXX_STDLIB` to a value in the syntax of
+//! `cargo:rustc-link-lib`:
+//!
+//! ```bash
+//! $ OLM_CXX_STDLIB=static=c++ cargo build --target x86_64-unknown-linux-musl
+//! ```
+//!
+//! Setting it to `none` builds `libolm` without any C++ standard library, which
+//! avoids clashes with the C++ runtime linked by other crates. The C++ sources are
+//! then compiled with `-fno-exceptions -fno-rtti` against minimal headers shipped
+//! with this crate, and the C++ runtime symbols they could reference
+//! (`operator new`/`delete`, `__cxa_pure_virtual`) are provided in Rust. This is
+//! not supported with MSVC.
+//!
+//! ```bash
+//! $ OLM_CXX_STDLIB=none cargo build
+//! ```
+//!
+//! #### Build options
+//!
+//! Extra options for the build of the vendored `libolm` can be given with two
+//! environment variables:
+//!
+//! - `OLM_CMAKE_ARGS`: whitespace separated arguments for configuring `libolm`
+//!   with CMake, usually `-DNAME=VALUE` definitions. `CMAKE_BUILD_TYPE` selects
+//!   the build profile, which otherwise follows the one of Cargo.
+//! - `OLM_CFLAGS`: whitespace separated flags for both the C and the C++ compiler.
+//!
+//! Both are split on whitespace without any quoting or escaping, so a single
+//! argument can't contain spaces, e.g. a path to a directory with spaces in its
+//! name. Such a path has to be given some other way, like a symlink without spaces
+//! or CMake's own environment variables.
+//!
+//! For example, a hardened build with debug info:
+//!
+//! ```bash
+//! $ OLM_CMAKE_ARGS="-DCMAKE_BUILD_TYPE=RelWithDebInfo" \
+//!   OLM_CFLAGS="-fstack-protector-strong -D_FORTIFY_SOURCE=2" \
+//!   cargo build --release
+//! ```
+//!
+//! #### Sanitizers
+//!
+//! When the Rust code is built with a sanitizer (`-Zsanitizer=...`) the vendored
+//! `libolm` is instrumented for it as well, so that e.g. MemorySanitizer knows about
+//! the memory `libolm` writes. The `address`, `hwaddress`, `leak`, `memory` and
+//! `thread` sanitizers are supported. As the runtime linked by rustc is the one of
+//! LLVM, `libolm` should be built with clang:
+//!
+//! ```bash
+//! $ CC=clang CXX=clang++ RUSTFLAGS="-Zsanitizer=address" \
+//!   cargo +nightly test --target x86_64-unknown-linux-gnu
+//! ```
+//!
+//! `OLM_SANITIZER` overrides the sanitizers, given as a comma separated list, e.g.
+//! for instrumenting only `libolm`. The sanitizer runtime then has to be linked
+//! some other way. An empty `OLM_SANITIZER` is ignored like an unset one.
+//!
+//! #### libolm's test suite
+//!
+//! With the `libolm-tests` feature the C++ test suite of the vendored `libolm` is
+//! built along with the library, and run as part of this crate's tests:
+//!
+//! ```bash
+//! $ cargo test --features libolm-tests --test libolm
+//! ```
+//!
+//! This catches toolchains or build options which miscompile `libolm`. It needs
+//! the C++ standard library, so it can't be combined with `OLM_CXX_STDLIB=none`.
+//!
+//! #### Symbol prefixing
+//!
+//! A static `libolm` exports all of its symbols, so linking it into a binary which
+//! already contains another copy of `libolm`, e.g. embedded in a C library, leads
+//! to clashes. With the `prefix-symbols` feature every symbol defined by the
+//! vendored `libolm` is renamed to `olmsys_<symbol>` (`olm_account` becomes
+//! `olmsys_olm_account`), and the bindings link against the renamed symbols.
+//!
+//! This uses `nm` and `objcopy`, which can be overridden with the `NM` and
+//! `OBJCOPY` environment variables, e.g. to use `llvm-objcopy`. The feature only
+//! works with the static build.
 //!
 //! ### Dynamic
 //!
@@ -49,6 +140,64 @@
 //! $ OLM_LINK_VARIANT=dylib cargo build
 //! ```
 //!
+//! The `libolm` found at runtime may be older than the one these bindings were
+//! generated from. Use `olm_sys::version::check_compatible()` at start-up to find
+//! out whether it lacks any of the functionality the bindings expose, for example
+//! fallback keys on anything older than v3.2.0.
+//!
+//! ### Loading at runtime
+//!
+//! With the `runtime-dlopen` feature `libolm` isn't linked at all. Instead it is
+//! loaded at runtime from a path of your choosing, so your application can still
+//! start if it is missing:
+//!
+//! ```rust,ignore
+//! use olm_sys::backend::{self, OlmLibrary};
+//!
+//! let library = unsafe { OlmLibrary::new("libolm.so.3")? };
+//! backend::install(library).ok();
+//! olm_sys::version::check_compatible()?;
+//! ```
+//!
+//! The raw functions are available as methods of `OlmLibrary`. Functions that
+//! are missing from the loaded library are reported by `check_compatible()`, and
+//! panic when called. That includes the internal `_olm_*` functions some safe
+//! wrappers need, which libraries built with `libolm`'s Makefile don't export. The
+//! safe wrappers of this crate call through the installed library.
+//!
+//! ### Using libolm from other build scripts
+//!
+//! Build scripts of crates depending on `olm-sys` can compile their own C code
+//! against the `libolm` this crate links to, using the environment variables
+//! Cargo passes them:
+//!
+//! - `DEP_OLM_INCLUDE`: the directory holding the `olm/*.h` headers
+//! - `DEP_OLM_ROOT`: the installation prefix of `libolm`
+//! - `DEP_OLM_VERSION`: the version of `libolm`, e.g. `3.2.7`
+//! - `DEP_OLM_STATIC`: `1` if `libolm` is linked statically, `0` otherwise
+//!
+//! When linking dynamically the system's `libolm` is looked up with pkg-config.
+//! If it can't be found, the vendored headers and version are published instead.
+//!
+//! ### Building for WebAssembly
+//!
+//! WebAssembly builds are always static. For `wasm32-unknown-unknown` and
+//! `wasm32-wasi` `libolm` is compiled with clang directly, without a C++ standard
+//! library, so the result links cleanly with `wasm-bindgen`:
+//!
+//! ```bash
+//! $ cargo build --target wasm32-unknown-unknown
+//! ```
+//!
+//! For `wasm32-wasi`, point the `WASI_SYSROOT` environment variable to the sysroot
+//! of your [wasi-sdk](https://github.com/WebAssembly/wasi-sdk) installation:
+//!
+//! ```bash
+//! $ WASI_SYSROOT=/opt/wasi-sdk/share/wasi-sysroot cargo build --target wasm32-wasi
+//! ```
+//!
+//! `wasm32-unknown-emscripten` keeps using `libolm`'s own Makefile and Emscripten.
+//!
 //! ### Cross compiling for Android
 //!
 //! To enable cross compilation for Android set the environment variable
@@ -72,259 +221,144 @@
 //! ```bash
 //! $ ANDROID_NDK=~/Android/Sdk/ndk/22.0.7026061 cargo build --target aarch64-linux-android
 //! ```
+//!
+//! ### Cross compiling for iOS
+//!
+//! To enable cross compilation for iOS, set the environment variable
+//! `IOS_SDK_PATH` to the iOS SDK location by running:
+//!
+//! ```bash
+//! $ export IOS_SDK_PATH=`xcrun --show-sdk-path --sdk iphoneos`
+//! ```
+//!
+//! ## Safe wrappers and `no_std`
+//!
+//! Besides the raw bindings, the crate has a few safe wrappers, like
+//! `olm_sys::account::Account`. They take their randomness from an
+//! `olm_sys::random::RandomSource` implementation instead of an operating system,
+//! since `libolm` expects its callers to provide random bytes.
+//!
+//! The bindings and the safe wrappers only need `core` and `alloc`. Disabling the
+//! default `std` feature makes the crate `no_std`, for example for embedded key
+//! storage:
+//!
+//! ```toml
+//! [dependencies]
+//! olm-sys = { version = "1", default-features = false }
+//! ```
+//!
+//! Without `std` the error types don't implement `std::error::Error`, and the
+//! `runtime-dlopen` feature isn't available.
+//!
+//! ## Matrix key formats
+//!
+//! The `matrix` feature adds the file and message formats Matrix builds on top of
+//! `libolm`, using the hashes and ciphers `libolm` exports. The one exception is
+//! SHA-512 for passphrases, which shared builds of `libolm` don't export and comes
+//! from the `sha2` crate. It needs `serde_json` and `sha2`, but no `std`:
+//!
+//! ```toml
+//! [dependencies]
+//! olm-sys = { version = "1", features = ["matrix"] }
+//! ```
+//!
+//! `olm_sys::key_export` reads and writes the passphrase protected room key files
+//! of Element's "Export E2E room keys". `olm_sys::backup` encrypts room keys for
+//! server-side key backup and decrypts them with the base58 recovery key.
+//! `olm_sys::secret_storage` encrypts secrets for the account data with a secret
+//! storage key, `m.secret_storage.v1.aes-hmac-sha2`.
+//!
+//! `olm_sys::signing` signs and verifies JSON objects over their canonical JSON,
+//! with an `Account` or a `PkSigning` key.
+//! `olm_sys::cross_signing` holds a user's master, self-signing and user-signing
+//! keys and verifies the chains of signatures they make.
+//! `Account::device_keys` and `Account::signed_one_time_keys` build the signed
+//! bodies of `/keys/upload`, and `olm_sys::one_time_keys` decides how many
+//! one-time keys to upload and when to replace the fallback key, from the
+//! publication and first-use times `olm_sys::fallback_keys` keeps next to the
+//! account.
+//!
+//! ## Benchmarks
+//!
+//! `benches/olm.rs` measures the hot paths of a Matrix client with
+//! [Criterion](https://github.com/bheisler/criterion.rs): group decryption at
+//! several message indices, decryption of pre-key and normal messages, inbound
+//! session creation, account (un)pickling and `olm_pk_decrypt`. All inputs are
+//! deterministic, so comparing runs before and after updating the vendored
+//! `libolm` shows regressions:
+//!
+//! ```bash
+//! $ cargo bench --bench olm -- --save-baseline before
+//! $ cargo bench --bench olm -- --baseline before
+//! ```
+//!
+//! ## Fuzzing
+//!
+//! The `fuzz/` directory contains [cargo-fuzz](https://github.com/rust-fuzz/cargo-fuzz)
+//! targets for the entry points `libolm`'s own fuzzers in `olm/fuzzing` cover
+//! (`decrypt`, `group_decrypt`, `decode_message`, `unpickle_account`,
+//! `unpickle_session` and `unpickle_megolm_outbound`), plus `pk_decrypt` and
+//! `unpickle_pk_decryption`. Seed corpora built from `libolm`'s test vectors are
+//! in `fuzz/seeds`. Fuzzing needs a nightly toolchain:
+//!
+//! ```bash
+//! $ cargo +nightly fuzz run decrypt fuzz/corpus/decrypt fuzz/seeds/decrypt
+//! ```
+//!
+//! The unpickle targets take raw, unencrypted pickles and encrypt them before
+//! unpickling, so that the fuzzer gets past the pickle's MAC.
 
+#![cfg_attr(not(feature = "std"), no_std)]
 #![allow(non_upper_case_globals)]
 #![allow(non_camel_case_types)]
 #![allow(non_snake_case)]
 
+extern crate alloc;
+
+pub mod account;
+pub mod backend;
+#[cfg(feature = "matrix")]
+pub mod backup;
+#[cfg(feature = "matrix")]
+mod base58;
+mod base64;
+#[cfg(feature = "matrix")]
+pub mod cross_signing;
+#[cfg(feature = "matrix")]
+mod crypto;
+#[cfg(olm_sys_no_cxx_stdlib)]
+mod cxxrt;
+#[cfg(feature = "runtime-dlopen")]
+#[allow(clippy::all)]
+mod dynamic;
+pub mod error;
+#[cfg(feature = "matrix")]
+pub mod fallback_keys;
+mod glibc;
+pub mod group_session;
+#[cfg(feature = "matrix")]
+pub mod key_export;
+pub mod megolm;
+mod memory;
+pub mod message;
+#[cfg(feature = "matrix")]
+pub mod one_time_keys;
+#[cfg(feature = "matrix")]
+mod pickle;
+pub mod pk;
+pub mod random;
+#[cfg(feature = "matrix")]
+pub mod secret_storage;
+#[cfg(feature = "matrix")]
+pub mod signing;
+pub mod version;
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+mod wasm;
+
+pub use glibc::*;
+
 /* automatically generated by rust-bindgen */
 
-pub const _STDINT_H: u32 = 1;
-pub const _FEATURES_H: u32 = 1;
-pub const _DEFAULT_SOURCE: u32 = 1;
-pub const __GLIBC_USE_ISOC2X: u32 = 0;
-pub const __USE_ISOC11: u32 = 1;
-pub const __USE_ISOC99: u32 = 1;
-pub const __USE_ISOC95: u32 = 1;
-pub const __USE_POSIX_IMPLICITLY: u32 = 1;
-pub const _POSIX_SOURCE: u32 = 1;
-pub const _POSIX_C_SOURCE: u32 = 200809;
-pub const __USE_POSIX: u32 = 1;
-pub const __USE_POSIX2: u32 = 1;
-pub const __USE_POSIX199309: u32 = 1;
-pub const __USE_POSIX199506: u32 = 1;
-pub const __USE_XOPEN2K: u32 = 1;
-pub const __USE_XOPEN2K8: u32 = 1;
-pub const _ATFILE_SOURCE: u32 = 1;
-pub const __USE_MISC: u32 = 1;
-pub const __USE_ATFILE: u32 = 1;
-pub const __USE_FORTIFY_LEVEL: u32 = 0;
-pub const __GLIBC_USE_DEPRECATED_GETS: u32 = 0;
-pub const __GLIBC_USE_DEPRECATED_SCANF: u32 = 0;
-pub const _STDC_PREDEF_H: u32 = 1;
-pub const __STDC_IEC_559__: u32 = 1;
-pub const __STDC_IEC_559_COMPLEX__: u32 = 1;
-pub const __STDC_ISO_10646__: u32 = 201706;
-pub const __GNU_LIBRARY__: u32 = 6;
-pub const __GLIBC__: u32 = 2;
-pub const __GLIBC_MINOR__: u32 = 33;
-pub const _SYS_CDEFS_H: u32 = 1;
-pub const __glibc_c99_flexarr_available: u32 = 1;
-pub const __WORDSIZE: u32 = 64;
-pub const __WORDSIZE_TIME64_COMPAT32: u32 = 1;
-pub const __SYSCALL_WORDSIZE: u32 = 64;
-pub const __LDOUBLE_REDIRECTS_TO_FLOAT128_ABI: u32 = 0;
-pub const __HAVE_GENERIC_SELECTION: u32 = 1;
-pub const __GLIBC_USE_LIB_EXT2: u32 = 0;
-pub const __GLIBC_USE_IEC_60559_BFP_EXT: u32 = 0;
-pub const __GLIBC_USE_IEC_60559_BFP_EXT_C2X: u32 = 0;
-pub const __GLIBC_USE_IEC_60559_FUNCS_EXT: u32 = 0;
-pub const __GLIBC_USE_IEC_60559_FUNCS_EXT_C2X: u32 = 0;
-pub const __GLIBC_USE_IEC_60559_TYPES_EXT: u32 = 0;
-pub const _BITS_TYPES_H: u32 = 1;
-pub const __TIMESIZE: u32 = 64;
-pub const _BITS_TYPESIZES_H: u32 = 1;
-pub const __OFF_T_MATCHES_OFF64_T: u32 = 1;
-pub const __INO_T_MATCHES_INO64_T: u32 = 1;
-pub const __RLIM_T_MATCHES_RLIM64_T: u32 = 1;
-pub const __STATFS_MATCHES_STATFS64: u32 = 1;
-pub const __KERNEL_OLD_TIMEVAL_MATCHES_TIMEVAL64: u32 = 1;
-pub const __FD_SETSIZE: u32 = 1024;
-pub const _BITS_TIME64_H: u32 = 1;
-pub const _BITS_WCHAR_H: u32 = 1;
-pub const _BITS_STDINT_INTN_H: u32 = 1;
-pub const _BITS_STDINT_UINTN_H: u32 = 1;
-pub const INT8_MIN: i32 = -128;
-pub const INT16_MIN: i32 = -32768;
-pub const INT32_MIN: i32 = -2147483648;
-pub const INT8_MAX: u32 = 127;
-pub const INT16_MAX: u32 = 32767;
-pub const INT32_MAX: u32 = 2147483647;
-pub const UINT8_MAX: u32 = 255;
-pub const UINT16_MAX: u32 = 65535;
-pub const UINT32_MAX: u32 = 4294967295;
-pub const INT_LEAST8_MIN: i32 = -128;
-pub const INT_LEAST16_MIN: i32 = -32768;
-pub const INT_LEAST32_MIN: i32 = -2147483648;
-pub const INT_LEAST8_MAX: u32 = 127;
-pub const INT_LEAST16_MAX: u32 = 32767;
-pub const INT_LEAST32_MAX: u32 = 2147483647;
-pub const UINT_LEAST8_MAX: u32 = 255;
-pub const UINT_LEAST16_MAX: u32 = 65535;
-pub const UINT_LEAST32_MAX: u32 = 4294967295;
-pub const INT_FAST8_MIN: i32 = -128;
-pub const INT_FAST16_MIN: i64 = -9223372036854775808;
-pub const INT_FAST32_MIN: i64 = -9223372036854775808;
-pub const INT_FAST8_MAX: u32 = 127;
-pub const INT_FAST16_MAX: u64 = 9223372036854775807;
-pub const INT_FAST32_MAX: u64 = 9223372036854775807;
-pub const UINT_FAST8_MAX: u32 = 255;
-pub const UINT_FAST16_MAX: i32 = -1;
-pub const UINT_FAST32_MAX: i32 = -1;
-pub const INTPTR_MIN: i64 = -9223372036854775808;
-pub const INTPTR_MAX: u64 = 9223372036854775807;
-pub const UINTPTR_MAX: i32 = -1;
-pub const PTRDIFF_MIN: i64 = -9223372036854775808;
-pub const PTRDIFF_MAX: u64 = 9223372036854775807;
-pub const SIG_ATOMIC_MIN: i32 = -2147483648;
-pub const SIG_ATOMIC_MAX: u32 = 2147483647;
-pub const SIZE_MAX: i32 = -1;
-pub const WINT_MIN: u32 = 0;
-pub const WINT_MAX: u32 = 4294967295;
-pub type wchar_t = ::std::os::raw::c_int;
-#[repr(C)]
-#[repr(align(16))]
-#[derive(Debug, Copy, Clone)]
-pub struct max_align_t {
-    pub __clang_max_align_nonce1: ::std::os::raw::c_longlong,
-    pub __bindgen_padding_0: u64,
-    pub __clang_max_align_nonce2: u128,
-}
-#[test]
-fn bindgen_test_layout_max_align_t() {
-    assert_eq!(
-        ::std::mem::size_of::<max_align_t>(),
-        32usize,
-        concat!("Size of: ", stringify!(max_align_t))
-    );
-    assert_eq!(
-        ::std::mem::align_of::<max_align_t>(),
-        16usize,
-        concat!("Alignment of ", stringify!(max_align_t))
-    );
-    assert_eq!(
-        unsafe {
-            &(*(::std::ptr::null::<max_align_t>())).__clang_max_align_nonce1 as *const _ as usize
-        },
-        0usize,
-        concat!(
-            "Offset of field: ",
-            stringify!(max_align_t),
-            "::",
-            stringify!(__clang_max_align_nonce1)
-        )
-    );
-    assert_eq!(
-        unsafe {
-            &(*(::std::ptr::null::<max_align_t>())).__clang_max_align_nonce2 as *const _ as usize
-        },
-        16usize,
-        concat!(
-            "Offset of field: ",
-            stringify!(max_align_t),
-            "::",
-            stringify!(__clang_max_align_nonce2)
-        )
-    );
-}
-pub type __u_char = ::std::os::raw::c_uchar;
-pub type __u_short = ::std::os::raw::c_ushort;
-pub type __u_int = ::std::os::raw::c_uint;
-pub type __u_long = ::std::os::raw::c_ulong;
-pub type __int8_t = ::std::os::raw::c_schar;
-pub type __uint8_t = ::std::os::raw::c_uchar;
-pub type __int16_t = ::std::os::raw::c_short;
-pub type __uint16_t = ::std::os::raw::c_ushort;
-pub type __int32_t = ::std::os::raw::c_int;
-pub type __uint32_t = ::std::os::raw::c_uint;
-pub type __int64_t = ::std::os::raw::c_long;
-pub type __uint64_t = ::std::os::raw::c_ulong;
-pub type __int_least8_t = __int8_t;
-pub type __uint_least8_t = __uint8_t;
-pub type __int_least16_t = __int16_t;
-pub type __uint_least16_t = __uint16_t;
-pub type __int_least32_t = __int32_t;
-pub type __uint_least32_t = __uint32_t;
-pub type __int_least64_t = __int64_t;
-pub type __uint_least64_t = __uint64_t;
-pub type __quad_t = ::std::os::raw::c_long;
-pub type __u_quad_t = ::std::os::raw::c_ulong;
-pub type __intmax_t = ::std::os::raw::c_long;
-pub type __uintmax_t = ::std::os::raw::c_ulong;
-pub type __dev_t = ::std::os::raw::c_ulong;
-pub type __uid_t = ::std::os::raw::c_uint;
-pub type __gid_t = ::std::os::raw::c_uint;
-pub type __ino_t = ::std::os::raw::c_ulong;
-pub type __ino64_t = ::std::os::raw::c_ulong;
-pub type __mode_t = ::std::os::raw::c_uint;
-pub type __nlink_t = ::std::os::raw::c_ulong;
-pub type __off_t = ::std::os::raw::c_long;
-pub type __off64_t = ::std::os::raw::c_long;
-pub type __pid_t = ::std::os::raw::c_int;
-#[repr(C)]
-#[derive(Debug, Copy, Clone)]
-pub struct __fsid_t {
-    pub __val: [::std::os::raw::c_int; 2usize],
-}
-#[test]
-fn bindgen_test_layout___fsid_t() {
-    assert_eq!(
-        ::std::mem::size_of::<__fsid_t>(),
-        8usize,
-        concat!("Size of: ", stringify!(__fsid_t))
-    );
-    assert_eq!(
-        ::std::mem::align_of::<__fsid_t>(),
-        4usize,
-        concat!("Alignment of ", stringify!(__fsid_t))
-    );
-    assert_eq!(
-        unsafe { &(*(::std::ptr::null::<__fsid_t>())).__val as *const _ as usize },
-        0usize,
-        concat!(
-            "Offset of field: ",
-            stringify!(__fsid_t),
-            "::",
-            stringify!(__val)
-        )
-    );
-}
-pub type __clock_t = ::std::os::raw::c_long;
-pub type __rlim_t = ::std::os::raw::c_ulong;
-pub type __rlim64_t = ::std::os::raw::c_ulong;
-pub type __id_t = ::std::os::raw::c_uint;
-pub type __time_t = ::std::os::raw::c_long;
-pub type __useconds_t = ::std::os::raw::c_uint;
-pub type __suseconds_t = ::std::os::raw::c_long;
-pub type __suseconds64_t = ::std::os::raw::c_long;
-pub type __daddr_t = ::std::os::raw::c_int;
-pub type __key_t = ::std::os::raw::c_int;
-pub type __clockid_t = ::std::os::raw::c_int;
-pub type __timer_t = *mut ::std::os::raw::c_void;
-pub type __blksize_t = ::std::os::raw::c_long;
-pub type __blkcnt_t = ::std::os::raw::c_long;
-pub type __blkcnt64_t = ::std::os::raw::c_long;
-pub type __fsblkcnt_t = ::std::os::raw::c_ulong;
-pub type __fsblkcnt64_t = ::std::os::raw::c_ulong;
-pub type __fsfilcnt_t = ::std::os::raw::c_ulong;
-pub type __fsfilcnt64_t = ::std::os::raw::c_ulong;
-pub type __fsword_t = ::std::os::raw::c_long;
-pub type __ssize_t = ::std::os::raw::c_long;
-pub type __syscall_slong_t = ::std::os::raw::c_long;
-pub type __syscall_ulong_t = ::std::os::raw::c_ulong;
-pub type __loff_t = __off64_t;
-pub type __caddr_t = *mut ::std::os::raw::c_char;
-pub type __intptr_t = ::std::os::raw::c_long;
-pub type __socklen_t = ::std::os::raw::c_uint;
-pub type __sig_atomic_t = ::std::os::raw::c_int;
-pub type int_least8_t = __int_least8_t;
-pub type int_least16_t = __int_least16_t;
-pub type int_least32_t = __int_least32_t;
-pub type int_least64_t = __int_least64_t;
-pub type uint_least8_t = __uint_least8_t;
-pub type uint_least16_t = __uint_least16_t;
-pub type uint_least32_t = __uint_least32_t;
-pub type uint_least64_t = __uint_least64_t;
-pub type int_fast8_t = ::std::os::raw::c_schar;
-pub type int_fast16_t = ::std::os::raw::c_long;
-pub type int_fast32_t = ::std::os::raw::c_long;
-pub type int_fast64_t = ::std::os::raw::c_long;
-pub type uint_fast8_t = ::std::os::raw::c_uchar;
-pub type uint_fast16_t = ::std::os::raw::c_ulong;
-pub type uint_fast32_t = ::std::os::raw::c_ulong;
-pub type uint_fast64_t = ::std::os::raw::c_ulong;
-pub type intmax_t = __intmax_t;
-pub type uintmax_t = __uintmax_t;
 #[doc = "< There wasn't an error"]
 pub const OlmErrorCode_OLM_SUCCESS: OlmErrorCode = 0;
 #[doc = "< Not enough entropy was supplied"]
@@ -370,7 +404,8 @@ pub const OlmErrorCode_OLM_PICKLE_EXTRA_DATA: OlmErrorCode = 17;
 pub type OlmErrorCode = u32;
 extern "C" {
     #[doc = " get a string representation of the given error code."]
-    pub fn _olm_error_to_string(error: OlmErrorCode) -> *const ::std::os::raw::c_char;
+    #[cfg_attr(feature = "prefix-symbols", link_name = "olmsys__olm_error_to_string")]
+    pub fn _olm_error_to_string(error: OlmErrorCode) -> *const ::core::ffi::c_char;
 }
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
@@ -379,36 +414,60 @@ pub struct OlmInboundGroupSession {
 }
 extern "C" {
     #[doc = " get the size of an inbound group session, in bytes."]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_inbound_group_session_size"
+    )]
     pub fn olm_inbound_group_session_size() -> usize;
 }
 extern "C" {
     #[doc = " Initialise an inbound group session object using the supplied memory"]
     #[doc = " The supplied memory should be at least olm_inbound_group_session_size()"]
     #[doc = " bytes."]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_inbound_group_session"
+    )]
     pub fn olm_inbound_group_session(
-        memory: *mut ::std::os::raw::c_void,
+        memory: *mut ::core::ffi::c_void,
     ) -> *mut OlmInboundGroupSession;
 }
 extern "C" {
     #[doc = " A null terminated string describing the most recent error to happen to a"]
     #[doc = " group session"]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_inbound_group_session_last_error"
+    )]
     pub fn olm_inbound_group_session_last_error(
         session: *const OlmInboundGroupSession,
-    ) -> *const ::std::os::raw::c_char;
+    ) -> *const ::core::ffi::c_char;
 }
 extern "C" {
     #[doc = " An error code describing the most recent error to happen to a group"]
     #[doc = " session"]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_inbound_group_session_last_error_code"
+    )]
     pub fn olm_inbound_group_session_last_error_code(
         session: *const OlmInboundGroupSession,
     ) -> OlmErrorCode;
 }
 extern "C" {
     #[doc = " Clears the memory used to back this group session"]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_clear_inbound_group_session"
+    )]
     pub fn olm_clear_inbound_group_session(session: *mut OlmInboundGroupSession) -> usize;
 }
 extern "C" {
     #[doc = " Returns the number of bytes needed to store an inbound group session"]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_pickle_inbound_group_session_length"
+    )]
     pub fn olm_pickle_inbound_group_session_length(session: *const OlmInboundGroupSession)
         -> usize;
 }
@@ -419,11 +478,15 @@ extern "C" {
     #[doc = " Returns olm_error() on failure. If the pickle output buffer"]
     #[doc = " is smaller than olm_pickle_inbound_group_session_length() then"]
     #[doc = " olm_inbound_group_session_last_error() will be \"OUTPUT_BUFFER_TOO_SMALL\""]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_pickle_inbound_group_session"
+    )]
     pub fn olm_pickle_inbound_group_session(
         session: *mut OlmInboundGroupSession,
-        key: *const ::std::os::raw::c_void,
+        key: *const ::core::ffi::c_void,
         key_length: usize,
-        pickled: *mut ::std::os::raw::c_void,
+        pickled: *mut ::core::ffi::c_void,
         pickled_length: usize,
     ) -> usize;
 }
@@ -436,11 +499,15 @@ extern "C" {
     #[doc = " \"BAD_ACCOUNT_KEY\". If the base64 couldn't be decoded then"]
     #[doc = " olm_inbound_group_session_last_error() will be \"INVALID_BASE64\". The input"]
     #[doc = " pickled buffer is destroyed"]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_unpickle_inbound_group_session"
+    )]
     pub fn olm_unpickle_inbound_group_session(
         session: *mut OlmInboundGroupSession,
-        key: *const ::std::os::raw::c_void,
+        key: *const ::core::ffi::c_void,
         key_length: usize,
-        pickled: *mut ::std::os::raw::c_void,
+        pickled: *mut ::core::ffi::c_void,
         pickled_length: usize,
     ) -> usize;
 }
@@ -453,6 +520,10 @@ extern "C" {
     #[doc = ""]
     #[doc = "  * OLM_INVALID_BASE64  if the session_key is not valid base64"]
     #[doc = "  * OLM_BAD_SESSION_KEY if the session_key is invalid"]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_init_inbound_group_session"
+    )]
     pub fn olm_init_inbound_group_session(
         session: *mut OlmInboundGroupSession,
         session_key: *const u8,
@@ -467,6 +538,10 @@ extern "C" {
     #[doc = ""]
     #[doc = "  * OLM_INVALID_BASE64  if the session_key is not valid base64"]
     #[doc = "  * OLM_BAD_SESSION_KEY if the session_key is invalid"]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_import_inbound_group_session"
+    )]
     pub fn olm_import_inbound_group_session(
         session: *mut OlmInboundGroupSession,
         session_key: *const u8,
@@ -481,6 +556,10 @@ extern "C" {
     #[doc = " The input message buffer is destroyed."]
     #[doc = ""]
     #[doc = " Returns olm_error() on failure."]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_group_decrypt_max_plaintext_length"
+    )]
     pub fn olm_group_decrypt_max_plaintext_length(
         session: *mut OlmInboundGroupSession,
         message: *mut u8,
@@ -505,6 +584,7 @@ extern "C" {
     #[doc = "   * OLM_UNKNOWN_MESSAGE_INDEX  if we do not have a session key corresponding to the"]
     #[doc = "     message's index (ie, it was sent before the session key was shared with"]
     #[doc = "     us)"]
+    #[cfg_attr(feature = "prefix-symbols", link_name = "olmsys_olm_group_decrypt")]
     pub fn olm_group_decrypt(
         session: *mut OlmInboundGroupSession,
         message: *mut u8,
@@ -516,6 +596,10 @@ extern "C" {
 }
 extern "C" {
     #[doc = " Get the number of bytes returned by olm_inbound_group_session_id()"]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_inbound_group_session_id_length"
+    )]
     pub fn olm_inbound_group_session_id_length(session: *const OlmInboundGroupSession) -> usize;
 }
 extern "C" {
@@ -525,6 +609,10 @@ extern "C" {
     #[doc = " failure. On failure last_error will be set with an error code. The"]
     #[doc = " last_error will be OUTPUT_BUFFER_TOO_SMALL if the id buffer was too"]
     #[doc = " small."]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_inbound_group_session_id"
+    )]
     pub fn olm_inbound_group_session_id(
         session: *mut OlmInboundGroupSession,
         id: *mut u8,
@@ -533,6 +621,10 @@ extern "C" {
 }
 extern "C" {
     #[doc = " Get the first message index we know how to decrypt."]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_inbound_group_session_first_known_index"
+    )]
     pub fn olm_inbound_group_session_first_known_index(
         session: *const OlmInboundGroupSession,
     ) -> u32;
@@ -544,12 +636,20 @@ extern "C" {
     #[doc = " or because we have subsequently successfully decrypted a message.)"]
     #[doc = ""]
     #[doc = " This is mainly intended for the unit tests, currently."]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_inbound_group_session_is_verified"
+    )]
     pub fn olm_inbound_group_session_is_verified(
         session: *const OlmInboundGroupSession,
-    ) -> ::std::os::raw::c_int;
+    ) -> ::core::ffi::c_int;
 }
 extern "C" {
     #[doc = " Get the number of bytes returned by olm_export_inbound_group_session()"]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_export_inbound_group_session_length"
+    )]
     pub fn olm_export_inbound_group_session_length(session: *const OlmInboundGroupSession)
         -> usize;
 }
@@ -564,6 +664,10 @@ extern "C" {
     #[doc = "   * OLM_UNKNOWN_MESSAGE_INDEX  if we do not have a session key corresponding to the"]
     #[doc = "     given index (ie, it was sent before the session key was shared with"]
     #[doc = "     us)"]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_export_inbound_group_session"
+    )]
     pub fn olm_export_inbound_group_session(
         session: *mut OlmInboundGroupSession,
         key: *mut u8,
@@ -578,36 +682,60 @@ pub struct OlmOutboundGroupSession {
 }
 extern "C" {
     #[doc = " get the size of an outbound group session, in bytes."]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_outbound_group_session_size"
+    )]
     pub fn olm_outbound_group_session_size() -> usize;
 }
 extern "C" {
     #[doc = " Initialise an outbound group session object using the supplied memory"]
     #[doc = " The supplied memory should be at least olm_outbound_group_session_size()"]
     #[doc = " bytes."]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_outbound_group_session"
+    )]
     pub fn olm_outbound_group_session(
-        memory: *mut ::std::os::raw::c_void,
+        memory: *mut ::core::ffi::c_void,
     ) -> *mut OlmOutboundGroupSession;
 }
 extern "C" {
     #[doc = " A null terminated string describing the most recent error to happen to a"]
     #[doc = " group session"]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_outbound_group_session_last_error"
+    )]
     pub fn olm_outbound_group_session_last_error(
         session: *const OlmOutboundGroupSession,
-    ) -> *const ::std::os::raw::c_char;
+    ) -> *const ::core::ffi::c_char;
 }
 extern "C" {
     #[doc = " An error code describing the most recent error to happen to a group"]
     #[doc = " session"]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_outbound_group_session_last_error_code"
+    )]
     pub fn olm_outbound_group_session_last_error_code(
         session: *const OlmOutboundGroupSession,
     ) -> OlmErrorCode;
 }
 extern "C" {
     #[doc = " Clears the memory used to back this group session"]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_clear_outbound_group_session"
+    )]
     pub fn olm_clear_outbound_group_session(session: *mut OlmOutboundGroupSession) -> usize;
 }
 extern "C" {
     #[doc = " Returns the number of bytes needed to store an outbound group session"]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_pickle_outbound_group_session_length"
+    )]
     pub fn olm_pickle_outbound_group_session_length(
         session: *const OlmOutboundGroupSession,
     ) -> usize;
@@ -619,11 +747,15 @@ extern "C" {
     #[doc = " Returns olm_error() on failure. If the pickle output buffer"]
     #[doc = " is smaller than olm_pickle_outbound_group_session_length() then"]
     #[doc = " olm_outbound_group_session_last_error() will be \"OUTPUT_BUFFER_TOO_SMALL\""]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_pickle_outbound_group_session"
+    )]
     pub fn olm_pickle_outbound_group_session(
         session: *mut OlmOutboundGroupSession,
-        key: *const ::std::os::raw::c_void,
+        key: *const ::core::ffi::c_void,
         key_length: usize,
-        pickled: *mut ::std::os::raw::c_void,
+        pickled: *mut ::core::ffi::c_void,
         pickled_length: usize,
     ) -> usize;
 }
@@ -636,16 +768,24 @@ extern "C" {
     #[doc = " \"BAD_ACCOUNT_KEY\". If the base64 couldn't be decoded then"]
     #[doc = " olm_outbound_group_session_last_error() will be \"INVALID_BASE64\". The input"]
     #[doc = " pickled buffer is destroyed"]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_unpickle_outbound_group_session"
+    )]
     pub fn olm_unpickle_outbound_group_session(
         session: *mut OlmOutboundGroupSession,
-        key: *const ::std::os::raw::c_void,
+        key: *const ::core::ffi::c_void,
         key_length: usize,
-        pickled: *mut ::std::os::raw::c_void,
+        pickled: *mut ::core::ffi::c_void,
         pickled_length: usize,
     ) -> usize;
 }
 extern "C" {
     #[doc = " The number of random bytes needed to create an outbound group session"]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_init_outbound_group_session_random_length"
+    )]
     pub fn olm_init_outbound_group_session_random_length(
         session: *const OlmOutboundGroupSession,
     ) -> usize;
@@ -654,6 +794,10 @@ extern "C" {
     #[doc = " Start a new outbound group session. Returns olm_error() on failure. On"]
     #[doc = " failure last_error will be set with an error code. The last_error will be"]
     #[doc = " NOT_ENOUGH_RANDOM if the number of random bytes was too small."]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_init_outbound_group_session"
+    )]
     pub fn olm_init_outbound_group_session(
         session: *mut OlmOutboundGroupSession,
         random: *mut u8,
@@ -662,6 +806,10 @@ extern "C" {
 }
 extern "C" {
     #[doc = " The number of bytes that will be created by encrypting a message"]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_group_encrypt_message_length"
+    )]
     pub fn olm_group_encrypt_message_length(
         session: *mut OlmOutboundGroupSession,
         plaintext_length: usize,
@@ -672,6 +820,7 @@ extern "C" {
     #[doc = " olm_error() on failure. On failure last_error will be set with an"]
     #[doc = " error code. The last_error will be OUTPUT_BUFFER_TOO_SMALL if the output"]
     #[doc = " buffer is too small."]
+    #[cfg_attr(feature = "prefix-symbols", link_name = "olmsys_olm_group_encrypt")]
     pub fn olm_group_encrypt(
         session: *mut OlmOutboundGroupSession,
         plaintext: *const u8,
@@ -682,6 +831,10 @@ extern "C" {
 }
 extern "C" {
     #[doc = " Get the number of bytes returned by olm_outbound_group_session_id()"]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_outbound_group_session_id_length"
+    )]
     pub fn olm_outbound_group_session_id_length(session: *const OlmOutboundGroupSession) -> usize;
 }
 extern "C" {
@@ -691,6 +844,10 @@ extern "C" {
     #[doc = " failure. On failure last_error will be set with an error code. The"]
     #[doc = " last_error will be OUTPUT_BUFFER_TOO_SMALL if the id buffer was too"]
     #[doc = " small."]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_outbound_group_session_id"
+    )]
     pub fn olm_outbound_group_session_id(
         session: *mut OlmOutboundGroupSession,
         id: *mut u8,
@@ -702,10 +859,18 @@ extern "C" {
     #[doc = ""]
     #[doc = " Each message is sent with an increasing index; this returns the index for"]
     #[doc = " the next message."]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_outbound_group_session_message_index"
+    )]
     pub fn olm_outbound_group_session_message_index(session: *mut OlmOutboundGroupSession) -> u32;
 }
 extern "C" {
     #[doc = " Get the number of bytes returned by olm_outbound_group_session_key()"]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_outbound_group_session_key_length"
+    )]
     pub fn olm_outbound_group_session_key_length(session: *const OlmOutboundGroupSession) -> usize;
 }
 extern "C" {
@@ -717,6 +882,10 @@ extern "C" {
     #[doc = " Returns the length of the ratchet key on success or olm_error() on"]
     #[doc = " failure. On failure last_error will be set with an error code. The"]
     #[doc = " last_error will be OUTPUT_BUFFER_TOO_SMALL if the buffer was too small."]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_outbound_group_session_key"
+    )]
     pub fn olm_outbound_group_session_key(
         session: *mut OlmOutboundGroupSession,
         key: *mut u8,
@@ -743,84 +912,130 @@ pub struct OlmUtility {
 extern "C" {
     #[doc = " Get the version number of the library."]
     #[doc = " Arguments will be updated if non-null."]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_get_library_version"
+    )]
     pub fn olm_get_library_version(major: *mut u8, minor: *mut u8, patch: *mut u8);
 }
 extern "C" {
     #[doc = " The size of an account object in bytes"]
+    #[cfg_attr(feature = "prefix-symbols", link_name = "olmsys_olm_account_size")]
     pub fn olm_account_size() -> usize;
 }
 extern "C" {
     #[doc = " The size of a session object in bytes"]
+    #[cfg_attr(feature = "prefix-symbols", link_name = "olmsys_olm_session_size")]
     pub fn olm_session_size() -> usize;
 }
 extern "C" {
     #[doc = " The size of a utility object in bytes"]
+    #[cfg_attr(feature = "prefix-symbols", link_name = "olmsys_olm_utility_size")]
     pub fn olm_utility_size() -> usize;
 }
 extern "C" {
     #[doc = " Initialise an account object using the supplied memory"]
     #[doc = "  The supplied memory must be at least olm_account_size() bytes"]
-    pub fn olm_account(memory: *mut ::std::os::raw::c_void) -> *mut OlmAccount;
+    #[cfg_attr(feature = "prefix-symbols", link_name = "olmsys_olm_account")]
+    pub fn olm_account(memory: *mut ::core::ffi::c_void) -> *mut OlmAccount;
 }
 extern "C" {
     #[doc = " Initialise a session object using the supplied memory"]
     #[doc = "  The supplied memory must be at least olm_session_size() bytes"]
-    pub fn olm_session(memory: *mut ::std::os::raw::c_void) -> *mut OlmSession;
+    #[cfg_attr(feature = "prefix-symbols", link_name = "olmsys_olm_session")]
+    pub fn olm_session(memory: *mut ::core::ffi::c_void) -> *mut OlmSession;
 }
 extern "C" {
     #[doc = " Initialise a utility object using the supplied memory"]
     #[doc = "  The supplied memory must be at least olm_utility_size() bytes"]
-    pub fn olm_utility(memory: *mut ::std::os::raw::c_void) -> *mut OlmUtility;
+    #[cfg_attr(feature = "prefix-symbols", link_name = "olmsys_olm_utility")]
+    pub fn olm_utility(memory: *mut ::core::ffi::c_void) -> *mut OlmUtility;
 }
 extern "C" {
     #[doc = " The value that olm will return from a function if there was an error"]
+    #[cfg_attr(feature = "prefix-symbols", link_name = "olmsys_olm_error")]
     pub fn olm_error() -> usize;
 }
 extern "C" {
     #[doc = " A null terminated string describing the most recent error to happen to an"]
     #[doc = " account"]
-    pub fn olm_account_last_error(account: *const OlmAccount) -> *const ::std::os::raw::c_char;
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_account_last_error"
+    )]
+    pub fn olm_account_last_error(account: *const OlmAccount) -> *const ::core::ffi::c_char;
 }
 extern "C" {
     #[doc = " An error code describing the most recent error to happen to an account"]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_account_last_error_code"
+    )]
     pub fn olm_account_last_error_code(account: *const OlmAccount) -> OlmErrorCode;
 }
 extern "C" {
     #[doc = " A null terminated string describing the most recent error to happen to a"]
     #[doc = " session"]
-    pub fn olm_session_last_error(session: *const OlmSession) -> *const ::std::os::raw::c_char;
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_session_last_error"
+    )]
+    pub fn olm_session_last_error(session: *const OlmSession) -> *const ::core::ffi::c_char;
 }
 extern "C" {
     #[doc = " An error code describing the most recent error to happen to a session"]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_session_last_error_code"
+    )]
     pub fn olm_session_last_error_code(session: *const OlmSession) -> OlmErrorCode;
 }
 extern "C" {
     #[doc = " A null terminated string describing the most recent error to happen to a"]
     #[doc = " utility"]
-    pub fn olm_utility_last_error(utility: *const OlmUtility) -> *const ::std::os::raw::c_char;
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_utility_last_error"
+    )]
+    pub fn olm_utility_last_error(utility: *const OlmUtility) -> *const ::core::ffi::c_char;
 }
 extern "C" {
     #[doc = " An error code describing the most recent error to happen to a utility"]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_utility_last_error_code"
+    )]
     pub fn olm_utility_last_error_code(utility: *const OlmUtility) -> OlmErrorCode;
 }
 extern "C" {
     #[doc = " Clears the memory used to back this account"]
+    #[cfg_attr(feature = "prefix-symbols", link_name = "olmsys_olm_clear_account")]
     pub fn olm_clear_account(account: *mut OlmAccount) -> usize;
 }
 extern "C" {
     #[doc = " Clears the memory used to back this session"]
+    #[cfg_attr(feature = "prefix-symbols", link_name = "olmsys_olm_clear_session")]
     pub fn olm_clear_session(session: *mut OlmSession) -> usize;
 }
 extern "C" {
     #[doc = " Clears the memory used to back this utility"]
+    #[cfg_attr(feature = "prefix-symbols", link_name = "olmsys_olm_clear_utility")]
     pub fn olm_clear_utility(utility: *mut OlmUtility) -> usize;
 }
 extern "C" {
     #[doc = " Returns the number of bytes needed to store an account"]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_pickle_account_length"
+    )]
     pub fn olm_pickle_account_length(account: *const OlmAccount) -> usize;
 }
 extern "C" {
     #[doc = " Returns the number of bytes needed to store a session"]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_pickle_session_length"
+    )]
     pub fn olm_pickle_session_length(session: *const OlmSession) -> usize;
 }
 extern "C" {
@@ -829,11 +1044,12 @@ extern "C" {
     #[doc = " Returns olm_error() on failure. If the pickle output buffer"]
     #[doc = " is smaller than olm_pickle_account_length() then"]
     #[doc = " olm_account_last_error() will be \"OUTPUT_BUFFER_TOO_SMALL\""]
+    #[cfg_attr(feature = "prefix-symbols", link_name = "olmsys_olm_pickle_account")]
     pub fn olm_pickle_account(
         account: *mut OlmAccount,
-        key: *const ::std::os::raw::c_void,
+        key: *const ::core::ffi::c_void,
         key_length: usize,
-        pickled: *mut ::std::os::raw::c_void,
+        pickled: *mut ::core::ffi::c_void,
         pickled_length: usize,
     ) -> usize;
 }
@@ -843,11 +1059,12 @@ extern "C" {
     #[doc = " Returns olm_error() on failure. If the pickle output buffer"]
     #[doc = " is smaller than olm_pickle_session_length() then"]
     #[doc = " olm_session_last_error() will be \"OUTPUT_BUFFER_TOO_SMALL\""]
+    #[cfg_attr(feature = "prefix-symbols", link_name = "olmsys_olm_pickle_session")]
     pub fn olm_pickle_session(
         session: *mut OlmSession,
-        key: *const ::std::os::raw::c_void,
+        key: *const ::core::ffi::c_void,
         key_length: usize,
-        pickled: *mut ::std::os::raw::c_void,
+        pickled: *mut ::core::ffi::c_void,
         pickled_length: usize,
     ) -> usize;
 }
@@ -858,11 +1075,12 @@ extern "C" {
     #[doc = " will be \"BAD_ACCOUNT_KEY\". If the base64 couldn't be decoded then"]
     #[doc = " olm_account_last_error() will be \"INVALID_BASE64\". The input pickled"]
     #[doc = " buffer is destroyed"]
+    #[cfg_attr(feature = "prefix-symbols", link_name = "olmsys_olm_unpickle_account")]
     pub fn olm_unpickle_account(
         account: *mut OlmAccount,
-        key: *const ::std::os::raw::c_void,
+        key: *const ::core::ffi::c_void,
         key_length: usize,
-        pickled: *mut ::std::os::raw::c_void,
+        pickled: *mut ::core::ffi::c_void,
         pickled_length: usize,
     ) -> usize;
 }
@@ -873,30 +1091,40 @@ extern "C" {
     #[doc = " will be \"BAD_ACCOUNT_KEY\". If the base64 couldn't be decoded then"]
     #[doc = " olm_session_last_error() will be \"INVALID_BASE64\". The input pickled"]
     #[doc = " buffer is destroyed"]
+    #[cfg_attr(feature = "prefix-symbols", link_name = "olmsys_olm_unpickle_session")]
     pub fn olm_unpickle_session(
         session: *mut OlmSession,
-        key: *const ::std::os::raw::c_void,
+        key: *const ::core::ffi::c_void,
         key_length: usize,
-        pickled: *mut ::std::os::raw::c_void,
+        pickled: *mut ::core::ffi::c_void,
         pickled_length: usize,
     ) -> usize;
 }
 extern "C" {
     #[doc = " The number of random bytes needed to create an account."]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_create_account_random_length"
+    )]
     pub fn olm_create_account_random_length(account: *const OlmAccount) -> usize;
 }
 extern "C" {
     #[doc = " Creates a new account. Returns olm_error() on failure. If there weren't"]
     #[doc = " enough random bytes then olm_account_last_error() will be"]
     #[doc = " \"NOT_ENOUGH_RANDOM\""]
+    #[cfg_attr(feature = "prefix-symbols", link_name = "olmsys_olm_create_account")]
     pub fn olm_create_account(
         account: *mut OlmAccount,
-        random: *mut ::std::os::raw::c_void,
+        random: *mut ::core::ffi::c_void,
         random_length: usize,
     ) -> usize;
 }
 extern "C" {
     #[doc = " The size of the output buffer needed to hold the identity keys"]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_account_identity_keys_length"
+    )]
     pub fn olm_account_identity_keys_length(account: *const OlmAccount) -> usize;
 }
 extern "C" {
@@ -904,30 +1132,43 @@ extern "C" {
     #[doc = " identity_keys output buffer. Returns olm_error() on failure. If the"]
     #[doc = " identity_keys buffer was too small then olm_account_last_error() will be"]
     #[doc = " \"OUTPUT_BUFFER_TOO_SMALL\"."]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_account_identity_keys"
+    )]
     pub fn olm_account_identity_keys(
         account: *mut OlmAccount,
-        identity_keys: *mut ::std::os::raw::c_void,
+        identity_keys: *mut ::core::ffi::c_void,
         identity_key_length: usize,
     ) -> usize;
 }
 extern "C" {
     #[doc = " The length of an ed25519 signature encoded as base64."]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_account_signature_length"
+    )]
     pub fn olm_account_signature_length(account: *const OlmAccount) -> usize;
 }
 extern "C" {
     #[doc = " Signs a message with the ed25519 key for this account. Returns olm_error()"]
     #[doc = " on failure. If the signature buffer was too small then"]
     #[doc = " olm_account_last_error() will be \"OUTPUT_BUFFER_TOO_SMALL\""]
+    #[cfg_attr(feature = "prefix-symbols", link_name = "olmsys_olm_account_sign")]
     pub fn olm_account_sign(
         account: *mut OlmAccount,
-        message: *const ::std::os::raw::c_void,
+        message: *const ::core::ffi::c_void,
         message_length: usize,
-        signature: *mut ::std::os::raw::c_void,
+        signature: *mut ::core::ffi::c_void,
         signature_length: usize,
     ) -> usize;
 }
 extern "C" {
     #[doc = " The size of the output buffer needed to hold the one time keys"]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_account_one_time_keys_length"
+    )]
     pub fn olm_account_one_time_keys_length(account: *const OlmAccount) -> usize;
 }
 extern "C" {
@@ -949,9 +1190,13 @@ extern "C" {
     #[doc = " <p>"]
     #[doc = " If the one_time_keys buffer was too small then olm_account_last_error()"]
     #[doc = " will be \"OUTPUT_BUFFER_TOO_SMALL\"."]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_account_one_time_keys"
+    )]
     pub fn olm_account_one_time_keys(
         account: *mut OlmAccount,
-        one_time_keys: *mut ::std::os::raw::c_void,
+        one_time_keys: *mut ::core::ffi::c_void,
         one_time_keys_length: usize,
     ) -> usize;
 }
@@ -963,15 +1208,27 @@ extern "C" {
     #[doc = ""]
     #[doc = " Returns the number of one-time keys that were marked as published.  Note that"]
     #[doc = " this count does not include the fallback key."]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_account_mark_keys_as_published"
+    )]
     pub fn olm_account_mark_keys_as_published(account: *mut OlmAccount) -> usize;
 }
 extern "C" {
     #[doc = " The largest number of one time keys this account can store."]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_account_max_number_of_one_time_keys"
+    )]
     pub fn olm_account_max_number_of_one_time_keys(account: *const OlmAccount) -> usize;
 }
 extern "C" {
     #[doc = " The number of random bytes needed to generate a given number of new one"]
     #[doc = " time keys."]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_account_generate_one_time_keys_random_length"
+    )]
     pub fn olm_account_generate_one_time_keys_random_length(
         account: *const OlmAccount,
         number_of_keys: usize,
@@ -982,51 +1239,79 @@ extern "C" {
     #[doc = " by this account exceeds max_number_of_one_time_keys() then the old keys are"]
     #[doc = " discarded. Returns olm_error() on error. If the number of random bytes is"]
     #[doc = " too small then olm_account_last_error() will be \"NOT_ENOUGH_RANDOM\"."]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_account_generate_one_time_keys"
+    )]
     pub fn olm_account_generate_one_time_keys(
         account: *mut OlmAccount,
         number_of_keys: usize,
-        random: *mut ::std::os::raw::c_void,
+        random: *mut ::core::ffi::c_void,
         random_length: usize,
     ) -> usize;
 }
 extern "C" {
     #[doc = " The number of random bytes needed to generate a fallback key."]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_account_generate_fallback_key_random_length"
+    )]
     pub fn olm_account_generate_fallback_key_random_length(account: *const OlmAccount) -> usize;
 }
 extern "C" {
     #[doc = " Generates a new fallback key. Only one previous fallback key is"]
     #[doc = " stored. Returns olm_error() on error. If the number of random bytes is too"]
     #[doc = " small then olm_account_last_error() will be \"NOT_ENOUGH_RANDOM\"."]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_account_generate_fallback_key"
+    )]
     pub fn olm_account_generate_fallback_key(
         account: *mut OlmAccount,
-        random: *mut ::std::os::raw::c_void,
+        random: *mut ::core::ffi::c_void,
         random_length: usize,
     ) -> usize;
 }
 extern "C" {
     #[doc = " The number of bytes needed to hold the fallback key as returned by"]
     #[doc = " olm_account_fallback_key."]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_account_fallback_key_length"
+    )]
     pub fn olm_account_fallback_key_length(account: *const OlmAccount) -> usize;
 }
 extern "C" {
     #[doc = " Deprecated: use olm_account_unpublished_fallback_key instead"]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_account_fallback_key"
+    )]
     pub fn olm_account_fallback_key(
         account: *mut OlmAccount,
-        fallback_key: *mut ::std::os::raw::c_void,
+        fallback_key: *mut ::core::ffi::c_void,
         fallback_key_size: usize,
     ) -> usize;
 }
 extern "C" {
     #[doc = " The number of bytes needed to hold the unpublished fallback key as returned"]
     #[doc = " by olm_account_unpublished fallback_key."]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_account_unpublished_fallback_key_length"
+    )]
     pub fn olm_account_unpublished_fallback_key_length(account: *const OlmAccount) -> usize;
 }
 extern "C" {
     #[doc = " Returns the fallback key (if present, and if unpublished) into the"]
     #[doc = " fallback_key buffer"]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_account_unpublished_fallback_key"
+    )]
     pub fn olm_account_unpublished_fallback_key(
         account: *mut OlmAccount,
-        fallback_key: *mut ::std::os::raw::c_void,
+        fallback_key: *mut ::core::ffi::c_void,
         fallback_key_size: usize,
     ) -> usize;
 }
@@ -1035,10 +1320,18 @@ extern "C" {
     #[doc = " reasonably certain that you will not receive any more messages that use"]
     #[doc = " the old fallback key (e.g. 5 minutes after the new fallback key has been"]
     #[doc = " published)."]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_account_forget_old_fallback_key"
+    )]
     pub fn olm_account_forget_old_fallback_key(account: *mut OlmAccount);
 }
 extern "C" {
     #[doc = " The number of random bytes needed to create an outbound session"]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_create_outbound_session_random_length"
+    )]
     pub fn olm_create_outbound_session_random_length(session: *const OlmSession) -> usize;
 }
 extern "C" {
@@ -1047,14 +1340,18 @@ extern "C" {
     #[doc = " decoded as base64 then olm_session_last_error() will be \"INVALID_BASE64\""]
     #[doc = " If there weren't enough random bytes then olm_session_last_error() will"]
     #[doc = " be \"NOT_ENOUGH_RANDOM\"."]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_create_outbound_session"
+    )]
     pub fn olm_create_outbound_session(
         session: *mut OlmSession,
         account: *const OlmAccount,
-        their_identity_key: *const ::std::os::raw::c_void,
+        their_identity_key: *const ::core::ffi::c_void,
         their_identity_key_length: usize,
-        their_one_time_key: *const ::std::os::raw::c_void,
+        their_one_time_key: *const ::core::ffi::c_void,
         their_one_time_key_length: usize,
-        random: *mut ::std::os::raw::c_void,
+        random: *mut ::core::ffi::c_void,
         random_length: usize,
     ) -> usize;
 }
@@ -1067,10 +1364,14 @@ extern "C" {
     #[doc = " couldn't be decoded then olm_session_last_error() will be"]
     #[doc = " \"BAD_MESSAGE_FORMAT\". If the message refers to an unknown one time"]
     #[doc = " key then olm_session_last_error() will be \"BAD_MESSAGE_KEY_ID\"."]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_create_inbound_session"
+    )]
     pub fn olm_create_inbound_session(
         session: *mut OlmSession,
         account: *mut OlmAccount,
-        one_time_key_message: *mut ::std::os::raw::c_void,
+        one_time_key_message: *mut ::core::ffi::c_void,
         message_length: usize,
     ) -> usize;
 }
@@ -1078,38 +1379,49 @@ extern "C" {
     #[doc = " Same as olm_create_inbound_session, but ensures that the identity key"]
     #[doc = " in the pre-key message matches the expected identity key, supplied via the"]
     #[doc = " `their_identity_key` parameter. Fails early if there is no match."]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_create_inbound_session_from"
+    )]
     pub fn olm_create_inbound_session_from(
         session: *mut OlmSession,
         account: *mut OlmAccount,
-        their_identity_key: *const ::std::os::raw::c_void,
+        their_identity_key: *const ::core::ffi::c_void,
         their_identity_key_length: usize,
-        one_time_key_message: *mut ::std::os::raw::c_void,
+        one_time_key_message: *mut ::core::ffi::c_void,
         message_length: usize,
     ) -> usize;
 }
 extern "C" {
     #[doc = " The length of the buffer needed to return the id for this session."]
+    #[cfg_attr(feature = "prefix-symbols", link_name = "olmsys_olm_session_id_length")]
     pub fn olm_session_id_length(session: *const OlmSession) -> usize;
 }
 extern "C" {
     #[doc = " An identifier for this session. Will be the same for both ends of the"]
     #[doc = " conversation. If the id buffer is too small then olm_session_last_error()"]
     #[doc = " will be \"OUTPUT_BUFFER_TOO_SMALL\"."]
+    #[cfg_attr(feature = "prefix-symbols", link_name = "olmsys_olm_session_id")]
     pub fn olm_session_id(
         session: *mut OlmSession,
-        id: *mut ::std::os::raw::c_void,
+        id: *mut ::core::ffi::c_void,
         id_length: usize,
     ) -> usize;
 }
 extern "C" {
-    pub fn olm_session_has_received_message(session: *const OlmSession) -> ::std::os::raw::c_int;
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_session_has_received_message"
+    )]
+    pub fn olm_session_has_received_message(session: *const OlmSession) -> ::core::ffi::c_int;
 }
 extern "C" {
     #[doc = " Write a null-terminated string describing the internal state of an olm"]
     #[doc = " session to the buffer provided for debugging and logging purposes."]
+    #[cfg_attr(feature = "prefix-symbols", link_name = "olmsys_olm_session_describe")]
     pub fn olm_session_describe(
         session: *mut OlmSession,
-        buf: *mut ::std::os::raw::c_char,
+        buf: *mut ::core::ffi::c_char,
         buflen: usize,
     );
 }
@@ -1123,9 +1435,13 @@ extern "C" {
     #[doc = " unsupported protocol version then olm_session_last_error() will be"]
     #[doc = " \"BAD_MESSAGE_VERSION\". If the message couldn't be decoded then then"]
     #[doc = " olm_session_last_error() will be \"BAD_MESSAGE_FORMAT\"."]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_matches_inbound_session"
+    )]
     pub fn olm_matches_inbound_session(
         session: *mut OlmSession,
-        one_time_key_message: *mut ::std::os::raw::c_void,
+        one_time_key_message: *mut ::core::ffi::c_void,
         message_length: usize,
     ) -> usize;
 }
@@ -1139,11 +1455,15 @@ extern "C" {
     #[doc = " unsupported protocol version then olm_session_last_error() will be"]
     #[doc = " \"BAD_MESSAGE_VERSION\". If the message couldn't be decoded then then"]
     #[doc = " olm_session_last_error() will be \"BAD_MESSAGE_FORMAT\"."]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_matches_inbound_session_from"
+    )]
     pub fn olm_matches_inbound_session_from(
         session: *mut OlmSession,
-        their_identity_key: *const ::std::os::raw::c_void,
+        their_identity_key: *const ::core::ffi::c_void,
         their_identity_key_length: usize,
-        one_time_key_message: *mut ::std::os::raw::c_void,
+        one_time_key_message: *mut ::core::ffi::c_void,
         message_length: usize,
     ) -> usize;
 }
@@ -1151,6 +1471,10 @@ extern "C" {
     #[doc = " Removes the one time keys that the session used from the account. Returns"]
     #[doc = " olm_error() on failure. If the account doesn't have any matching one time"]
     #[doc = " keys then olm_account_last_error() will be \"BAD_MESSAGE_KEY_ID\"."]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_remove_one_time_keys"
+    )]
     pub fn olm_remove_one_time_keys(account: *mut OlmAccount, session: *mut OlmSession) -> usize;
 }
 extern "C" {
@@ -1158,15 +1482,27 @@ extern "C" {
     #[doc = " OLM_MESSAGE_TYPE_PRE_KEY if the message will be a PRE_KEY message."]
     #[doc = " Returns OLM_MESSAGE_TYPE_MESSAGE if the message will be a normal message."]
     #[doc = " Returns olm_error on failure."]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_encrypt_message_type"
+    )]
     pub fn olm_encrypt_message_type(session: *const OlmSession) -> usize;
 }
 extern "C" {
     #[doc = " The number of random bytes needed to encrypt the next message."]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_encrypt_random_length"
+    )]
     pub fn olm_encrypt_random_length(session: *const OlmSession) -> usize;
 }
 extern "C" {
     #[doc = " The size of the next message in bytes for the given number of plain-text"]
     #[doc = " bytes."]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_encrypt_message_length"
+    )]
     pub fn olm_encrypt_message_length(session: *const OlmSession, plaintext_length: usize)
         -> usize;
 }
@@ -1177,13 +1513,14 @@ extern "C" {
     #[doc = " olm_session_last_error() will be \"OUTPUT_BUFFER_TOO_SMALL\". If there"]
     #[doc = " weren't enough random bytes then olm_session_last_error() will be"]
     #[doc = " \"NOT_ENOUGH_RANDOM\"."]
+    #[cfg_attr(feature = "prefix-symbols", link_name = "olmsys_olm_encrypt")]
     pub fn olm_encrypt(
         session: *mut OlmSession,
-        plaintext: *const ::std::os::raw::c_void,
+        plaintext: *const ::core::ffi::c_void,
         plaintext_length: usize,
-        random: *mut ::std::os::raw::c_void,
+        random: *mut ::core::ffi::c_void,
         random_length: usize,
-        message: *mut ::std::os::raw::c_void,
+        message: *mut ::core::ffi::c_void,
         message_length: usize,
     ) -> usize;
 }
@@ -1196,10 +1533,14 @@ extern "C" {
     #[doc = " protocol then olm_session_last_error() will be \"BAD_MESSAGE_VERSION\"."]
     #[doc = " If the message couldn't be decoded then olm_session_last_error() will be"]
     #[doc = " \"BAD_MESSAGE_FORMAT\"."]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_decrypt_max_plaintext_length"
+    )]
     pub fn olm_decrypt_max_plaintext_length(
         session: *mut OlmSession,
         message_type: usize,
-        message: *mut ::std::os::raw::c_void,
+        message: *mut ::core::ffi::c_void,
         message_length: usize,
     ) -> usize;
 }
@@ -1215,28 +1556,31 @@ extern "C" {
     #[doc = " olm_session_last_error() will be BAD_MESSAGE_FORMAT\"."]
     #[doc = " If the MAC on the message was invalid then olm_session_last_error() will"]
     #[doc = " be \"BAD_MESSAGE_MAC\"."]
+    #[cfg_attr(feature = "prefix-symbols", link_name = "olmsys_olm_decrypt")]
     pub fn olm_decrypt(
         session: *mut OlmSession,
         message_type: usize,
-        message: *mut ::std::os::raw::c_void,
+        message: *mut ::core::ffi::c_void,
         message_length: usize,
-        plaintext: *mut ::std::os::raw::c_void,
+        plaintext: *mut ::core::ffi::c_void,
         max_plaintext_length: usize,
     ) -> usize;
 }
 extern "C" {
     #[doc = " The length of the buffer needed to hold the SHA-256 hash."]
+    #[cfg_attr(feature = "prefix-symbols", link_name = "olmsys_olm_sha256_length")]
     pub fn olm_sha256_length(utility: *const OlmUtility) -> usize;
 }
 extern "C" {
     #[doc = " Calculates the SHA-256 hash of the input and encodes it as base64. If the"]
     #[doc = " output buffer is smaller than olm_sha256_length() then"]
     #[doc = " olm_utility_last_error() will be \"OUTPUT_BUFFER_TOO_SMALL\"."]
+    #[cfg_attr(feature = "prefix-symbols", link_name = "olmsys_olm_sha256")]
     pub fn olm_sha256(
         utility: *mut OlmUtility,
-        input: *const ::std::os::raw::c_void,
+        input: *const ::core::ffi::c_void,
         input_length: usize,
-        output: *mut ::std::os::raw::c_void,
+        output: *mut ::core::ffi::c_void,
         output_length: usize,
     ) -> usize;
 }
@@ -1244,13 +1588,14 @@ extern "C" {
     #[doc = " Verify an ed25519 signature. If the key was too small then"]
     #[doc = " olm_utility_last_error() will be \"INVALID_BASE64\". If the signature was invalid"]
     #[doc = " then olm_utility_last_error() will be \"BAD_MESSAGE_MAC\"."]
+    #[cfg_attr(feature = "prefix-symbols", link_name = "olmsys_olm_ed25519_verify")]
     pub fn olm_ed25519_verify(
         utility: *mut OlmUtility,
-        key: *const ::std::os::raw::c_void,
+        key: *const ::core::ffi::c_void,
         key_length: usize,
-        message: *const ::std::os::raw::c_void,
+        message: *const ::core::ffi::c_void,
         message_length: usize,
-        signature: *mut ::std::os::raw::c_void,
+        signature: *mut ::core::ffi::c_void,
         signature_length: usize,
     ) -> usize;
 }
@@ -1262,28 +1607,40 @@ pub struct OlmSAS {
 extern "C" {
     #[doc = " A null terminated string describing the most recent error to happen to an"]
     #[doc = " SAS object."]
-    pub fn olm_sas_last_error(sas: *const OlmSAS) -> *const ::std::os::raw::c_char;
+    #[cfg_attr(feature = "prefix-symbols", link_name = "olmsys_olm_sas_last_error")]
+    pub fn olm_sas_last_error(sas: *const OlmSAS) -> *const ::core::ffi::c_char;
 }
 extern "C" {
     #[doc = " An error code describing the most recent error to happen to an SAS"]
     #[doc = " object."]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_sas_last_error_code"
+    )]
     pub fn olm_sas_last_error_code(sas: *const OlmSAS) -> OlmErrorCode;
 }
 extern "C" {
     #[doc = " The size of an SAS object in bytes."]
+    #[cfg_attr(feature = "prefix-symbols", link_name = "olmsys_olm_sas_size")]
     pub fn olm_sas_size() -> usize;
 }
 extern "C" {
     #[doc = " Initialize an SAS object using the supplied memory."]
     #[doc = " The supplied memory must be at least `olm_sas_size()` bytes."]
-    pub fn olm_sas(memory: *mut ::std::os::raw::c_void) -> *mut OlmSAS;
+    #[cfg_attr(feature = "prefix-symbols", link_name = "olmsys_olm_sas")]
+    pub fn olm_sas(memory: *mut ::core::ffi::c_void) -> *mut OlmSAS;
 }
 extern "C" {
     #[doc = " Clears the memory used to back an SAS object."]
+    #[cfg_attr(feature = "prefix-symbols", link_name = "olmsys_olm_clear_sas")]
     pub fn olm_clear_sas(sas: *mut OlmSAS) -> usize;
 }
 extern "C" {
     #[doc = " The number of random bytes needed to create an SAS object."]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_create_sas_random_length"
+    )]
     pub fn olm_create_sas_random_length(sas: *const OlmSAS) -> usize;
 }
 extern "C" {
@@ -1297,14 +1654,16 @@ extern "C" {
     #[doc = ""]
     #[doc = " @return `olm_error()` on failure.  If there weren't enough random bytes then"]
     #[doc = " `olm_sas_last_error()` will be `NOT_ENOUGH_RANDOM`."]
+    #[cfg_attr(feature = "prefix-symbols", link_name = "olmsys_olm_create_sas")]
     pub fn olm_create_sas(
         sas: *mut OlmSAS,
-        random: *mut ::std::os::raw::c_void,
+        random: *mut ::core::ffi::c_void,
         random_length: usize,
     ) -> usize;
 }
 extern "C" {
     #[doc = " The size of a public key in bytes."]
+    #[cfg_attr(feature = "prefix-symbols", link_name = "olmsys_olm_sas_pubkey_length")]
     pub fn olm_sas_pubkey_length(sas: *const OlmSAS) -> usize;
 }
 extern "C" {
@@ -1317,9 +1676,10 @@ extern "C" {
     #[doc = ""]
     #[doc = " @return `olm_error()` on failure.  If the `pubkey` buffer is too small, then"]
     #[doc = " `olm_sas_last_error()` will be `OUTPUT_BUFFER_TOO_SMALL`."]
+    #[cfg_attr(feature = "prefix-symbols", link_name = "olmsys_olm_sas_get_pubkey")]
     pub fn olm_sas_get_pubkey(
         sas: *mut OlmSAS,
-        pubkey: *mut ::std::os::raw::c_void,
+        pubkey: *mut ::core::ffi::c_void,
         pubkey_length: usize,
     ) -> usize;
 }
@@ -1333,9 +1693,10 @@ extern "C" {
     #[doc = ""]
     #[doc = " @return `olm_error()` on failure.  If the `their_key` buffer is too small,"]
     #[doc = " then `olm_sas_last_error()` will be `INPUT_BUFFER_TOO_SMALL`."]
+    #[cfg_attr(feature = "prefix-symbols", link_name = "olmsys_olm_sas_set_their_key")]
     pub fn olm_sas_set_their_key(
         sas: *mut OlmSAS,
-        their_key: *mut ::std::os::raw::c_void,
+        their_key: *mut ::core::ffi::c_void,
         their_key_length: usize,
     ) -> usize;
 }
@@ -1344,7 +1705,11 @@ extern "C" {
     #[doc = ""]
     #[doc = " @param[in] sas the SAS object."]
     #[doc = ""]
-    pub fn olm_sas_is_their_key_set(sas: *const OlmSAS) -> ::std::os::raw::c_int;
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_sas_is_their_key_set"
+    )]
+    pub fn olm_sas_is_their_key_set(sas: *const OlmSAS) -> ::core::ffi::c_int;
 }
 extern "C" {
     #[doc = " Generate bytes to use for the short authentication string."]
@@ -1359,17 +1724,22 @@ extern "C" {
     #[doc = ""]
     #[doc = " @return `olm_error()` on failure. If their key wasn't set then"]
     #[doc = " `olm_sas_last_error()` will be `SAS_THEIR_KEY_NOT_SET`."]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_sas_generate_bytes"
+    )]
     pub fn olm_sas_generate_bytes(
         sas: *mut OlmSAS,
-        info: *const ::std::os::raw::c_void,
+        info: *const ::core::ffi::c_void,
         info_length: usize,
-        output: *mut ::std::os::raw::c_void,
+        output: *mut ::core::ffi::c_void,
         output_length: usize,
     ) -> usize;
 }
 extern "C" {
     #[doc = " The size of the message authentication code generated by"]
     #[doc = " olm_sas_calculate_mac()`."]
+    #[cfg_attr(feature = "prefix-symbols", link_name = "olmsys_olm_sas_mac_length")]
     pub fn olm_sas_mac_length(sas: *const OlmSAS) -> usize;
 }
 extern "C" {
@@ -1387,35 +1757,44 @@ extern "C" {
     #[doc = ""]
     #[doc = " @return `olm_error()` on failure.  If the `mac` buffer is too small, then"]
     #[doc = " `olm_sas_last_error()` will be `OUTPUT_BUFFER_TOO_SMALL`."]
+    #[cfg_attr(feature = "prefix-symbols", link_name = "olmsys_olm_sas_calculate_mac")]
     pub fn olm_sas_calculate_mac(
         sas: *mut OlmSAS,
-        input: *const ::std::os::raw::c_void,
+        input: *const ::core::ffi::c_void,
         input_length: usize,
-        info: *const ::std::os::raw::c_void,
+        info: *const ::core::ffi::c_void,
         info_length: usize,
-        mac: *mut ::std::os::raw::c_void,
+        mac: *mut ::core::ffi::c_void,
         mac_length: usize,
     ) -> usize;
 }
 extern "C" {
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_sas_calculate_mac_fixed_base64"
+    )]
     pub fn olm_sas_calculate_mac_fixed_base64(
         sas: *mut OlmSAS,
-        input: *const ::std::os::raw::c_void,
+        input: *const ::core::ffi::c_void,
         input_length: usize,
-        info: *const ::std::os::raw::c_void,
+        info: *const ::core::ffi::c_void,
         info_length: usize,
-        mac: *mut ::std::os::raw::c_void,
+        mac: *mut ::core::ffi::c_void,
         mac_length: usize,
     ) -> usize;
 }
 extern "C" {
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_sas_calculate_mac_long_kdf"
+    )]
     pub fn olm_sas_calculate_mac_long_kdf(
         sas: *mut OlmSAS,
-        input: *const ::std::os::raw::c_void,
+        input: *const ::core::ffi::c_void,
         input_length: usize,
-        info: *const ::std::os::raw::c_void,
+        info: *const ::core::ffi::c_void,
         info_length: usize,
-        mac: *mut ::std::os::raw::c_void,
+        mac: *mut ::core::ffi::c_void,
         mac_length: usize,
     ) -> usize;
 }
@@ -1425,40 +1804,65 @@ pub struct OlmPkEncryption {
     _unused: [u8; 0],
 }
 extern "C" {
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_pk_encryption_size"
+    )]
     pub fn olm_pk_encryption_size() -> usize;
 }
 extern "C" {
     #[doc = " Initialise an encryption object using the supplied memory"]
     #[doc = "  The supplied memory must be at least olm_pk_encryption_size() bytes"]
-    pub fn olm_pk_encryption(memory: *mut ::std::os::raw::c_void) -> *mut OlmPkEncryption;
+    #[cfg_attr(feature = "prefix-symbols", link_name = "olmsys_olm_pk_encryption")]
+    pub fn olm_pk_encryption(memory: *mut ::core::ffi::c_void) -> *mut OlmPkEncryption;
 }
 extern "C" {
     #[doc = " A null terminated string describing the most recent error to happen to an"]
     #[doc = " encryption object"]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_pk_encryption_last_error"
+    )]
     pub fn olm_pk_encryption_last_error(
         encryption: *const OlmPkEncryption,
-    ) -> *const ::std::os::raw::c_char;
+    ) -> *const ::core::ffi::c_char;
 }
 extern "C" {
     #[doc = " An error code describing the most recent error to happen to an encryption"]
     #[doc = " object"]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_pk_encryption_last_error_code"
+    )]
     pub fn olm_pk_encryption_last_error_code(encryption: *const OlmPkEncryption) -> OlmErrorCode;
 }
 extern "C" {
     #[doc = " Clears the memory used to back this encryption object"]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_clear_pk_encryption"
+    )]
     pub fn olm_clear_pk_encryption(encryption: *mut OlmPkEncryption) -> usize;
 }
 extern "C" {
     #[doc = " Set the recipient's public key for encrypting to"]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_pk_encryption_set_recipient_key"
+    )]
     pub fn olm_pk_encryption_set_recipient_key(
         encryption: *mut OlmPkEncryption,
-        public_key: *const ::std::os::raw::c_void,
+        public_key: *const ::core::ffi::c_void,
         public_key_length: usize,
     ) -> usize;
 }
 extern "C" {
     #[doc = " Get the length of the ciphertext that will correspond to a plaintext of the"]
     #[doc = " given length."]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_pk_ciphertext_length"
+    )]
     pub fn olm_pk_ciphertext_length(
         encryption: *const OlmPkEncryption,
         plaintext_length: usize,
@@ -1466,14 +1870,20 @@ extern "C" {
 }
 extern "C" {
     #[doc = " Get the length of the message authentication code."]
+    #[cfg_attr(feature = "prefix-symbols", link_name = "olmsys_olm_pk_mac_length")]
     pub fn olm_pk_mac_length(encryption: *const OlmPkEncryption) -> usize;
 }
 extern "C" {
     #[doc = " Get the length of a public or ephemeral key"]
+    #[cfg_attr(feature = "prefix-symbols", link_name = "olmsys_olm_pk_key_length")]
     pub fn olm_pk_key_length() -> usize;
 }
 extern "C" {
     #[doc = " The number of random bytes needed to encrypt a message."]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_pk_encrypt_random_length"
+    )]
     pub fn olm_pk_encrypt_random_length(encryption: *const OlmPkEncryption) -> usize;
 }
 extern "C" {
@@ -1487,17 +1897,18 @@ extern "C" {
     #[doc = " ephemeral_key buffers were too small then olm_pk_encryption_last_error()"]
     #[doc = " will be \"OUTPUT_BUFFER_TOO_SMALL\". If there weren't enough random bytes then"]
     #[doc = " olm_pk_encryption_last_error() will be \"OLM_INPUT_BUFFER_TOO_SMALL\"."]
+    #[cfg_attr(feature = "prefix-symbols", link_name = "olmsys_olm_pk_encrypt")]
     pub fn olm_pk_encrypt(
         encryption: *mut OlmPkEncryption,
-        plaintext: *const ::std::os::raw::c_void,
+        plaintext: *const ::core::ffi::c_void,
         plaintext_length: usize,
-        ciphertext: *mut ::std::os::raw::c_void,
+        ciphertext: *mut ::core::ffi::c_void,
         ciphertext_length: usize,
-        mac: *mut ::std::os::raw::c_void,
+        mac: *mut ::core::ffi::c_void,
         mac_length: usize,
-        ephemeral_key: *mut ::std::os::raw::c_void,
+        ephemeral_key: *mut ::core::ffi::c_void,
         ephemeral_key_size: usize,
-        random: *const ::std::os::raw::c_void,
+        random: *const ::core::ffi::c_void,
         random_length: usize,
     ) -> usize;
 }
@@ -1507,35 +1918,60 @@ pub struct OlmPkDecryption {
     _unused: [u8; 0],
 }
 extern "C" {
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_pk_decryption_size"
+    )]
     pub fn olm_pk_decryption_size() -> usize;
 }
 extern "C" {
     #[doc = " Initialise a decryption object using the supplied memory"]
     #[doc = "  The supplied memory must be at least olm_pk_decryption_size() bytes"]
-    pub fn olm_pk_decryption(memory: *mut ::std::os::raw::c_void) -> *mut OlmPkDecryption;
+    #[cfg_attr(feature = "prefix-symbols", link_name = "olmsys_olm_pk_decryption")]
+    pub fn olm_pk_decryption(memory: *mut ::core::ffi::c_void) -> *mut OlmPkDecryption;
 }
 extern "C" {
     #[doc = " A null terminated string describing the most recent error to happen to a"]
     #[doc = " decription object"]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_pk_decryption_last_error"
+    )]
     pub fn olm_pk_decryption_last_error(
         decryption: *const OlmPkDecryption,
-    ) -> *const ::std::os::raw::c_char;
+    ) -> *const ::core::ffi::c_char;
 }
 extern "C" {
     #[doc = " An error code describing the most recent error to happen to a decription"]
     #[doc = " object"]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_pk_decryption_last_error_code"
+    )]
     pub fn olm_pk_decryption_last_error_code(decryption: *const OlmPkDecryption) -> OlmErrorCode;
 }
 extern "C" {
     #[doc = " Clears the memory used to back this decryption object"]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_clear_pk_decryption"
+    )]
     pub fn olm_clear_pk_decryption(decryption: *mut OlmPkDecryption) -> usize;
 }
 extern "C" {
     #[doc = " Get the number of bytes required to store an olm private key"]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_pk_private_key_length"
+    )]
     pub fn olm_pk_private_key_length() -> usize;
 }
 extern "C" {
     #[doc = " DEPRECATED: Use olm_pk_private_key_length()"]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_pk_generate_key_random_length"
+    )]
     pub fn olm_pk_generate_key_random_length() -> usize;
 }
 extern "C" {
@@ -1548,26 +1984,35 @@ extern "C" {
     #[doc = ""]
     #[doc = " Note that the pubkey is a base64 encoded string, but the private key is"]
     #[doc = " an unencoded byte array"]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_pk_key_from_private"
+    )]
     pub fn olm_pk_key_from_private(
         decryption: *mut OlmPkDecryption,
-        pubkey: *mut ::std::os::raw::c_void,
+        pubkey: *mut ::core::ffi::c_void,
         pubkey_length: usize,
-        privkey: *const ::std::os::raw::c_void,
+        privkey: *const ::core::ffi::c_void,
         privkey_length: usize,
     ) -> usize;
 }
 extern "C" {
     #[doc = " DEPRECATED: Use olm_pk_key_from_private"]
+    #[cfg_attr(feature = "prefix-symbols", link_name = "olmsys_olm_pk_generate_key")]
     pub fn olm_pk_generate_key(
         decryption: *mut OlmPkDecryption,
-        pubkey: *mut ::std::os::raw::c_void,
+        pubkey: *mut ::core::ffi::c_void,
         pubkey_length: usize,
-        privkey: *const ::std::os::raw::c_void,
+        privkey: *const ::core::ffi::c_void,
         privkey_length: usize,
     ) -> usize;
 }
 extern "C" {
     #[doc = " Returns the number of bytes needed to store a decryption object."]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_pickle_pk_decryption_length"
+    )]
     pub fn olm_pickle_pk_decryption_length(decryption: *const OlmPkDecryption) -> usize;
 }
 extern "C" {
@@ -1576,11 +2021,15 @@ extern "C" {
     #[doc = " Returns olm_error() on failure. If the pickle output buffer"]
     #[doc = " is smaller than olm_pickle_pk_decryption_length() then"]
     #[doc = " olm_pk_decryption_last_error() will be \"OUTPUT_BUFFER_TOO_SMALL\""]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_pickle_pk_decryption"
+    )]
     pub fn olm_pickle_pk_decryption(
         decryption: *mut OlmPkDecryption,
-        key: *const ::std::os::raw::c_void,
+        key: *const ::core::ffi::c_void,
         key_length: usize,
-        pickled: *mut ::std::os::raw::c_void,
+        pickled: *mut ::core::ffi::c_void,
         pickled_length: usize,
     ) -> usize;
 }
@@ -1592,19 +2041,27 @@ extern "C" {
     #[doc = " will be \"BAD_ACCOUNT_KEY\". If the base64 couldn't be decoded then"]
     #[doc = " olm_pk_decryption_last_error() will be \"INVALID_BASE64\". The input pickled"]
     #[doc = " buffer is destroyed"]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_unpickle_pk_decryption"
+    )]
     pub fn olm_unpickle_pk_decryption(
         decryption: *mut OlmPkDecryption,
-        key: *const ::std::os::raw::c_void,
+        key: *const ::core::ffi::c_void,
         key_length: usize,
-        pickled: *mut ::std::os::raw::c_void,
+        pickled: *mut ::core::ffi::c_void,
         pickled_length: usize,
-        pubkey: *mut ::std::os::raw::c_void,
+        pubkey: *mut ::core::ffi::c_void,
         pubkey_length: usize,
     ) -> usize;
 }
 extern "C" {
     #[doc = " Get the length of the plaintext that will correspond to a ciphertext of the"]
     #[doc = " given length."]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_pk_max_plaintext_length"
+    )]
     pub fn olm_pk_max_plaintext_length(
         decryption: *const OlmPkDecryption,
         ciphertext_length: usize,
@@ -1616,15 +2073,16 @@ extern "C" {
     #[doc = " arguments. Returns the length of the plaintext on success. Returns"]
     #[doc = " olm_error() on failure. If the plaintext buffer is too small then"]
     #[doc = " olm_pk_encryption_last_error() will be \"OUTPUT_BUFFER_TOO_SMALL\"."]
+    #[cfg_attr(feature = "prefix-symbols", link_name = "olmsys_olm_pk_decrypt")]
     pub fn olm_pk_decrypt(
         decryption: *mut OlmPkDecryption,
-        ephemeral_key: *const ::std::os::raw::c_void,
+        ephemeral_key: *const ::core::ffi::c_void,
         ephemeral_key_length: usize,
-        mac: *const ::std::os::raw::c_void,
+        mac: *const ::core::ffi::c_void,
         mac_length: usize,
-        ciphertext: *mut ::std::os::raw::c_void,
+        ciphertext: *mut ::core::ffi::c_void,
         ciphertext_length: usize,
-        plaintext: *mut ::std::os::raw::c_void,
+        plaintext: *mut ::core::ffi::c_void,
         max_plaintext_length: usize,
     ) -> usize;
 }
@@ -1635,9 +2093,13 @@ extern "C" {
     #[doc = " private_key_length. If the given buffer is too small, returns olm_error()"]
     #[doc = " and olm_pk_encryption_last_error() will be \"OUTPUT_BUFFER_TOO_SMALL\"."]
     #[doc = " Returns the number of bytes written."]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_pk_get_private_key"
+    )]
     pub fn olm_pk_get_private_key(
         decryption: *mut OlmPkDecryption,
-        private_key: *mut ::std::os::raw::c_void,
+        private_key: *mut ::core::ffi::c_void,
         private_key_length: usize,
     ) -> usize;
 }
@@ -1647,25 +2109,36 @@ pub struct OlmPkSigning {
     _unused: [u8; 0],
 }
 extern "C" {
+    #[cfg_attr(feature = "prefix-symbols", link_name = "olmsys_olm_pk_signing_size")]
     pub fn olm_pk_signing_size() -> usize;
 }
 extern "C" {
     #[doc = " Initialise a signing object using the supplied memory"]
     #[doc = "  The supplied memory must be at least olm_pk_signing_size() bytes"]
-    pub fn olm_pk_signing(memory: *mut ::std::os::raw::c_void) -> *mut OlmPkSigning;
+    #[cfg_attr(feature = "prefix-symbols", link_name = "olmsys_olm_pk_signing")]
+    pub fn olm_pk_signing(memory: *mut ::core::ffi::c_void) -> *mut OlmPkSigning;
 }
 extern "C" {
     #[doc = " A null terminated string describing the most recent error to happen to a"]
     #[doc = " signing object"]
-    pub fn olm_pk_signing_last_error(sign: *const OlmPkSigning) -> *const ::std::os::raw::c_char;
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_pk_signing_last_error"
+    )]
+    pub fn olm_pk_signing_last_error(sign: *const OlmPkSigning) -> *const ::core::ffi::c_char;
 }
 extern "C" {
     #[doc = " A null terminated string describing the most recent error to happen to a"]
     #[doc = " signing object"]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_pk_signing_last_error_code"
+    )]
     pub fn olm_pk_signing_last_error_code(sign: *const OlmPkSigning) -> OlmErrorCode;
 }
 extern "C" {
     #[doc = " Clears the memory used to back this signing object"]
+    #[cfg_attr(feature = "prefix-symbols", link_name = "olmsys_olm_clear_pk_signing")]
     pub fn olm_clear_pk_signing(sign: *mut OlmPkSigning) -> usize;
 }
 extern "C" {
@@ -1675,30 +2148,47 @@ extern "C" {
     #[doc = " olm_pk_signing_last_error() will be \"OUTPUT_BUFFER_TOO_SMALL\".  If the seed"]
     #[doc = " buffer is too small then olm_pk_signing_last_error() will be"]
     #[doc = " \"INPUT_BUFFER_TOO_SMALL\"."]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_pk_signing_key_from_seed"
+    )]
     pub fn olm_pk_signing_key_from_seed(
         sign: *mut OlmPkSigning,
-        pubkey: *mut ::std::os::raw::c_void,
+        pubkey: *mut ::core::ffi::c_void,
         pubkey_length: usize,
-        seed: *const ::std::os::raw::c_void,
+        seed: *const ::core::ffi::c_void,
         seed_length: usize,
     ) -> usize;
 }
 extern "C" {
     #[doc = " The size required for the seed for initialising a signing object."]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_pk_signing_seed_length"
+    )]
     pub fn olm_pk_signing_seed_length() -> usize;
 }
 extern "C" {
     #[doc = " The size of the public key of a signing object."]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_pk_signing_public_key_length"
+    )]
     pub fn olm_pk_signing_public_key_length() -> usize;
 }
 extern "C" {
     #[doc = " The size of a signature created by a signing object."]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys_olm_pk_signature_length"
+    )]
     pub fn olm_pk_signature_length() -> usize;
 }
 extern "C" {
     #[doc = " Sign a message. The signature will be written to the signature"]
     #[doc = " buffer. Returns olm_error() on failure. If the signature buffer is too"]
     #[doc = " small, olm_pk_signing_last_error() will be \"OUTPUT_BUFFER_TOO_SMALL\"."]
+    #[cfg_attr(feature = "prefix-symbols", link_name = "olmsys_olm_pk_sign")]
     pub fn olm_pk_sign(
         sign: *mut OlmPkSigning,
         message: *const u8,
@@ -1707,3 +2197,339 @@ extern "C" {
         signature_length: usize,
     ) -> usize;
 }
+extern "C" {
+    #[doc = " The length of the buffer needed to hold a group message."]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys__olm_encode_group_message_length"
+    )]
+    pub fn _olm_encode_group_message_length(
+        chain_index: u32,
+        ciphertext_length: usize,
+        mac_length: usize,
+        signature_length: usize,
+    ) -> usize;
+}
+extern "C" {
+    #[doc = " Writes the message headers into the output buffer."]
+    #[doc = ""]
+    #[doc = " version:            version number of the olm protocol"]
+    #[doc = " message_index:      message index"]
+    #[doc = " ciphertext_length:  length of the ciphertext"]
+    #[doc = " output:             where to write the output. Should be at least"]
+    #[doc = "                     olm_encode_group_message_length() bytes long."]
+    #[doc = " ciphertext_ptr:     returns the address that the ciphertext"]
+    #[doc = "                     should be written to, followed by the MAC and the"]
+    #[doc = "                     signature."]
+    #[doc = ""]
+    #[doc = " Returns the size of the message, up to the MAC."]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys__olm_encode_group_message"
+    )]
+    pub fn _olm_encode_group_message(
+        version: u8,
+        message_index: u32,
+        ciphertext_length: usize,
+        output: *mut u8,
+        ciphertext_ptr: *mut *mut u8,
+    ) -> usize;
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _OlmDecodeGroupMessageResults {
+    pub version: u8,
+    pub message_index: u32,
+    pub has_message_index: ::core::ffi::c_int,
+    pub ciphertext: *const u8,
+    pub ciphertext_length: usize,
+}
+extern "C" {
+    #[doc = " Reads the message headers from the input buffer."]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys__olm_decode_group_message"
+    )]
+    pub fn _olm_decode_group_message(
+        input: *const u8,
+        input_length: usize,
+        mac_length: usize,
+        signature_length: usize,
+        results: *mut _OlmDecodeGroupMessageResults,
+    );
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _olm_aes256_key {
+    pub key: [u8; 32usize],
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _olm_aes256_iv {
+    pub iv: [u8; 16usize],
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _olm_curve25519_public_key {
+    pub public_key: [u8; 32usize],
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _olm_curve25519_private_key {
+    pub private_key: [u8; 32usize],
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _olm_curve25519_key_pair {
+    pub public_key: _olm_curve25519_public_key,
+    pub private_key: _olm_curve25519_private_key,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _olm_ed25519_public_key {
+    pub public_key: [u8; 32usize],
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _olm_ed25519_private_key {
+    pub private_key: [u8; 64usize],
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _olm_ed25519_key_pair {
+    pub public_key: _olm_ed25519_public_key,
+    pub private_key: _olm_ed25519_private_key,
+}
+extern "C" {
+    #[doc = " The length of output the aes_encrypt_cbc function will write"]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys__olm_crypto_aes_encrypt_cbc_length"
+    )]
+    pub fn _olm_crypto_aes_encrypt_cbc_length(input_length: usize) -> usize;
+}
+extern "C" {
+    #[doc = " Encrypts the input using AES256 in CBC mode with PKCS#7 padding."]
+    #[doc = " The output buffer must be big enough to hold the output including padding"]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys__olm_crypto_aes_encrypt_cbc"
+    )]
+    pub fn _olm_crypto_aes_encrypt_cbc(
+        key: *const _olm_aes256_key,
+        iv: *const _olm_aes256_iv,
+        input: *const u8,
+        input_length: usize,
+        output: *mut u8,
+    );
+}
+extern "C" {
+    #[doc = " Decrypts the input using AES256 in CBC mode. The output buffer must be at"]
+    #[doc = " least the same size as the input buffer. Returns the length of the plaintext"]
+    #[doc = " without padding on success or std::size_t(-1) if the padding is invalid."]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys__olm_crypto_aes_decrypt_cbc"
+    )]
+    pub fn _olm_crypto_aes_decrypt_cbc(
+        key: *const _olm_aes256_key,
+        iv: *const _olm_aes256_iv,
+        input: *const u8,
+        input_length: usize,
+        output: *mut u8,
+    ) -> usize;
+}
+extern "C" {
+    #[doc = " Computes SHA-256 of the input. The output buffer must be a least"]
+    #[doc = " SHA256_OUTPUT_LENGTH (32) bytes long."]
+    #[cfg_attr(feature = "prefix-symbols", link_name = "olmsys__olm_crypto_sha256")]
+    pub fn _olm_crypto_sha256(input: *const u8, input_length: usize, output: *mut u8);
+}
+extern "C" {
+    #[doc = " HMAC: Keyed-Hashing for Message Authentication"]
+    #[doc = " http://tools.ietf.org/html/rfc2104"]
+    #[doc = " Computes HMAC-SHA-256 of the input for the key. The output buffer must"]
+    #[doc = " be at least SHA256_OUTPUT_LENGTH (32) bytes long."]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys__olm_crypto_hmac_sha256"
+    )]
+    pub fn _olm_crypto_hmac_sha256(
+        key: *const u8,
+        key_length: usize,
+        input: *const u8,
+        input_length: usize,
+        output: *mut u8,
+    );
+}
+extern "C" {
+    #[doc = " HMAC-based Key Derivation Function (HKDF)"]
+    #[doc = " https://tools.ietf.org/html/rfc5869"]
+    #[doc = " Derives key material from the input bytes."]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys__olm_crypto_hkdf_sha256"
+    )]
+    pub fn _olm_crypto_hkdf_sha256(
+        input: *const u8,
+        input_length: usize,
+        info: *const u8,
+        info_length: usize,
+        salt: *const u8,
+        salt_length: usize,
+        output: *mut u8,
+        output_length: usize,
+    );
+}
+extern "C" {
+    #[doc = " Generate a curve25519 key pair"]
+    #[doc = " random_32_bytes should be CURVE25519_RANDOM_LENGTH (32) bytes long."]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys__olm_crypto_curve25519_generate_key"
+    )]
+    pub fn _olm_crypto_curve25519_generate_key(
+        random_32_bytes: *const u8,
+        output: *mut _olm_curve25519_key_pair,
+    );
+}
+extern "C" {
+    #[doc = " Create a shared secret using our private key and their public key."]
+    #[doc = " The output buffer must be at least CURVE25519_SHARED_SECRET_LENGTH (32) bytes long."]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys__olm_crypto_curve25519_shared_secret"
+    )]
+    pub fn _olm_crypto_curve25519_shared_secret(
+        our_key: *const _olm_curve25519_key_pair,
+        their_key: *const _olm_curve25519_public_key,
+        output: *mut u8,
+    );
+}
+extern "C" {
+    #[doc = " Generate a ed25519 key pair"]
+    #[doc = " random_32_bytes should be ED25519_RANDOM_LENGTH (32) bytes long."]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys__olm_crypto_ed25519_generate_key"
+    )]
+    pub fn _olm_crypto_ed25519_generate_key(
+        random_bytes: *const u8,
+        output: *mut _olm_ed25519_key_pair,
+    );
+}
+extern "C" {
+    #[doc = " Signs the message using our private key."]
+    #[doc = ""]
+    #[doc = " The output buffer must be at least ED25519_SIGNATURE_LENGTH (64) bytes"]
+    #[doc = " long."]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys__olm_crypto_ed25519_sign"
+    )]
+    pub fn _olm_crypto_ed25519_sign(
+        our_key: *const _olm_ed25519_key_pair,
+        message: *const u8,
+        message_length: usize,
+        output: *mut u8,
+    );
+}
+extern "C" {
+    #[doc = " Verify an ed25519 signature"]
+    #[doc = " The signature input buffer must be ED25519_SIGNATURE_LENGTH (64) bytes long."]
+    #[doc = " Returns non-zero if the signature is valid."]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys__olm_crypto_ed25519_verify"
+    )]
+    pub fn _olm_crypto_ed25519_verify(
+        their_key: *const _olm_ed25519_public_key,
+        message: *const u8,
+        message_length: usize,
+        signature: *const u8,
+    ) -> ::core::ffi::c_int;
+}
+extern "C" {
+    #[doc = " Get the number of bytes needed to encode a pickle of the length given"]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys__olm_enc_output_length"
+    )]
+    pub fn _olm_enc_output_length(raw_length: usize) -> usize;
+}
+extern "C" {
+    #[doc = " Get the point in the output buffer that the raw pickle should be written to."]
+    #[doc = ""]
+    #[doc = " In order that we can use the same buffer for the raw pickle, and the encoded"]
+    #[doc = " pickle, the raw pickle needs to be written at the end of the buffer. (The"]
+    #[doc = " base-64 encoding would otherwise overwrite the end of the input before it"]
+    #[doc = " was encoded.)"]
+    #[cfg_attr(feature = "prefix-symbols", link_name = "olmsys__olm_enc_output_pos")]
+    pub fn _olm_enc_output_pos(output: *mut u8, raw_length: usize) -> *mut u8;
+}
+extern "C" {
+    #[doc = " Encrypt and encode the given pickle in-situ."]
+    #[doc = ""]
+    #[doc = " The raw pickle should have been written to enc_output_pos(pickle,"]
+    #[doc = " raw_length)."]
+    #[doc = ""]
+    #[doc = " Returns the number of bytes in the encoded pickle."]
+    #[cfg_attr(feature = "prefix-symbols", link_name = "olmsys__olm_enc_output")]
+    pub fn _olm_enc_output(
+        key: *const u8,
+        key_length: usize,
+        pickle: *mut u8,
+        raw_length: usize,
+    ) -> usize;
+}
+extern "C" {
+    #[doc = " Decode and decrypt the given pickle in-situ."]
+    #[doc = ""]
+    #[doc = " Returns the number of bytes in the decoded pickle, or olm_error() on error,"]
+    #[doc = " in which case *last_error will be updated, if last_error is non-NULL."]
+    #[cfg_attr(feature = "prefix-symbols", link_name = "olmsys__olm_enc_input")]
+    pub fn _olm_enc_input(
+        key: *const u8,
+        key_length: usize,
+        input: *mut u8,
+        b64_length: usize,
+        last_error: *mut OlmErrorCode,
+    ) -> usize;
+}
+extern "C" {
+    #[doc = " The number of bytes of unpadded base64 needed to encode a length of input."]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys__olm_encode_base64_length"
+    )]
+    pub fn _olm_encode_base64_length(input_length: usize) -> usize;
+}
+extern "C" {
+    #[doc = " Encode the raw input as unpadded base64."]
+    #[doc = " Writes encode_base64_length(input_length) bytes to the output buffer."]
+    #[doc = " The input can overlap with the last three quarters of the output buffer."]
+    #[doc = " That is, the input pointer may be output + output_length - input_length."]
+    #[doc = ""]
+    #[doc = " Returns number of bytes encoded"]
+    #[cfg_attr(feature = "prefix-symbols", link_name = "olmsys__olm_encode_base64")]
+    pub fn _olm_encode_base64(input: *const u8, input_length: usize, output: *mut u8) -> usize;
+}
+extern "C" {
+    #[doc = " The number of bytes of raw data a length of unpadded base64 will encode to."]
+    #[doc = " Returns size_t(-1) if the length is not a valid length for base64."]
+    #[cfg_attr(
+        feature = "prefix-symbols",
+        link_name = "olmsys__olm_decode_base64_length"
+    )]
+    pub fn _olm_decode_base64_length(input_length: usize) -> usize;
+}
+extern "C" {
+    #[doc = " Decodes the unpadded base64 input to raw bytes."]
+    #[doc = " Writes decode_base64_length(input_length) bytes to the output buffer."]
+    #[doc = " The output can overlap with the first three quarters of the input buffer."]
+    #[doc = " That is, the input pointers and output pointer may be the same."]
+    #[doc = ""]
+    #[doc = " Returns number of bytes decoded"]
+    #[cfg_attr(feature = "prefix-symbols", link_name = "olmsys__olm_decode_base64")]
+    pub fn _olm_decode_base64(input: *const u8, input_length: usize, output: *mut u8) -> usize;
+}
@@ -0,0 +1,131 @@
+// Copyright 2020 Johannes Hayeß
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Inspecting Megolm group messages without decrypting them.
+
+use alloc::vec::Vec;
+
+use core::ops::Range;
+
+use crate::backend::olm;
+use crate::error::OlmError;
+use crate::{
+    _OlmDecodeGroupMessageResults, OlmErrorCode_OLM_BAD_MESSAGE_FORMAT,
+    OlmErrorCode_OLM_INVALID_BASE64,
+};
+
+/// The headers and trailers of a Megolm message, as `olm_group_encrypt(…)`
+/// produces it.
+///
+/// A message is laid out as the version byte, the encoded message index and
+/// ciphertext, a [`MAC_LENGTH`](Self::MAC_LENGTH) byte MAC over all of that,
+/// and a [`SIGNATURE_LENGTH`](Self::SIGNATURE_LENGTH) byte Ed25519 signature
+/// over everything before it. Offsets refer to the base64 decoded message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MegolmMessage {
+    bytes: Vec<u8>,
+    version: u8,
+    message_index: Option<u32>,
+    ciphertext: Range<usize>,
+}
+
+impl MegolmMessage {
+    /// Length of the truncated HMAC-SHA-256 following the ciphertext.
+    pub const MAC_LENGTH: usize = 8;
+    /// Length of the Ed25519 signature at the end of the message.
+    pub const SIGNATURE_LENGTH: usize = 64;
+
+    /// Decodes the headers of the base64 `message`.
+    ///
+    /// Only the structure is checked: the version may be one `libolm` can't
+    /// decrypt, and neither the MAC nor the signature are verified. Fails with
+    /// `OLM_INVALID_BASE64` or, if the message is too short or has no
+    /// ciphertext, `OLM_BAD_MESSAGE_FORMAT`.
+    pub fn parse(message: &str) -> Result<Self, OlmError> {
+        let bytes =
+            crate::base64::decode(message).ok_or(OlmError::new(OlmErrorCode_OLM_INVALID_BASE64))?;
+
+        let mut results = _OlmDecodeGroupMessageResults {
+            version: 0,
+            message_index: 0,
+            has_message_index: 0,
+            ciphertext: core::ptr::null(),
+            ciphertext_length: 0,
+        };
+        unsafe {
+            olm!(_olm_decode_group_message(
+                bytes.as_ptr(),
+                bytes.len(),
+                Self::MAC_LENGTH,
+                Self::SIGNATURE_LENGTH,
+                &mut results,
+            ))
+        };
+
+        if results.ciphertext.is_null() {
+            return Err(OlmError::new(OlmErrorCode_OLM_BAD_MESSAGE_FORMAT));
+        }
+        let start = results.ciphertext as usize - bytes.as_ptr() as usize;
+
+        Ok(MegolmMessage {
+            version: results.version,
+            message_index: (results.has_message_index != 0).then_some(results.message_index),
+            ciphertext: start..start + results.ciphertext_length,
+            bytes,
+        })
+    }
+
+    /// The protocol version, 3 for all messages `libolm` currently creates.
+    pub fn version(&self) -> u8 {
+        self.version
+    }
+
+    /// The index of the message in the ratchet of its session, if the
+    /// message has one. A session can only decrypt messages from its first
+    /// known index onwards.
+    pub fn message_index(&self) -> Option<u32> {
+        self.message_index
+    }
+
+    /// The encrypted payload.
+    pub fn ciphertext(&self) -> &[u8] {
+        &self.bytes[self.ciphertext.clone()]
+    }
+
+    /// Offset of the MAC in the decoded message.
+    pub fn mac_offset(&self) -> usize {
+        self.signature_offset() - Self::MAC_LENGTH
+    }
+
+    /// The MAC of the version, index and ciphertext.
+    pub fn mac(&self) -> &[u8] {
+        &self.bytes[self.mac_offset()..self.signature_offset()]
+    }
+
+    /// Offset of the signature in the decoded message, which is also the
+    /// length of the signed part.
+    pub fn signature_offset(&self) -> usize {
+        self.bytes.len() - Self::SIGNATURE_LENGTH
+    }
+
+    /// The Ed25519 signature of the session's signing key.
+    pub fn signature(&self) -> &[u8] {
+        &self.bytes[self.signature_offset()..]
+    }
+
+    /// The base64 decoded message.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+}
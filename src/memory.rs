@@ -0,0 +1,31 @@
+// Copyright 2020 Johannes Hayeß
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Memory for the `libolm` objects the safe wrappers own.
+
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// Zeroed, 8-byte aligned memory of at least `size` bytes, which doesn't move
+/// when the owning wrapper does.
+pub(crate) fn object_memory(size: usize) -> Box<[u64]> {
+    vec![0; size.div_ceil(8)].into_boxed_slice()
+}
+
+/// The base64 or JSON output of a `libolm` function.
+pub(crate) fn into_string(output: Vec<u8>) -> String {
+    String::from_utf8(output).expect("libolm wrote a non-ASCII string")
+}
@@ -0,0 +1,463 @@
+// Copyright 2020 Johannes Hayeß
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Inspecting Olm messages without a session or an account.
+//!
+//! The messages are decoded by `libolm`'s own `olm/src/message.cpp`, through
+//! the C entry points of `shim/message.cpp`, so fields are read exactly as
+//! when decrypting: in order, with unknown ones skipped and truncated ones
+//! dropped. Shared builds of `libolm` hide these decoders, so when linking
+//! one, or loading it at runtime, a port of them decodes the messages instead.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use core::ops::Range;
+
+use crate::error::OlmError;
+use crate::{OlmErrorCode_OLM_BAD_MESSAGE_FORMAT, OlmErrorCode_OLM_INVALID_BASE64};
+
+/// Length of the Curve25519 keys in a message.
+const KEY_LENGTH: usize = 32;
+
+/// A normal Olm message, `OLM_MESSAGE_TYPE_MESSAGE`, or the message inside a
+/// [`PreKeyMessage`].
+///
+/// Neither the MAC nor the ratchet are checked, only the structure.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OlmMessage {
+    bytes: Vec<u8>,
+    version: u8,
+    ratchet_key: Range<usize>,
+    chain_index: u32,
+    ciphertext: Range<usize>,
+}
+
+impl OlmMessage {
+    /// Length of the truncated HMAC-SHA-256 at the end of the message.
+    pub const MAC_LENGTH: usize = 8;
+
+    /// Decodes the base64 `message`.
+    ///
+    /// Fails with `OLM_INVALID_BASE64`, or with `OLM_BAD_MESSAGE_FORMAT` if the
+    /// ratchet key, the chain index or the ciphertext is missing, like
+    /// `libolm` would.
+    pub fn parse(message: &str) -> Result<Self, OlmError> {
+        OlmMessage::decode(decode_base64(message)?)
+    }
+
+    fn decode(bytes: Vec<u8>) -> Result<Self, OlmError> {
+        if bytes.len() <= Self::MAC_LENGTH {
+            return Err(bad_format());
+        }
+
+        let fields = decoder::decode_message(&bytes, Self::MAC_LENGTH);
+        Ok(OlmMessage {
+            version: fields.version,
+            ratchet_key: key(fields.ratchet_key)?,
+            chain_index: fields.counter.ok_or_else(bad_format)?,
+            ciphertext: fields.ciphertext.ok_or_else(bad_format)?,
+            bytes,
+        })
+    }
+
+    /// The protocol version, 3 for all messages `libolm` currently creates.
+    pub fn version(&self) -> u8 {
+        self.version
+    }
+
+    /// The sender's current ratchet key, as unpadded base64.
+    pub fn ratchet_key(&self) -> String {
+        crate::base64::encode(&self.bytes[self.ratchet_key.clone()])
+    }
+
+    /// The index of the message in the sender's chain.
+    pub fn chain_index(&self) -> u32 {
+        self.chain_index
+    }
+
+    /// The encrypted payload.
+    pub fn ciphertext(&self) -> &[u8] {
+        &self.bytes[self.ciphertext.clone()]
+    }
+
+    /// The MAC of everything before it.
+    pub fn mac(&self) -> &[u8] {
+        &self.bytes[self.bytes.len() - Self::MAC_LENGTH..]
+    }
+
+    /// The base64 decoded message.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+/// A pre-key message, `OLM_MESSAGE_TYPE_PRE_KEY`, which sets up a new session
+/// from one of the recipient's one-time keys.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PreKeyMessage {
+    version: u8,
+    one_time_key: [u8; KEY_LENGTH],
+    base_key: [u8; KEY_LENGTH],
+    identity_key: [u8; KEY_LENGTH],
+    message: OlmMessage,
+}
+
+impl PreKeyMessage {
+    /// Decodes the base64 `message`.
+    ///
+    /// Fails with `OLM_INVALID_BASE64`, or with `OLM_BAD_MESSAGE_FORMAT` if a
+    /// key or the inner message is missing, like `libolm` would.
+    pub fn parse(message: &str) -> Result<Self, OlmError> {
+        let bytes = decode_base64(message)?;
+        if bytes.is_empty() {
+            return Err(bad_format());
+        }
+
+        let fields = decoder::decode_pre_key_message(&bytes);
+        let key_bytes = |range| -> Result<[u8; KEY_LENGTH], OlmError> {
+            Ok(bytes[key(range)?].try_into().unwrap())
+        };
+        let inner = fields.message.ok_or_else(bad_format)?;
+
+        Ok(PreKeyMessage {
+            version: fields.version,
+            one_time_key: key_bytes(fields.one_time_key)?,
+            base_key: key_bytes(fields.base_key)?,
+            identity_key: key_bytes(fields.identity_key)?,
+            message: OlmMessage::decode(bytes[inner].to_vec())?,
+        })
+    }
+
+    /// The protocol version, 3 for all messages `libolm` currently creates.
+    pub fn version(&self) -> u8 {
+        self.version
+    }
+
+    /// The recipient's one-time key the session was created with, as
+    /// unpadded base64 like in `olm_account_one_time_keys(…)`.
+    pub fn one_time_key(&self) -> String {
+        crate::base64::encode(&self.one_time_key)
+    }
+
+    /// The sender's ephemeral base key, as unpadded base64.
+    pub fn base_key(&self) -> String {
+        crate::base64::encode(&self.base_key)
+    }
+
+    /// The sender's Curve25519 identity key, as unpadded base64.
+    pub fn identity_key(&self) -> String {
+        crate::base64::encode(&self.identity_key)
+    }
+
+    /// The first message of the session.
+    pub fn message(&self) -> &OlmMessage {
+        &self.message
+    }
+}
+
+fn bad_format() -> OlmError {
+    OlmError::new(OlmErrorCode_OLM_BAD_MESSAGE_FORMAT)
+}
+
+fn decode_base64(message: &str) -> Result<Vec<u8>, OlmError> {
+    crate::base64::decode(message).ok_or(OlmError::new(OlmErrorCode_OLM_INVALID_BASE64))
+}
+
+/// The range of a Curve25519 key, which has to be present.
+fn key(range: Option<Range<usize>>) -> Result<Range<usize>, OlmError> {
+    range
+        .filter(|range| range.len() == KEY_LENGTH)
+        .ok_or_else(bad_format)
+}
+
+/// The fields `decode_message(…)` found in a normal message, as ranges of its
+/// bytes.
+struct MessageFields {
+    version: u8,
+    counter: Option<u32>,
+    ratchet_key: Option<Range<usize>>,
+    ciphertext: Option<Range<usize>>,
+}
+
+/// The fields `decode_one_time_key_message(…)` found in a pre-key message, as
+/// ranges of its bytes.
+struct PreKeyMessageFields {
+    version: u8,
+    one_time_key: Option<Range<usize>>,
+    base_key: Option<Range<usize>>,
+    identity_key: Option<Range<usize>>,
+    message: Option<Range<usize>>,
+}
+
+/// `libolm`'s decoders, through `shim/message.cpp`.
+#[cfg(olm_sys_message_shim)]
+mod decoder {
+    use super::{MessageFields, PreKeyMessageFields};
+
+    use core::ops::Range;
+    use core::ptr;
+
+    #[repr(C)]
+    struct olm_sys_message_reader {
+        version: u8,
+        has_counter: u8,
+        counter: u32,
+        ratchet_key: *const u8,
+        ratchet_key_length: usize,
+        ciphertext: *const u8,
+        ciphertext_length: usize,
+    }
+
+    #[repr(C)]
+    struct olm_sys_pre_key_message_reader {
+        version: u8,
+        identity_key: *const u8,
+        identity_key_length: usize,
+        base_key: *const u8,
+        base_key_length: usize,
+        one_time_key: *const u8,
+        one_time_key_length: usize,
+        message: *const u8,
+        message_length: usize,
+    }
+
+    extern "C" {
+        fn olm_sys_decode_message(
+            reader: *mut olm_sys_message_reader,
+            input: *const u8,
+            input_length: usize,
+            mac_length: usize,
+        );
+        fn olm_sys_decode_pre_key_message(
+            reader: *mut olm_sys_pre_key_message_reader,
+            input: *const u8,
+            input_length: usize,
+        );
+    }
+
+    pub(super) fn decode_message(input: &[u8], mac_length: usize) -> MessageFields {
+        let mut reader = olm_sys_message_reader {
+            version: 0,
+            has_counter: 0,
+            counter: 0,
+            ratchet_key: ptr::null(),
+            ratchet_key_length: 0,
+            ciphertext: ptr::null(),
+            ciphertext_length: 0,
+        };
+        unsafe { olm_sys_decode_message(&mut reader, input.as_ptr(), input.len(), mac_length) };
+
+        MessageFields {
+            version: reader.version,
+            counter: (reader.has_counter != 0).then_some(reader.counter),
+            ratchet_key: range(input, reader.ratchet_key, reader.ratchet_key_length),
+            ciphertext: range(input, reader.ciphertext, reader.ciphertext_length),
+        }
+    }
+
+    pub(super) fn decode_pre_key_message(input: &[u8]) -> PreKeyMessageFields {
+        let mut reader = olm_sys_pre_key_message_reader {
+            version: 0,
+            identity_key: ptr::null(),
+            identity_key_length: 0,
+            base_key: ptr::null(),
+            base_key_length: 0,
+            one_time_key: ptr::null(),
+            one_time_key_length: 0,
+            message: ptr::null(),
+            message_length: 0,
+        };
+        unsafe { olm_sys_decode_pre_key_message(&mut reader, input.as_ptr(), input.len()) };
+
+        PreKeyMessageFields {
+            version: reader.version,
+            one_time_key: range(input, reader.one_time_key, reader.one_time_key_length),
+            base_key: range(input, reader.base_key, reader.base_key_length),
+            identity_key: range(input, reader.identity_key, reader.identity_key_length),
+            message: range(input, reader.message, reader.message_length),
+        }
+    }
+
+    /// Where the field the decoder found at `field` lies in `input`.
+    fn range(input: &[u8], field: *const u8, length: usize) -> Option<Range<usize>> {
+        if field.is_null() {
+            return None;
+        }
+
+        let start = field as usize - input.as_ptr() as usize;
+        Some(start..start + length)
+    }
+}
+
+/// A port of `libolm`'s decoders, for shared builds of it which hide them.
+#[cfg(not(olm_sys_message_shim))]
+mod decoder {
+    use super::{MessageFields, PreKeyMessageFields};
+
+    use core::ops::Range;
+
+    const RATCHET_KEY_TAG: u8 = 0o12;
+    const COUNTER_TAG: u8 = 0o20;
+    const CIPHERTEXT_TAG: u8 = 0o42;
+
+    const ONE_TIME_KEY_ID_TAG: u8 = 0o12;
+    const BASE_KEY_TAG: u8 = 0o22;
+    const IDENTITY_KEY_TAG: u8 = 0o32;
+    const MESSAGE_TAG: u8 = 0o42;
+
+    /// Port of `decode_message(…)`.
+    pub(super) fn decode_message(input: &[u8], mac_length: usize) -> MessageFields {
+        let mut ratchet_key = Field::Bytes(None);
+        let mut counter = Field::Varint(None);
+        let mut ciphertext = Field::Bytes(None);
+        decode_fields(
+            &input[..input.len() - mac_length],
+            &mut [
+                (RATCHET_KEY_TAG, &mut ratchet_key),
+                (COUNTER_TAG, &mut counter),
+                (CIPHERTEXT_TAG, &mut ciphertext),
+            ],
+        );
+
+        MessageFields {
+            version: input[0],
+            counter: counter.varint(),
+            ratchet_key: ratchet_key.bytes(),
+            ciphertext: ciphertext.bytes(),
+        }
+    }
+
+    /// Port of `decode_one_time_key_message(…)`.
+    pub(super) fn decode_pre_key_message(input: &[u8]) -> PreKeyMessageFields {
+        let mut one_time_key = Field::Bytes(None);
+        let mut base_key = Field::Bytes(None);
+        let mut identity_key = Field::Bytes(None);
+        let mut message = Field::Bytes(None);
+        decode_fields(
+            input,
+            &mut [
+                (ONE_TIME_KEY_ID_TAG, &mut one_time_key),
+                (BASE_KEY_TAG, &mut base_key),
+                (IDENTITY_KEY_TAG, &mut identity_key),
+                (MESSAGE_TAG, &mut message),
+            ],
+        );
+
+        PreKeyMessageFields {
+            version: input[0],
+            one_time_key: one_time_key.bytes(),
+            base_key: base_key.bytes(),
+            identity_key: identity_key.bytes(),
+            message: message.bytes(),
+        }
+    }
+
+    /// A field of a message, and where it was found if it was.
+    enum Field {
+        Varint(Option<u32>),
+        Bytes(Option<Range<usize>>),
+    }
+
+    impl Field {
+        fn varint(self) -> Option<u32> {
+            match self {
+                Field::Varint(value) => value,
+                Field::Bytes(_) => None,
+            }
+        }
+
+        fn bytes(self) -> Option<Range<usize>> {
+            match self {
+                Field::Bytes(range) => range,
+                Field::Varint(_) => None,
+            }
+        }
+    }
+
+    /// Decodes the fields after the version byte of `input`, port of the
+    /// loops in `decode_message(…)` and `decode_one_time_key_message(…)`.
+    fn decode_fields(input: &[u8], fields: &mut [(u8, &mut Field)]) {
+        let end = input.len();
+        let mut pos = 1;
+
+        while pos < end {
+            let unknown = pos;
+            for (tag, field) in fields.iter_mut() {
+                if pos == end || input[pos] != *tag {
+                    continue;
+                }
+
+                let start = pos + 1;
+                pos = skip_varint(input, start);
+                let value = decode_varint(&input[start..pos]);
+                match field {
+                    Field::Varint(varint) => *varint = Some(value as u32),
+                    Field::Bytes(range) => {
+                        let length = value as usize;
+                        if length > end - pos {
+                            pos = end;
+                        } else {
+                            *range = Some(pos..pos + length);
+                            pos += length;
+                        }
+                    }
+                }
+            }
+
+            if unknown == pos {
+                pos = skip_unknown(input, pos);
+            }
+        }
+    }
+
+    /// Port of `varint_skip`: the position after the varint at `pos`.
+    fn skip_varint(input: &[u8], mut pos: usize) -> usize {
+        while pos < input.len() {
+            pos += 1;
+            if input[pos - 1] & 0x80 == 0 {
+                break;
+            }
+        }
+
+        pos
+    }
+
+    /// Port of `varint_decode`, which silently drops overflowing bits.
+    fn decode_varint(varint: &[u8]) -> u64 {
+        varint
+            .iter()
+            .rev()
+            .fold(0, |value, &byte| value << 7 | u64::from(byte & 0x7f))
+    }
+
+    /// Port of `skip_unknown`: skips a varint or length delimited field, or
+    /// gives up on the rest of the message for other wire types.
+    fn skip_unknown(input: &[u8], pos: usize) -> usize {
+        match input[pos] & 0x7 {
+            0 => skip_varint(input, skip_varint(input, pos)),
+            2 => {
+                let start = skip_varint(input, pos);
+                let pos = skip_varint(input, start);
+                let length = decode_varint(&input[start..pos]) as usize;
+                if length > input.len() - pos {
+                    input.len()
+                } else {
+                    pos + length
+                }
+            }
+            _ => input.len(),
+        }
+    }
+}
@@ -0,0 +1,152 @@
+// Copyright 2020 Johannes Hayeß
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Keeping the server stocked with one-time keys and a fresh fallback key.
+//!
+//! An account holds at most [`Account::max_number_of_one_time_keys`]
+//! one-time keys, published or not, and generating more discards the oldest
+//! ones, which may still be on the server. [`OneTimeKeyManager`] tops the
+//! server up to half of that, and rotates the fallback key on a
+//! [`FallbackKeySchedule`]:
+//!
+//! ```no_run
+//! use core::time::Duration;
+//! use olm_sys::account::Account;
+//! use olm_sys::one_time_keys::{FallbackKeySchedule, OneTimeKeyManager};
+//! # use olm_sys::random::RandomSource;
+//! # struct Random;
+//! # impl RandomSource for Random {
+//! #     fn fill_bytes(&mut self, buffer: &mut [u8]) {}
+//! # }
+//! # let mut account = Account::new(&mut Random);
+//! # let now = Duration::ZERO;
+//! # let server_count = 0;
+//!
+//! let mut manager =
+//!     OneTimeKeyManager::new("@alice:example.org", "DEVICE", FallbackKeySchedule::default());
+//!
+//! // With the `signed_curve25519` count of `one_time_key_counts`
+//! let upload = manager.keys_to_upload(&mut account, server_count, now, &mut Random);
+//! // Once the server accepted `upload`
+//! manager.mark_published(&mut account, now);
+//! ```
+
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+
+use core::time::Duration;
+
+use serde_json::Value;
+
+use crate::account::Account;
+use crate::fallback_keys::FallbackKeys;
+use crate::random::RandomSource;
+
+pub use crate::fallback_keys::FallbackKeySchedule;
+
+/// Decides how many one-time keys to generate and when to replace the
+/// fallback key.
+///
+/// Times are given by the caller, as the time since the Unix epoch.
+#[derive(Debug, Clone)]
+pub struct OneTimeKeyManager {
+    user_id: String,
+    device_id: String,
+    schedule: FallbackKeySchedule,
+    fallback_keys: FallbackKeys,
+}
+
+impl OneTimeKeyManager {
+    /// Manages the keys of the device `device_id` of `user_id`.
+    pub fn new(user_id: &str, device_id: &str, schedule: FallbackKeySchedule) -> Self {
+        OneTimeKeyManager::with_fallback_keys(user_id, device_id, schedule, FallbackKeys::new())
+    }
+
+    /// Like [`OneTimeKeyManager::new`], for an account whose fallback keys
+    /// were tracked before, e.g. by the unpickled [`FallbackKeys`].
+    pub fn with_fallback_keys(
+        user_id: &str,
+        device_id: &str,
+        schedule: FallbackKeySchedule,
+        fallback_keys: FallbackKeys,
+    ) -> Self {
+        OneTimeKeyManager {
+            user_id: user_id.to_owned(),
+            device_id: device_id.to_owned(),
+            schedule,
+            fallback_keys,
+        }
+    }
+
+    /// The lifetimes of the account's fallback keys, to pickle alongside
+    /// the account.
+    pub fn fallback_keys(&self) -> &FallbackKeys {
+        &self.fallback_keys
+    }
+
+    /// The lifetimes of the account's fallback keys, e.g. to record that a
+    /// session was created with one.
+    pub fn fallback_keys_mut(&mut self) -> &mut FallbackKeys {
+        &mut self.fallback_keys
+    }
+
+    /// How many one-time keys to generate when the server has
+    /// `server_count` of them, counting those generated but not yet
+    /// published as already uploaded.
+    pub fn keys_to_generate(account: &Account, server_count: usize) -> usize {
+        let unpublished = serde_json::from_str::<Value>(&account.one_time_keys())
+            .expect("libolm wrote valid JSON")["curve25519"]
+            .as_object()
+            .map_or(0, |keys| keys.len());
+        let target = account.max_number_of_one_time_keys() / 2;
+
+        target.saturating_sub(server_count + unpublished)
+    }
+
+    /// Generates the one-time keys the server is missing, replaces and
+    /// forgets fallback keys as scheduled, and returns the signed
+    /// `one_time_keys` and `fallback_keys` to upload, like
+    /// [`Account::signed_one_time_keys`].
+    ///
+    /// `server_count` is the `signed_curve25519` count of the server's
+    /// `one_time_key_counts`. Call [`OneTimeKeyManager::mark_published`]
+    /// once the server accepted the upload.
+    pub fn keys_to_upload(
+        &mut self,
+        account: &mut Account,
+        server_count: usize,
+        now: Duration,
+        random: &mut impl RandomSource,
+    ) -> Value {
+        let count = OneTimeKeyManager::keys_to_generate(account, server_count);
+        if count > 0 {
+            account.generate_one_time_keys(count, random);
+        }
+
+        if self.fallback_keys.can_forget_old(now, &self.schedule) {
+            self.fallback_keys.forget_old(account);
+        }
+        if self.fallback_keys.should_rotate(now, &self.schedule) {
+            self.fallback_keys.generate(account, now, random);
+        }
+
+        account.signed_one_time_keys(&self.user_id, &self.device_id)
+    }
+
+    /// Marks the keys of the accepted upload as published, see
+    /// [`FallbackKeys::mark_published`].
+    pub fn mark_published(&mut self, account: &mut Account, now: Duration) {
+        self.fallback_keys.mark_published(account, now);
+    }
+}
@@ -0,0 +1,148 @@
+// Copyright 2020 Johannes Hayeß
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Pickles of the objects built on top of `libolm`, encrypted the way
+//! `libolm` encrypts its own.
+
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use zeroize::Zeroizing;
+
+use crate::backend::olm;
+use crate::error::OlmError;
+use crate::memory::into_string;
+use crate::{
+    OlmErrorCode_OLM_CORRUPTED_PICKLE, OlmErrorCode_OLM_SUCCESS,
+    OlmErrorCode_OLM_UNKNOWN_PICKLE_VERSION,
+};
+
+/// Encrypts the raw pickle `raw` with `key` into a base64 pickle.
+pub(crate) fn encrypt(key: &[u8], raw: &[u8]) -> String {
+    let length = unsafe { olm!(_olm_enc_output_length(raw.len())) };
+    // The raw pickle is encrypted in place, at the end of the buffer
+    let mut pickle = vec![0; length];
+    unsafe {
+        let position = olm!(_olm_enc_output_pos(pickle.as_mut_ptr(), raw.len()));
+        core::ptr::copy_nonoverlapping(raw.as_ptr(), position, raw.len());
+    }
+    let length = unsafe {
+        olm!(_olm_enc_output(
+            key.as_ptr(),
+            key.len(),
+            pickle.as_mut_ptr(),
+            raw.len(),
+        ))
+    };
+    pickle.truncate(length);
+
+    into_string(pickle)
+}
+
+/// Decrypts a pickle made by [`encrypt`] back into the raw pickle.
+///
+/// Fails with `OLM_BAD_ACCOUNT_KEY` if `key` is wrong, or
+/// `OLM_INVALID_BASE64` if the pickle isn't base64.
+pub(crate) fn decrypt(key: &[u8], pickle: &str) -> Result<Zeroizing<Vec<u8>>, OlmError> {
+    // Decrypting decodes the pickle in place
+    let mut raw = Zeroizing::new(pickle.as_bytes().to_vec());
+    let mut code = OlmErrorCode_OLM_SUCCESS;
+    let length = unsafe {
+        olm!(_olm_enc_input(
+            key.as_ptr(),
+            key.len(),
+            raw.as_mut_ptr(),
+            raw.len(),
+            &mut code,
+        ))
+    };
+    if length == unsafe { olm!(olm_error()) } {
+        return Err(OlmError::new(code));
+    }
+    raw.truncate(length);
+
+    Ok(raw)
+}
+
+/// Appends `string` to a raw pickle, prefixed with its length.
+pub(crate) fn write_string(raw: &mut Vec<u8>, string: &str) {
+    raw.extend_from_slice(&(string.len() as u32).to_be_bytes());
+    raw.extend_from_slice(string.as_bytes());
+}
+
+/// Reads the fields of a raw pickle, which are big-endian like `libolm`'s.
+///
+/// Running out of data fails with `OLM_CORRUPTED_PICKLE`.
+pub(crate) struct Reader<'a> {
+    raw: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    pub(crate) fn new(raw: &'a [u8]) -> Self {
+        Reader { raw }
+    }
+
+    /// Reads the version, failing with `OLM_UNKNOWN_PICKLE_VERSION` if it
+    /// isn't `expected`.
+    pub(crate) fn version(&mut self, expected: u32) -> Result<(), OlmError> {
+        if self.u32()? == expected {
+            Ok(())
+        } else {
+            Err(OlmError::new(OlmErrorCode_OLM_UNKNOWN_PICKLE_VERSION))
+        }
+    }
+
+    pub(crate) fn bytes(&mut self, length: usize) -> Result<&'a [u8], OlmError> {
+        if self.raw.len() < length {
+            return Err(OlmError::new(OlmErrorCode_OLM_CORRUPTED_PICKLE));
+        }
+        let (bytes, rest) = self.raw.split_at(length);
+        self.raw = rest;
+
+        Ok(bytes)
+    }
+
+    pub(crate) fn array<const N: usize>(&mut self) -> Result<&'a [u8; N], OlmError> {
+        Ok(self.bytes(N)?.try_into().expect("read N bytes"))
+    }
+
+    pub(crate) fn u8(&mut self) -> Result<u8, OlmError> {
+        Ok(self.array::<1>()?[0])
+    }
+
+    pub(crate) fn u32(&mut self) -> Result<u32, OlmError> {
+        Ok(u32::from_be_bytes(*self.array()?))
+    }
+
+    pub(crate) fn u64(&mut self) -> Result<u64, OlmError> {
+        Ok(u64::from_be_bytes(*self.array()?))
+    }
+
+    /// Reads a string written by [`write_string`].
+    pub(crate) fn string(&mut self) -> Result<&'a str, OlmError> {
+        let length = self.u32()? as usize;
+        core::str::from_utf8(self.bytes(length)?)
+            .map_err(|_| OlmError::new(OlmErrorCode_OLM_CORRUPTED_PICKLE))
+    }
+
+    /// Checks that the whole pickle was read.
+    pub(crate) fn finish(self) -> Result<(), OlmError> {
+        if self.raw.is_empty() {
+            Ok(())
+        } else {
+            Err(OlmError::new(OlmErrorCode_OLM_CORRUPTED_PICKLE))
+        }
+    }
+}
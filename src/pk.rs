@@ -0,0 +1,353 @@
+// Copyright 2020 Johannes Hayeß
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Safe wrappers around `OlmPkEncryption` and `OlmPkDecryption`, which
+//! encrypt messages for a Curve25519 public key, and `OlmPkSigning`, which
+//! signs with an Ed25519 key that isn't tied to an account.
+
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use core::ffi::c_void;
+
+use crate::backend::olm;
+use crate::error::OlmError;
+use crate::memory::{into_string, object_memory};
+use crate::random::{random_bytes, RandomSource};
+use crate::{
+    OlmErrorCode_OLM_BAD_MESSAGE_FORMAT, OlmErrorCode_OLM_INVALID_BASE64, OlmPkDecryption,
+    OlmPkEncryption, OlmPkSigning,
+};
+
+use zeroize::Zeroizing;
+
+/// A message encrypted by [`PkEncryption`], with all parts base64 encoded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PkMessage {
+    /// The AES-256-CBC encrypted plaintext.
+    pub ciphertext: String,
+    /// The truncated HMAC-SHA-256 of the message.
+    pub mac: String,
+    /// The public part of the ephemeral key the message was encrypted with.
+    pub ephemeral_key: String,
+}
+
+/// Encrypts messages for the owner of a public key.
+///
+/// The object is wiped when it's dropped.
+pub struct PkEncryption {
+    encryption: *mut OlmPkEncryption,
+    // Backs `encryption`, so it has to live as long as the object does
+    _memory: Box<[u64]>,
+}
+
+// `libolm` keeps no global state, so the object can move between threads.
+unsafe impl Send for PkEncryption {}
+
+impl PkEncryption {
+    /// Encrypts for the base64 Curve25519 `public_key`.
+    pub fn new(public_key: &str) -> Result<Self, OlmError> {
+        let mut memory = object_memory(unsafe { olm!(olm_pk_encryption_size()) });
+        let encryption = PkEncryption {
+            encryption: unsafe { olm!(olm_pk_encryption(memory.as_mut_ptr() as *mut c_void)) },
+            _memory: memory,
+        };
+
+        let result = unsafe {
+            olm!(olm_pk_encryption_set_recipient_key(
+                encryption.encryption,
+                public_key.as_ptr() as *const c_void,
+                public_key.len(),
+            ))
+        };
+        encryption.check(result)?;
+
+        Ok(encryption)
+    }
+
+    /// Encrypts `plaintext` with a new ephemeral key.
+    pub fn encrypt(&mut self, plaintext: &[u8], random: &mut impl RandomSource) -> PkMessage {
+        let ciphertext_length =
+            unsafe { olm!(olm_pk_ciphertext_length(self.encryption, plaintext.len())) };
+        let mut ciphertext = vec![0; ciphertext_length];
+        let mut mac = vec![0; unsafe { olm!(olm_pk_mac_length(self.encryption)) }];
+        let mut ephemeral_key = vec![0; unsafe { olm!(olm_pk_key_length()) }];
+        let random = random_bytes(random, unsafe {
+            olm!(olm_pk_encrypt_random_length(self.encryption))
+        });
+
+        let result = unsafe {
+            olm!(olm_pk_encrypt(
+                self.encryption,
+                plaintext.as_ptr() as *const c_void,
+                plaintext.len(),
+                ciphertext.as_mut_ptr() as *mut c_void,
+                ciphertext.len(),
+                mac.as_mut_ptr() as *mut c_void,
+                mac.len(),
+                ephemeral_key.as_mut_ptr() as *mut c_void,
+                ephemeral_key.len(),
+                random.as_ptr() as *const c_void,
+                random.len(),
+            ))
+        };
+        self.check(result).expect("libolm sized the message");
+
+        PkMessage {
+            ciphertext: into_string(ciphertext),
+            mac: into_string(mac),
+            ephemeral_key: into_string(ephemeral_key),
+        }
+    }
+
+    fn check(&self, result: usize) -> Result<usize, OlmError> {
+        if result == unsafe { olm!(olm_error()) } {
+            let code = unsafe { olm!(olm_pk_encryption_last_error_code(self.encryption)) };
+            Err(OlmError::new(code))
+        } else {
+            Ok(result)
+        }
+    }
+}
+
+impl Drop for PkEncryption {
+    fn drop(&mut self) {
+        unsafe { olm!(olm_clear_pk_encryption(self.encryption)) };
+    }
+}
+
+/// A Curve25519 key pair, which decrypts the messages [`PkEncryption`]
+/// encrypts for its public key.
+///
+/// The key pair is wiped when it's dropped.
+pub struct PkDecryption {
+    decryption: *mut OlmPkDecryption,
+    public_key: String,
+    // Backs `decryption`, so it has to live as long as the object does
+    _memory: Box<[u64]>,
+}
+
+// `libolm` keeps no global state, so the object can move between threads.
+unsafe impl Send for PkDecryption {}
+
+impl PkDecryption {
+    /// Length of the private key.
+    pub const PRIVATE_KEY_LENGTH: usize = 32;
+
+    /// Creates a new key pair.
+    pub fn new(random: &mut impl RandomSource) -> Self {
+        let private_key = random_bytes(random, Self::PRIVATE_KEY_LENGTH);
+        PkDecryption::from_private_key(&private_key).expect("the private key has the right length")
+    }
+
+    /// Restores the key pair from the private key returned by
+    /// [`PkDecryption::private_key`].
+    ///
+    /// Fails with `OLM_INPUT_BUFFER_TOO_SMALL` if the key is too short.
+    pub fn from_private_key(private_key: &[u8]) -> Result<Self, OlmError> {
+        let mut memory = object_memory(unsafe { olm!(olm_pk_decryption_size()) });
+        let mut decryption = PkDecryption {
+            decryption: unsafe { olm!(olm_pk_decryption(memory.as_mut_ptr() as *mut c_void)) },
+            public_key: String::new(),
+            _memory: memory,
+        };
+
+        let mut public_key = vec![0; unsafe { olm!(olm_pk_key_length()) }];
+        let result = unsafe {
+            olm!(olm_pk_key_from_private(
+                decryption.decryption,
+                public_key.as_mut_ptr() as *mut c_void,
+                public_key.len(),
+                private_key.as_ptr() as *const c_void,
+                private_key.len(),
+            ))
+        };
+        decryption.check(result)?;
+        decryption.public_key = into_string(public_key);
+
+        Ok(decryption)
+    }
+
+    /// The base64 public key to encrypt for.
+    pub fn public_key(&self) -> &str {
+        &self.public_key
+    }
+
+    /// The unencoded private key.
+    pub fn private_key(&self) -> Zeroizing<Vec<u8>> {
+        let mut private_key = Zeroizing::new(vec![0; unsafe { olm!(olm_pk_private_key_length()) }]);
+        let result = unsafe {
+            olm!(olm_pk_get_private_key(
+                self.decryption,
+                private_key.as_mut_ptr() as *mut c_void,
+                private_key.len(),
+            ))
+        };
+        self.check(result).expect("libolm sized the private key");
+
+        private_key
+    }
+
+    /// Decrypts `message`.
+    ///
+    /// Fails with `OLM_BAD_MESSAGE_MAC` if the message wasn't encrypted for
+    /// this key, or `OLM_BAD_MESSAGE_FORMAT` if the ciphertext isn't a whole
+    /// number of AES blocks. The MAC doesn't protect the ciphertext itself,
+    /// only the padding catches some modifications of it.
+    ///
+    /// The plaintext is wiped when it's dropped.
+    pub fn decrypt(&mut self, message: &PkMessage) -> Result<Zeroizing<Vec<u8>>, OlmError> {
+        // Decrypting decodes the ciphertext in place
+        let mut ciphertext = message.ciphertext.as_bytes().to_vec();
+        let max_length = unsafe {
+            olm!(olm_pk_max_plaintext_length(
+                self.decryption,
+                ciphertext.len()
+            ))
+        };
+        // Which is the case for impossible base64 lengths
+        if max_length == unsafe { olm!(olm_error()) } {
+            return Err(OlmError::new(OlmErrorCode_OLM_INVALID_BASE64));
+        }
+        // libolm's MAC doesn't cover the ciphertext, and decrypting a partial
+        // AES block writes past the end of the plaintext
+        let raw_length = ciphertext.len() * 3 / 4;
+        if raw_length == 0 || !raw_length.is_multiple_of(16) {
+            return Err(OlmError::new(OlmErrorCode_OLM_BAD_MESSAGE_FORMAT));
+        }
+        let mut plaintext = Zeroizing::new(vec![0; max_length]);
+
+        let length = unsafe {
+            olm!(olm_pk_decrypt(
+                self.decryption,
+                message.ephemeral_key.as_ptr() as *const c_void,
+                message.ephemeral_key.len(),
+                message.mac.as_ptr() as *const c_void,
+                message.mac.len(),
+                ciphertext.as_mut_ptr() as *mut c_void,
+                ciphertext.len(),
+                plaintext.as_mut_ptr() as *mut c_void,
+                plaintext.len(),
+            ))
+        };
+        plaintext.truncate(self.check(length)?);
+
+        Ok(plaintext)
+    }
+
+    fn check(&self, result: usize) -> Result<usize, OlmError> {
+        if result == unsafe { olm!(olm_error()) } {
+            let code = unsafe { olm!(olm_pk_decryption_last_error_code(self.decryption)) };
+            Err(OlmError::new(code))
+        } else {
+            Ok(result)
+        }
+    }
+}
+
+impl Drop for PkDecryption {
+    fn drop(&mut self) {
+        unsafe { olm!(olm_clear_pk_decryption(self.decryption)) };
+    }
+}
+
+/// An Ed25519 key pair derived from a seed, like a cross-signing key.
+///
+/// The key pair is wiped when it's dropped, the seed has to be kept by the
+/// caller.
+pub struct PkSigning {
+    signing: *mut OlmPkSigning,
+    public_key: String,
+    // Backs `signing`, so it has to live as long as the object does
+    _memory: Box<[u64]>,
+}
+
+// `libolm` keeps no global state, so the object can move between threads.
+unsafe impl Send for PkSigning {}
+
+impl PkSigning {
+    /// Length of the seed.
+    pub const SEED_LENGTH: usize = 32;
+
+    /// Derives the key pair from `seed`.
+    ///
+    /// Fails with `OLM_INPUT_BUFFER_TOO_SMALL` if the seed is too short.
+    pub fn from_seed(seed: &[u8]) -> Result<Self, OlmError> {
+        let mut memory = object_memory(unsafe { olm!(olm_pk_signing_size()) });
+        let mut signing = PkSigning {
+            signing: unsafe { olm!(olm_pk_signing(memory.as_mut_ptr() as *mut c_void)) },
+            public_key: String::new(),
+            _memory: memory,
+        };
+
+        let mut public_key = vec![0; unsafe { olm!(olm_pk_signing_public_key_length()) }];
+        let result = unsafe {
+            olm!(olm_pk_signing_key_from_seed(
+                signing.signing,
+                public_key.as_mut_ptr() as *mut c_void,
+                public_key.len(),
+                seed.as_ptr() as *const c_void,
+                seed.len(),
+            ))
+        };
+        signing.check(result)?;
+        signing.public_key = into_string(public_key);
+
+        Ok(signing)
+    }
+
+    /// The base64 Ed25519 public key.
+    pub fn public_key(&self) -> &str {
+        &self.public_key
+    }
+
+    /// Signs `message`, returning the base64 signature.
+    pub fn sign(&self, message: &[u8]) -> String {
+        let mut signature = vec![0; unsafe { olm!(olm_pk_signature_length()) }];
+        let result = unsafe {
+            olm!(olm_pk_sign(
+                self.signing,
+                message.as_ptr(),
+                message.len(),
+                signature.as_mut_ptr(),
+                signature.len(),
+            ))
+        };
+        self.check(result).expect("libolm sized the signature");
+
+        into_string(signature)
+    }
+
+    /// The underlying `OlmPkSigning`, for use with the raw bindings.
+    pub fn as_ptr(&self) -> *mut OlmPkSigning {
+        self.signing
+    }
+
+    fn check(&self, result: usize) -> Result<usize, OlmError> {
+        if result == unsafe { olm!(olm_error()) } {
+            let code = unsafe { olm!(olm_pk_signing_last_error_code(self.signing)) };
+            Err(OlmError::new(code))
+        } else {
+            Ok(result)
+        }
+    }
+}
+
+impl Drop for PkSigning {
+    fn drop(&mut self) {
+        unsafe { olm!(olm_clear_pk_signing(self.signing)) };
+    }
+}
@@ -0,0 +1,59 @@
+// Copyright 2020 Johannes Hayeß
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Randomness for the safe wrappers.
+//!
+//! `libolm` doesn't generate random numbers itself, every function that needs
+//! some takes them from the caller. The safe wrappers ask a [`RandomSource`]
+//! for them, so that this crate doesn't depend on an operating system:
+//!
+//! ```
+//! use olm_sys::random::RandomSource;
+//!
+//! struct Hardware;
+//!
+//! impl RandomSource for Hardware {
+//!     fn fill_bytes(&mut self, buffer: &mut [u8]) {
+//!         // Ask the hardware RNG, `getrandom`, `rand::rngs::OsRng`, …
+//!         # buffer.fill(4);
+//!     }
+//! }
+//! ```
+
+use alloc::vec::Vec;
+
+use zeroize::Zeroizing;
+
+/// A cryptographically secure source of random bytes.
+pub trait RandomSource {
+    /// Fills `buffer` with random bytes.
+    fn fill_bytes(&mut self, buffer: &mut [u8]);
+}
+
+impl<R: RandomSource + ?Sized> RandomSource for &mut R {
+    fn fill_bytes(&mut self, buffer: &mut [u8]) {
+        (**self).fill_bytes(buffer)
+    }
+}
+
+/// `length` bytes from `random`, which are wiped when they're dropped as
+/// they often become private keys.
+pub(crate) fn random_bytes(
+    random: &mut (impl RandomSource + ?Sized),
+    length: usize,
+) -> Zeroizing<Vec<u8>> {
+    let mut bytes = Zeroizing::new(alloc::vec![0; length]);
+    random.fill_bytes(&mut bytes);
+    bytes
+}
@@ -0,0 +1,299 @@
+// Copyright 2020 Johannes Hayeß
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Secret storage with the `m.secret_storage.v1.aes-hmac-sha2` algorithm.
+//!
+//! Secrets like the cross-signing keys are stored in the user's account data,
+//! encrypted with a [`SecretStorageKey`]. Each secret gets its own AES and
+//! HMAC keys, derived with HKDF-SHA-256 from the storage key and the secret's
+//! name:
+//!
+//! ```no_run
+//! use olm_sys::secret_storage::SecretStorageKey;
+//! # use olm_sys::random::RandomSource;
+//! # struct Random;
+//! # impl RandomSource for Random {
+//! #     fn fill_bytes(&mut self, buffer: &mut [u8]) {}
+//! # }
+//! # fn main() -> Result<(), olm_sys::secret_storage::SecretStorageError> {
+//!
+//! let key = SecretStorageKey::from_passphrase("correct horse battery staple", "<salt>", 500_000)?;
+//! let encrypted = key.encrypt_secret("m.cross_signing.master", b"<seed>", &mut Random);
+//!
+//! assert_eq!(*key.decrypt_secret("m.cross_signing.master", &encrypted)?, b"<seed>");
+//! # Ok(())
+//! # }
+//! ```
+
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use core::fmt;
+
+use serde_json::{json, Value};
+use zeroize::Zeroizing;
+
+use crate::crypto::{self, AES_BLOCK_LENGTH, SHA256_LENGTH};
+use crate::random::RandomSource;
+
+/// The secret storage algorithm of this module, as it appears in the key's
+/// description.
+pub const SECRET_STORAGE_ALGORITHM: &str = "m.secret_storage.v1.aes-hmac-sha2";
+
+/// The passphrase algorithm of [`SecretStorageKey::from_passphrase`].
+pub const PASSPHRASE_ALGORITHM: &str = "m.pbkdf2";
+
+/// Length of a secret storage key.
+pub const KEY_LENGTH: usize = 32;
+
+/// A secret as it's stored in the account data, with all parts base64
+/// encoded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EncryptedSecret {
+    /// The AES-CTR initialisation vector.
+    pub iv: String,
+    /// The AES-256-CTR encrypted secret.
+    pub ciphertext: String,
+    /// The HMAC-SHA-256 of the ciphertext.
+    pub mac: String,
+}
+
+impl EncryptedSecret {
+    /// The JSON object stored under the key's ID.
+    pub fn to_json(&self) -> Value {
+        json!({
+            "iv": self.iv,
+            "ciphertext": self.ciphertext,
+            "mac": self.mac,
+        })
+    }
+
+    /// Reads the JSON object stored under the key's ID.
+    pub fn from_json(secret: &Value) -> Result<Self, SecretStorageError> {
+        let field = |name: &str| {
+            secret
+                .get(name)
+                .and_then(Value::as_str)
+                .map(str::to_owned)
+                .ok_or(SecretStorageError::InvalidFormat)
+        };
+
+        Ok(EncryptedSecret {
+            iv: field("iv")?,
+            ciphertext: field("ciphertext")?,
+            mac: field("mac")?,
+        })
+    }
+}
+
+/// The `iv` and `mac` of a key's description, which tell whether a key is
+/// the one the description belongs to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyCheck {
+    /// The initialisation vector, base64 encoded.
+    pub iv: String,
+    /// The MAC of 32 zero bytes encrypted as the secret `""`, base64 encoded.
+    pub mac: String,
+}
+
+/// Why a secret couldn't be decrypted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SecretStorageError {
+    /// A part of the secret isn't base64 or the IV has the wrong length.
+    InvalidFormat,
+    /// The MAC doesn't match, usually because the key is wrong.
+    BadMac,
+    /// The recovery key isn't base58, has the wrong length, prefix or parity.
+    InvalidRecoveryKey,
+    /// The key's passphrase is described with zero PBKDF2 iterations.
+    InvalidIterations,
+}
+
+impl fmt::Display for SecretStorageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SecretStorageError::InvalidFormat => write!(f, "malformed encrypted secret"),
+            SecretStorageError::BadMac => write!(f, "wrong key or corrupted secret"),
+            SecretStorageError::InvalidRecoveryKey => write!(f, "invalid recovery key"),
+            SecretStorageError::InvalidIterations => write!(f, "zero PBKDF2 iterations"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SecretStorageError {}
+
+/// A key which encrypts secrets in the account data.
+///
+/// The key is wiped when it's dropped.
+pub struct SecretStorageKey {
+    key: Zeroizing<[u8; KEY_LENGTH]>,
+}
+
+impl SecretStorageKey {
+    /// Creates a new random key.
+    pub fn new(random: &mut impl RandomSource) -> Self {
+        let mut key = Zeroizing::new([0; KEY_LENGTH]);
+        random.fill_bytes(key.as_mut());
+
+        SecretStorageKey { key }
+    }
+
+    /// Uses `key` as it is.
+    pub fn from_bytes(key: &[u8; KEY_LENGTH]) -> Self {
+        SecretStorageKey {
+            key: Zeroizing::new(*key),
+        }
+    }
+
+    /// Derives the key from a passphrase with PBKDF2-HMAC-SHA-512, using the
+    /// `salt` and `iterations` of the key description's `passphrase`.
+    pub fn from_passphrase(
+        passphrase: &str,
+        salt: &str,
+        iterations: u32,
+    ) -> Result<Self, SecretStorageError> {
+        if iterations == 0 {
+            return Err(SecretStorageError::InvalidIterations);
+        }
+
+        let mut key = Zeroizing::new([0; KEY_LENGTH]);
+        crypto::pbkdf2(
+            crypto::hmac_sha512,
+            passphrase.as_bytes(),
+            salt.as_bytes(),
+            iterations,
+            key.as_mut(),
+        );
+
+        Ok(SecretStorageKey { key })
+    }
+
+    /// Decodes the base58 recovery key the user wrote down, ignoring
+    /// whitespace.
+    pub fn from_base58(recovery_key: &str) -> Result<Self, SecretStorageError> {
+        let key = crate::base58::decode_recovery_key(recovery_key)
+            .ok_or(SecretStorageError::InvalidRecoveryKey)?;
+
+        Ok(SecretStorageKey { key })
+    }
+
+    /// Encodes the key as a recovery key for the user, in groups of four
+    /// characters.
+    pub fn to_base58(&self) -> String {
+        crate::base58::encode_recovery_key(&self.key)
+    }
+
+    /// Encrypts the secret `plaintext`, stored under `name`.
+    pub fn encrypt_secret(
+        &self,
+        name: &str,
+        plaintext: &[u8],
+        random: &mut impl RandomSource,
+    ) -> EncryptedSecret {
+        let mut iv = [0; AES_BLOCK_LENGTH];
+        random.fill_bytes(&mut iv);
+        // Leaves room for 2^63 blocks before the counter wraps, which some
+        // implementations of AES-CTR don't handle
+        iv[8] &= 0x7f;
+
+        self.encrypt_with_iv(name, plaintext, &iv)
+    }
+
+    /// Decrypts the secret stored under `name`.
+    pub fn decrypt_secret(
+        &self,
+        name: &str,
+        secret: &EncryptedSecret,
+    ) -> Result<Zeroizing<Vec<u8>>, SecretStorageError> {
+        let decode =
+            |input: &str| crate::base64::decode(input).ok_or(SecretStorageError::InvalidFormat);
+        let iv: [u8; AES_BLOCK_LENGTH] = decode(&secret.iv)?
+            .try_into()
+            .map_err(|_| SecretStorageError::InvalidFormat)?;
+        let mut plaintext = Zeroizing::new(decode(&secret.ciphertext)?);
+        let mac = decode(&secret.mac)?;
+
+        let (aes_key, hmac_key) = self.derive_keys(name);
+        if !crypto::mac_matches(&crypto::hmac_sha256(&hmac_key[..], &plaintext), &mac) {
+            return Err(SecretStorageError::BadMac);
+        }
+        crypto::aes256_ctr(&aes_key, &iv, &mut plaintext);
+
+        Ok(plaintext)
+    }
+
+    /// Creates the `iv` and `mac` for the description of this key.
+    pub fn key_check(&self, random: &mut impl RandomSource) -> KeyCheck {
+        let check = self.encrypt_secret("", &[0; KEY_LENGTH], random);
+        KeyCheck {
+            iv: check.iv,
+            mac: check.mac,
+        }
+    }
+
+    /// Checks that this is the key a description's `iv` and `mac` belong to.
+    ///
+    /// Fails with [`SecretStorageError::BadMac`] if it isn't.
+    pub fn check_key(&self, check: &KeyCheck) -> Result<(), SecretStorageError> {
+        let iv: [u8; AES_BLOCK_LENGTH] = crate::base64::decode(&check.iv)
+            .and_then(|iv| iv.try_into().ok())
+            .ok_or(SecretStorageError::InvalidFormat)?;
+        let mac = crate::base64::decode(&check.mac).ok_or(SecretStorageError::InvalidFormat)?;
+
+        let expected = self.encrypt_with_iv("", &[0; KEY_LENGTH], &iv);
+        let expected = crate::base64::decode(&expected.mac).unwrap();
+        if crypto::mac_matches(&expected, &mac) {
+            Ok(())
+        } else {
+            Err(SecretStorageError::BadMac)
+        }
+    }
+
+    fn encrypt_with_iv(
+        &self,
+        name: &str,
+        plaintext: &[u8],
+        iv: &[u8; AES_BLOCK_LENGTH],
+    ) -> EncryptedSecret {
+        let (aes_key, hmac_key) = self.derive_keys(name);
+        let mut ciphertext = plaintext.to_vec();
+        crypto::aes256_ctr(&aes_key, iv, &mut ciphertext);
+        let mac = crypto::hmac_sha256(&hmac_key[..], &ciphertext);
+
+        EncryptedSecret {
+            iv: crate::base64::encode_padded(iv),
+            ciphertext: crate::base64::encode_padded(&ciphertext),
+            mac: crate::base64::encode_padded(&mac),
+        }
+    }
+
+    /// The AES and HMAC keys of the secret `name`.
+    fn derive_keys(&self, name: &str) -> (Zeroizing<[u8; 32]>, Zeroizing<[u8; 32]>) {
+        let mut keys = Zeroizing::new([0; 64]);
+        crypto::hkdf_sha256(
+            &self.key[..],
+            &[0; SHA256_LENGTH],
+            name.as_bytes(),
+            keys.as_mut(),
+        );
+
+        let (aes_key, hmac_key) = keys.split_at(32);
+        (
+            Zeroizing::new(aes_key.try_into().unwrap()),
+            Zeroizing::new(hmac_key.try_into().unwrap()),
+        )
+    }
+}
@@ -0,0 +1,259 @@
+// Copyright 2020 Johannes Hayeß
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Signing and verifying JSON objects the way Matrix does.
+//!
+//! Device keys, one-time keys and cross-signing keys are signed over their
+//! canonical JSON, without the `signatures` and `unsigned` members. The
+//! signature is then added to `signatures`, under the user ID and the
+//! `ed25519:` key ID:
+//!
+//! ```no_run
+//! use olm_sys::account::Account;
+//! use olm_sys::signing::{sign_json, verify_json};
+//! # use olm_sys::random::RandomSource;
+//! # struct Random;
+//! # impl RandomSource for Random {
+//! #     fn fill_bytes(&mut self, buffer: &mut [u8]) {}
+//! # }
+//! # fn main() -> Result<(), olm_sys::signing::SignatureError> {
+//! # let ed25519_key = "";
+//!
+//! let account = Account::new(&mut Random);
+//! let mut keys = serde_json::json!({
+//!     "user_id": "@alice:example.org",
+//!     "device_id": "JLAFKJWSCS",
+//! });
+//! sign_json(&account, "@alice:example.org", "JLAFKJWSCS", &mut keys)?;
+//!
+//! verify_json(ed25519_key, "@alice:example.org", "JLAFKJWSCS", &keys)?;
+//! # Ok(())
+//! # }
+//! ```
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use core::ffi::c_void;
+use core::fmt;
+
+use serde_json::{Map, Value};
+
+use crate::account::Account;
+use crate::backend::olm;
+use crate::error::OlmError;
+use crate::memory::object_memory;
+use crate::pk::PkSigning;
+
+/// The largest integer canonical JSON allows, 2^53 - 1. Its negation is the
+/// smallest.
+const MAX_SAFE_INTEGER: i64 = (1 << 53) - 1;
+
+/// Signs with an Ed25519 key, for [`sign_json`].
+pub trait Signer {
+    /// Signs `message`, returning the base64 signature.
+    fn sign(&self, message: &[u8]) -> String;
+}
+
+impl Signer for Account {
+    fn sign(&self, message: &[u8]) -> String {
+        Account::sign(self, message)
+    }
+}
+
+impl Signer for PkSigning {
+    fn sign(&self, message: &[u8]) -> String {
+        PkSigning::sign(self, message)
+    }
+}
+
+/// Why a JSON object couldn't be signed or verified.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SignatureError {
+    /// The value isn't an object, or its `signatures` aren't objects.
+    InvalidJson,
+    /// The value has no canonical JSON, because it contains a float or an
+    /// integer outside of ±(2^53 - 1).
+    NotCanonical,
+    /// The object isn't signed by the key.
+    MissingSignature,
+    /// `libolm` rejected the signature: `OLM_BAD_MESSAGE_MAC` if it doesn't
+    /// match, `OLM_INVALID_BASE64` if the public key or signature is
+    /// malformed.
+    Olm(OlmError),
+}
+
+impl From<OlmError> for SignatureError {
+    fn from(error: OlmError) -> Self {
+        SignatureError::Olm(error)
+    }
+}
+
+impl fmt::Display for SignatureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SignatureError::InvalidJson => write!(f, "not a signable JSON object"),
+            SignatureError::NotCanonical => write!(f, "JSON without a canonical form"),
+            SignatureError::MissingSignature => write!(f, "no signature by the key"),
+            SignatureError::Olm(error) => error.fmt(f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SignatureError {}
+
+/// The canonical JSON of `value`: object members sorted by key, no
+/// whitespace, and no escapes beyond those JSON requires.
+///
+/// The members are sorted here rather than relying on `serde_json`'s map,
+/// which keeps insertion order if another crate enables `preserve_order`.
+pub fn canonical_json(value: &Value) -> Result<String, SignatureError> {
+    let mut output = String::new();
+    write_canonical(value, &mut output)?;
+
+    Ok(output)
+}
+
+fn write_canonical(value: &Value, output: &mut String) -> Result<(), SignatureError> {
+    match value {
+        Value::Null | Value::Bool(_) => output.push_str(&value.to_string()),
+        Value::Number(number) => match number.as_i64() {
+            Some(number) if (-MAX_SAFE_INTEGER..=MAX_SAFE_INTEGER).contains(&number) => {
+                output.push_str(&number.to_string())
+            }
+            _ => return Err(SignatureError::NotCanonical),
+        },
+        Value::String(string) => output.push_str(&Value::from(string.as_str()).to_string()),
+        Value::Array(values) => {
+            output.push('[');
+            for (i, value) in values.iter().enumerate() {
+                if i > 0 {
+                    output.push(',');
+                }
+                write_canonical(value, output)?;
+            }
+            output.push(']');
+        }
+        Value::Object(members) => {
+            let mut members: Vec<_> = members.iter().collect();
+            // UTF-8 byte order, which is code point order
+            members.sort_by_key(|(key, _)| *key);
+
+            output.push('{');
+            for (i, (key, value)) in members.into_iter().enumerate() {
+                if i > 0 {
+                    output.push(',');
+                }
+                output.push_str(&Value::from(key.as_str()).to_string());
+                output.push(':');
+                write_canonical(value, output)?;
+            }
+            output.push('}');
+        }
+    }
+
+    Ok(())
+}
+
+/// The canonical JSON an object's signatures are made over, without its
+/// `signatures` and `unsigned` members.
+fn signed_json(object: &Map<String, Value>) -> Result<String, SignatureError> {
+    let mut object = object.clone();
+    object.remove("signatures");
+    object.remove("unsigned");
+
+    canonical_json(&Value::Object(object))
+}
+
+/// Signs the JSON object `value` with `signer`, adding the signature to its
+/// `signatures` as `signatures[user_id]["ed25519:" + key_id]`.
+///
+/// `key_id` is the device ID for device keys, and the public key for
+/// cross-signing keys. Other signatures are kept.
+pub fn sign_json(
+    signer: &impl Signer,
+    user_id: &str,
+    key_id: &str,
+    value: &mut Value,
+) -> Result<(), SignatureError> {
+    let object = value.as_object_mut().ok_or(SignatureError::InvalidJson)?;
+    let signature = signer.sign(signed_json(object)?.as_bytes());
+
+    let signatures = object
+        .entry("signatures")
+        .or_insert_with(|| Value::Object(Map::new()))
+        .as_object_mut()
+        .ok_or(SignatureError::InvalidJson)?;
+    signatures
+        .entry(user_id)
+        .or_insert_with(|| Value::Object(Map::new()))
+        .as_object_mut()
+        .ok_or(SignatureError::InvalidJson)?
+        .insert(format!("ed25519:{key_id}"), Value::String(signature));
+
+    Ok(())
+}
+
+/// Verifies the signature `sign_json` added to `value` for `user_id` and
+/// `key_id`, with the base64 Ed25519 `public_key`.
+pub fn verify_json(
+    public_key: &str,
+    user_id: &str,
+    key_id: &str,
+    value: &Value,
+) -> Result<(), SignatureError> {
+    let object = value.as_object().ok_or(SignatureError::InvalidJson)?;
+    let signature = object
+        .get("signatures")
+        .and_then(|signatures| signatures.get(user_id))
+        .and_then(|signatures| signatures.get(format!("ed25519:{key_id}")))
+        .and_then(Value::as_str)
+        .ok_or(SignatureError::MissingSignature)?;
+
+    ed25519_verify(public_key, signed_json(object)?.as_bytes(), signature)?;
+
+    Ok(())
+}
+
+/// Verifies the base64 Ed25519 `signature` of `message` with `olm_utility`.
+fn ed25519_verify(public_key: &str, message: &[u8], signature: &str) -> Result<(), OlmError> {
+    let mut memory = object_memory(unsafe { olm!(olm_utility_size()) });
+    let utility = unsafe { olm!(olm_utility(memory.as_mut_ptr() as *mut c_void)) };
+    // libolm decodes the signature in place
+    let mut signature = signature.as_bytes().to_vec();
+
+    let result = unsafe {
+        olm!(olm_ed25519_verify(
+            utility,
+            public_key.as_ptr() as *const c_void,
+            public_key.len(),
+            message.as_ptr() as *const c_void,
+            message.len(),
+            signature.as_mut_ptr() as *mut c_void,
+            signature.len(),
+        ))
+    };
+    let result = if result == unsafe { olm!(olm_error()) } {
+        Err(OlmError::new(unsafe {
+            olm!(olm_utility_last_error_code(utility))
+        }))
+    } else {
+        Ok(())
+    };
+    unsafe { olm!(olm_clear_utility(utility)) };
+
+    result
+}
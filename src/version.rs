@@ -0,0 +1,272 @@
+// Copyright 2020 Johannes Hayeß
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Runtime checks of the `libolm` version these bindings are linked against.
+//!
+//! When linking dynamically, the `libolm` found at runtime may be older than
+//! the one the bindings were generated from. [`check_compatible`] tells which
+//! parts of the bindings the loaded library can't back.
+
+use alloc::vec::Vec;
+
+use core::fmt;
+
+use crate::backend::olm;
+
+/// Returns the `(major, minor, patch)` version of the linked `libolm`.
+pub fn library_version() -> (u8, u8, u8) {
+    let (mut major, mut minor, mut patch) = (0, 0, 0);
+    unsafe { olm!(olm_get_library_version(&mut major, &mut minor, &mut patch)) };
+
+    (major, minor, patch)
+}
+
+/// Functionality of `libolm` that was added after the 3.0 release.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Feature {
+    /// The 3.x C API as a whole, which other major versions don't provide.
+    Api,
+    /// The internal `_olm_*` functions behind the Megolm message decoding,
+    /// the pickle encoding and the Matrix key formats of the safe wrappers.
+    /// Libraries built with `libolm`'s Makefile only export `olm_*`.
+    InternalFunctions,
+    /// Fallback keys, see `olm_account_generate_fallback_key(…)`.
+    FallbackKeys,
+    /// `olm_sas_calculate_mac_fixed_base64(…)`.
+    SasFixedBase64Mac,
+    /// The `olm_*_last_error_code(…)` functions.
+    ErrorCodes,
+    /// `olm_account_unpublished_fallback_key(…)` and
+    /// `olm_account_forget_old_fallback_key(…)`.
+    FallbackKeyRotation,
+}
+
+impl Feature {
+    /// All features, in the order they were added to `libolm`.
+    pub const ALL: [Feature; 6] = [
+        Feature::Api,
+        Feature::InternalFunctions,
+        Feature::FallbackKeys,
+        Feature::SasFixedBase64Mac,
+        Feature::ErrorCodes,
+        Feature::FallbackKeyRotation,
+    ];
+
+    /// The first `libolm` release which provides this feature.
+    pub fn since(self) -> (u8, u8, u8) {
+        match self {
+            Feature::Api | Feature::InternalFunctions => (3, 0, 0),
+            Feature::FallbackKeys => (3, 2, 0),
+            Feature::SasFixedBase64Mac => (3, 2, 2),
+            Feature::ErrorCodes => (3, 2, 5),
+            Feature::FallbackKeyRotation => (3, 2, 7),
+        }
+    }
+
+    /// Whether a `libolm` of the given version provides this feature.
+    pub fn is_supported_by(self, version: (u8, u8, u8)) -> bool {
+        version.0 == 3 && version >= self.since()
+    }
+
+    /// Whether all functions of this feature could be loaded from `library`.
+    #[cfg(feature = "runtime-dlopen")]
+    pub fn is_loaded_from(self, library: &crate::backend::OlmLibrary) -> bool {
+        match self {
+            Feature::Api => library.olm_get_library_version.is_ok(),
+            Feature::InternalFunctions => {
+                // Only the Matrix key formats use the symmetric primitives
+                let crypto = !cfg!(feature = "matrix")
+                    || (library._olm_crypto_aes_encrypt_cbc.is_ok()
+                        && library._olm_crypto_hmac_sha256.is_ok()
+                        && library._olm_crypto_hkdf_sha256.is_ok());
+
+                library._olm_decode_group_message.is_ok()
+                    && library._olm_enc_output_length.is_ok()
+                    && library._olm_enc_output_pos.is_ok()
+                    && library._olm_enc_output.is_ok()
+                    && library._olm_enc_input.is_ok()
+                    && crypto
+            }
+            Feature::FallbackKeys => {
+                library
+                    .olm_account_generate_fallback_key_random_length
+                    .is_ok()
+                    && library.olm_account_generate_fallback_key.is_ok()
+                    && library.olm_account_fallback_key_length.is_ok()
+                    && library.olm_account_fallback_key.is_ok()
+            }
+            Feature::SasFixedBase64Mac => library.olm_sas_calculate_mac_fixed_base64.is_ok(),
+            Feature::ErrorCodes => {
+                library.olm_account_last_error_code.is_ok()
+                    && library.olm_session_last_error_code.is_ok()
+                    && library.olm_utility_last_error_code.is_ok()
+                    && library.olm_inbound_group_session_last_error_code.is_ok()
+                    && library.olm_outbound_group_session_last_error_code.is_ok()
+                    && library.olm_sas_last_error_code.is_ok()
+                    && library.olm_pk_encryption_last_error_code.is_ok()
+                    && library.olm_pk_decryption_last_error_code.is_ok()
+                    && library.olm_pk_signing_last_error_code.is_ok()
+            }
+            Feature::FallbackKeyRotation => {
+                library.olm_account_unpublished_fallback_key_length.is_ok()
+                    && library.olm_account_unpublished_fallback_key.is_ok()
+                    && library.olm_account_forget_old_fallback_key.is_ok()
+            }
+        }
+    }
+}
+
+impl fmt::Display for Feature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Feature::Api => "libolm 3 API",
+            // Which libolm 3 has them depends on how it was built
+            Feature::InternalFunctions => {
+                return write!(f, "internal functions (exported by CMake builds)");
+            }
+            Feature::FallbackKeys => "fallback keys",
+            Feature::SasFixedBase64Mac => "fixed base64 SAS MAC",
+            Feature::ErrorCodes => "error codes",
+            Feature::FallbackKeyRotation => "fallback key rotation",
+        };
+        let (major, minor, patch) = self.since();
+
+        write!(f, "{} (since {}.{}.{})", name, major, minor, patch)
+    }
+}
+
+/// The linked `libolm` doesn't provide everything these bindings expose.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IncompatibleLibrary {
+    /// Version of the linked `libolm`.
+    pub version: (u8, u8, u8),
+    /// Features the linked `libolm` lacks.
+    pub missing: Vec<Feature>,
+}
+
+impl fmt::Display for IncompatibleLibrary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (major, minor, patch) = self.version;
+        write!(f, "libolm {}.{}.{} is missing:", major, minor, patch)?;
+        for (i, feature) in self.missing.iter().enumerate() {
+            let separator = if i == 0 { " " } else { ", " };
+            write!(f, "{}{}", separator, feature)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for IncompatibleLibrary {}
+
+/// Checks that the linked `libolm` provides everything these bindings expose.
+///
+/// Calling a function the library doesn't provide is undefined behaviour, so
+/// applications linking `libolm` dynamically should call this once at start-up.
+#[cfg(not(feature = "runtime-dlopen"))]
+pub fn check_compatible() -> Result<(), IncompatibleLibrary> {
+    check_version(library_version())
+}
+
+/// Checks that the installed `libolm` provides everything these bindings
+/// expose.
+///
+/// Besides the library's version, this checks which functions could actually
+/// be loaded from it.
+///
+/// # Panics
+///
+/// If no library has been [installed](crate::backend::install).
+#[cfg(feature = "runtime-dlopen")]
+pub fn check_compatible() -> Result<(), IncompatibleLibrary> {
+    let library = crate::backend::library();
+    let version = if library.olm_get_library_version.is_ok() {
+        library_version()
+    } else {
+        (0, 0, 0)
+    };
+
+    check_features(version, |feature| {
+        feature.is_supported_by(version) && feature.is_loaded_from(library)
+    })
+}
+
+#[cfg(any(test, not(feature = "runtime-dlopen")))]
+fn check_version(version: (u8, u8, u8)) -> Result<(), IncompatibleLibrary> {
+    check_features(version, |feature| feature.is_supported_by(version))
+}
+
+fn check_features(
+    version: (u8, u8, u8),
+    provides: impl Fn(Feature) -> bool,
+) -> Result<(), IncompatibleLibrary> {
+    let missing: Vec<Feature> = Feature::ALL
+        .iter()
+        .copied()
+        .filter(|&feature| !provides(feature))
+        .collect();
+
+    if missing.is_empty() {
+        Ok(())
+    } else {
+        Err(IncompatibleLibrary { version, missing })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::string::ToString;
+    use alloc::vec;
+
+    #[test]
+    fn older_releases_lack_newer_features() {
+        let error = check_version((3, 2, 2)).unwrap_err();
+        assert_eq!(
+            error.missing,
+            vec![Feature::ErrorCodes, Feature::FallbackKeyRotation]
+        );
+        assert_eq!(
+            error.to_string(),
+            "libolm 3.2.2 is missing: error codes (since 3.2.5), \
+             fallback key rotation (since 3.2.7)"
+        );
+    }
+
+    #[test]
+    fn internal_functions_are_named_by_build() {
+        assert_eq!(
+            IncompatibleLibrary {
+                version: (3, 2, 7),
+                missing: vec![Feature::InternalFunctions],
+            }
+            .to_string(),
+            "libolm 3.2.7 is missing: internal functions (exported by CMake builds)"
+        );
+    }
+
+    #[test]
+    fn other_major_versions_are_incompatible() {
+        assert_eq!(check_version((4, 0, 0)).unwrap_err().missing, Feature::ALL);
+        assert_eq!(check_version((2, 9, 9)).unwrap_err().missing, Feature::ALL);
+    }
+
+    #[test]
+    fn current_release_is_compatible() {
+        assert_eq!(check_version((3, 2, 7)), Ok(()));
+        assert_eq!(check_version((3, 3, 0)), Ok(()));
+    }
+}
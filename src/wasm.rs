@@ -0,0 +1,55 @@
+// Copyright 2020 Johannes Hayeß
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! `malloc()` and `free()` for `libolm` on `wasm32-unknown-unknown`, where
+//! there is no libc to provide them.
+//!
+//! `libolm` itself works on caller-provided memory. Only the AES-CCM functions
+//! it vendors allocate, and they are never called, but still need to link.
+
+use alloc::alloc::{alloc, dealloc, Layout};
+
+/// Space in front of each allocation recording its size, big enough to keep
+/// the returned pointer aligned for any C type.
+const HEADER: usize = 16;
+
+#[no_mangle]
+unsafe extern "C" fn malloc(size: usize) -> *mut u8 {
+    let layout = match size
+        .checked_add(HEADER)
+        .and_then(|size| Layout::from_size_align(size, HEADER).ok())
+    {
+        Some(layout) => layout,
+        None => return core::ptr::null_mut(),
+    };
+
+    let base = alloc(layout);
+    if base.is_null() {
+        return base;
+    }
+    (base as *mut usize).write(layout.size());
+
+    base.add(HEADER)
+}
+
+#[no_mangle]
+unsafe extern "C" fn free(ptr: *mut u8) {
+    if ptr.is_null() {
+        return;
+    }
+
+    let base = ptr.sub(HEADER);
+    let size = (base as *const usize).read();
+    dealloc(base, Layout::from_size_align_unchecked(size, HEADER));
+}
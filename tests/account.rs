@@ -0,0 +1,169 @@
+// Copyright 2020 Johannes Hayeß
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The safe `Account` wrapper.
+
+#![cfg(not(feature = "runtime-dlopen"))]
+
+mod common;
+
+use common::MockRandom;
+use olm_sys::account::Account;
+use olm_sys::*;
+
+use std::ffi::c_void;
+
+const PICKLE_KEY: &[u8] = b"secret_key";
+
+#[test]
+fn account_round_trips_through_pickle() {
+    let mut random = MockRandom::new(b'P', 0);
+    let mut account = Account::new(&mut random);
+    account.generate_one_time_keys(5, &mut random);
+
+    let pickle = account.pickle(PICKLE_KEY);
+    let unpickled = Account::unpickle(PICKLE_KEY, &pickle).unwrap();
+
+    assert_eq!(unpickled.identity_keys(), account.identity_keys());
+    assert_eq!(unpickled.one_time_keys(), account.one_time_keys());
+    assert_eq!(unpickled.pickle(PICKLE_KEY), pickle);
+}
+
+#[test]
+fn unpickling_with_the_wrong_key_fails() {
+    let account = Account::new(&mut MockRandom::new(b'P', 0));
+    let pickle = account.pickle(PICKLE_KEY);
+
+    let error = Account::unpickle(b"wrong_key", &pickle).err().unwrap();
+    assert_eq!(error.code(), OlmErrorCode_OLM_BAD_ACCOUNT_KEY);
+    assert_eq!(error.name(), "BAD_ACCOUNT_KEY");
+    assert_eq!(error.to_string(), "libolm error BAD_ACCOUNT_KEY");
+}
+
+#[test]
+fn signatures_verify() {
+    let account = Account::new(&mut MockRandom::new(b'S', 0));
+    let message = b"Hello, World";
+    let signature = account.sign(message);
+
+    // The Ed25519 key starts 71 bytes into the identity keys
+    let identity_keys = account.identity_keys();
+    let ed25519 = &identity_keys.as_bytes()[71..114];
+
+    let mut memory = common::alloc(unsafe { olm_utility_size() });
+    let utility = unsafe { olm_utility(common::memory(&mut memory)) };
+    let mut signature = signature.into_bytes();
+    let result = unsafe {
+        olm_ed25519_verify(
+            utility,
+            ed25519.as_ptr() as *const c_void,
+            ed25519.len(),
+            message.as_ptr() as *const c_void,
+            message.len(),
+            signature.as_mut_ptr() as *mut c_void,
+            signature.len(),
+        )
+    };
+    assert_ne!(result, unsafe { olm_error() });
+}
+
+#[test]
+fn published_keys_are_no_longer_listed() {
+    let mut random = MockRandom::new(b'O', 0);
+    let mut account = Account::new(&mut random);
+    assert_eq!(account.one_time_keys(), "{\"curve25519\":{}}");
+
+    account.generate_one_time_keys(2, &mut random);
+    account.generate_fallback_key(&mut random);
+    assert_ne!(account.one_time_keys(), "{\"curve25519\":{}}");
+    assert_ne!(account.unpublished_fallback_key(), "{\"curve25519\":{}}");
+
+    account.mark_keys_as_published();
+    assert_eq!(account.one_time_keys(), "{\"curve25519\":{}}");
+    assert_eq!(account.unpublished_fallback_key(), "{\"curve25519\":{}}");
+    assert!(account.max_number_of_one_time_keys() > 0);
+}
+
+#[cfg(feature = "matrix")]
+#[test]
+fn device_keys_are_signed() {
+    use olm_sys::signing::verify_json;
+
+    let account = Account::new(&mut MockRandom::new(b'D', 0));
+    let identity_keys: serde_json::Value = serde_json::from_str(&account.identity_keys()).unwrap();
+    let ed25519 = identity_keys["ed25519"].as_str().unwrap();
+
+    let device_keys = account.device_keys("@alice:example.org", "DEVICE");
+    assert_eq!(device_keys["user_id"], "@alice:example.org");
+    assert_eq!(device_keys["device_id"], "DEVICE");
+    assert_eq!(
+        device_keys["algorithms"],
+        serde_json::json!(["m.olm.v1.curve25519-aes-sha2", "m.megolm.v1.aes-sha2"])
+    );
+    assert_eq!(device_keys["keys"]["ed25519:DEVICE"], ed25519);
+    assert_eq!(
+        device_keys["keys"]["curve25519:DEVICE"],
+        identity_keys["curve25519"]
+    );
+    assert_eq!(
+        verify_json(ed25519, "@alice:example.org", "DEVICE", &device_keys),
+        Ok(())
+    );
+}
+
+#[cfg(feature = "matrix")]
+#[test]
+fn one_time_keys_are_signed_until_published() {
+    use olm_sys::signing::verify_json;
+
+    let mut random = MockRandom::new(b'K', 0);
+    let mut account = Account::new(&mut random);
+    let identity_keys: serde_json::Value = serde_json::from_str(&account.identity_keys()).unwrap();
+    let ed25519 = identity_keys["ed25519"].as_str().unwrap();
+
+    account.generate_one_time_keys(2, &mut random);
+    account.generate_fallback_key(&mut random);
+    let upload = account.signed_one_time_keys("@alice:example.org", "DEVICE");
+
+    let one_time_keys = upload["one_time_keys"].as_object().unwrap();
+    assert_eq!(one_time_keys.len(), 2);
+    for (key_id, key) in one_time_keys {
+        assert!(key_id.starts_with("signed_curve25519:"));
+        assert!(key["key"].is_string());
+        assert!(key.get("fallback").is_none());
+        assert_eq!(
+            verify_json(ed25519, "@alice:example.org", "DEVICE", key),
+            Ok(())
+        );
+    }
+    let fallback_keys = upload["fallback_keys"].as_object().unwrap();
+    assert_eq!(fallback_keys.len(), 1);
+    let fallback_key = fallback_keys.values().next().unwrap();
+    assert_eq!(fallback_key["fallback"], true);
+    assert_eq!(
+        verify_json(ed25519, "@alice:example.org", "DEVICE", fallback_key),
+        Ok(())
+    );
+
+    // A failed upload is retried with the same keys
+    assert_eq!(
+        account.signed_one_time_keys("@alice:example.org", "DEVICE"),
+        upload
+    );
+    account.mark_keys_as_published();
+    assert_eq!(
+        account.signed_one_time_keys("@alice:example.org", "DEVICE"),
+        serde_json::json!({"one_time_keys": {}, "fallback_keys": {}})
+    );
+}
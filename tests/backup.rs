@@ -0,0 +1,109 @@
+// Copyright 2020 Johannes Hayeß
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Server-side key backup.
+
+#![cfg(all(feature = "matrix", not(feature = "runtime-dlopen")))]
+
+mod common;
+
+use common::{MockRandom, MESSAGE, SESSION_KEY};
+use olm_sys::backup::{
+    BackupEncryption, BackupError, EncryptedSessionData, RecoveryKey, SessionData,
+};
+use olm_sys::group_session::InboundGroupSession;
+
+use std::str::from_utf8;
+
+/// The recovery key of the private key 0, 1, …, 31, made with Python.
+const RECOVERY_KEY: &str = "EsSz ykH7 LCZx 7Cae cmKD wcmY JRXi Ybtu 8iQ3 t8Ez nRwK pUY1";
+
+fn private_key() -> [u8; 32] {
+    std::array::from_fn(|i| i as u8)
+}
+
+fn session_data() -> SessionData {
+    let session = InboundGroupSession::new(from_utf8(SESSION_KEY).unwrap()).unwrap();
+    let mut data = SessionData::new("sender+curve25519+key", &session);
+    data.sender_claimed_keys
+        .insert("ed25519".to_string(), "sender+ed25519+key".to_string());
+
+    data
+}
+
+#[test]
+fn recovery_keys_encode_as_base58() {
+    let key = RecoveryKey::from_bytes(&private_key());
+    assert_eq!(key.to_base58(), RECOVERY_KEY);
+
+    let decoded = RecoveryKey::from_base58(&RECOVERY_KEY.replace(' ', "")).unwrap();
+    assert_eq!(decoded.public_key(), key.public_key());
+}
+
+#[test]
+fn invalid_recovery_keys_are_rejected() {
+    // A changed character breaks the parity
+    let typo = RECOVERY_KEY.replacen("EsSz", "EsSa", 1);
+    // The right parity, but no prefix
+    let unprefixed = "1111 1111 1111 1111 1111 1111 1111 1111 1111 1111 1111 1111";
+
+    for key in [&typo, unprefixed, "EsSz ykH7", "EsSz 0OIl"] {
+        assert_eq!(
+            RecoveryKey::from_base58(key).err(),
+            Some(BackupError::InvalidRecoveryKey),
+            "{}",
+            key
+        );
+    }
+}
+
+#[test]
+fn backed_up_sessions_decrypt_with_the_recovery_key() {
+    let mut random = MockRandom::new(b'B', 0);
+    let recovery_key = RecoveryKey::new(&mut random);
+    let mut backup = BackupEncryption::new(recovery_key.public_key()).unwrap();
+
+    let encrypted = backup.encrypt(&session_data(), &mut random);
+    let json = encrypted.to_json();
+    let keys: Vec<&String> = json.as_object().unwrap().keys().collect();
+    assert_eq!(keys, ["ciphertext", "ephemeral", "mac"]);
+
+    let restored = EncryptedSessionData::from_json(&json).unwrap();
+    let mut recovery_key = RecoveryKey::from_base58(&recovery_key.to_base58()).unwrap();
+    let decrypted = recovery_key.decrypt(&restored).unwrap();
+    assert_eq!(decrypted, session_data());
+
+    let mut session = decrypted.session().unwrap();
+    assert_eq!(
+        session.decrypt(from_utf8(MESSAGE).unwrap()).unwrap().0,
+        b"Message"
+    );
+}
+
+#[test]
+fn other_backups_cannot_decrypt() {
+    let mut random = MockRandom::new(b'B', 0);
+    let mut backup = BackupEncryption::new(RecoveryKey::new(&mut random).public_key()).unwrap();
+    let encrypted = backup.encrypt(&session_data(), &mut random);
+
+    let mut other = RecoveryKey::from_bytes(&private_key());
+    assert!(matches!(
+        other.decrypt(&encrypted),
+        Err(BackupError::Olm(error)) if error.name() == "BAD_MESSAGE_MAC"
+    ));
+    assert_eq!(
+        EncryptedSessionData::from_json(&serde_json::json!({"ciphertext": "", "mac": ""})),
+        Err(BackupError::InvalidJson)
+    );
+}
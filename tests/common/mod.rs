@@ -0,0 +1,278 @@
+// Copyright 2020 Johannes Hayeß
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Helpers shared by the test vectors ported from `olm/tests`, and by the
+//! benchmarks and fuzz targets which include this file.
+
+#![allow(dead_code)]
+
+use olm_sys::*;
+
+use std::ffi::{c_void, CStr};
+
+/// Allocates zeroed, 8-byte aligned memory of at least `size` bytes.
+pub fn alloc(size: usize) -> Vec<u64> {
+    vec![0; size.div_ceil(8)]
+}
+
+/// Casts `memory` to the `void *` the `olm_*` object constructors expect.
+pub fn memory(memory: &mut [u64]) -> *mut c_void {
+    memory.as_mut_ptr() as *mut c_void
+}
+
+/// The deterministic "randomness" of libolm's tests: every 32 byte block
+/// starts with `tag`, followed by 31 times a counter.
+pub struct MockRandom {
+    tag: u8,
+    current: u8,
+}
+
+impl MockRandom {
+    pub fn new(tag: u8, offset: u8) -> Self {
+        MockRandom {
+            tag,
+            current: offset,
+        }
+    }
+
+    pub fn fill(&mut self, bytes: &mut [u8]) {
+        for chunk in bytes.chunks_mut(32) {
+            chunk[0] = self.tag;
+            chunk[1..].fill(self.current);
+            self.current = self.current.wrapping_add(1);
+        }
+    }
+
+    /// `length` bytes of "randomness".
+    pub fn bytes(&mut self, length: usize) -> Vec<u8> {
+        let mut bytes = vec![0; length];
+        self.fill(&mut bytes);
+        bytes
+    }
+}
+
+impl olm_sys::random::RandomSource for MockRandom {
+    fn fill_bytes(&mut self, buffer: &mut [u8]) {
+        self.fill(buffer)
+    }
+}
+
+/// Signed Megolm session key and its first message, "Message", from the
+/// export/import test of `olm/tests/test_group_session.cpp`.
+pub const SESSION_KEY: &[u8] =
+    b"AgAAAAAwMTIzNDU2Nzg5QUJERUYwMTIzNDU2Nzg5QUJDREVGMDEyMzQ1Njc4OUFCREVGM\
+    DEyMzQ1Njc4OUFCQ0RFRjAxMjM0NTY3ODlBQkRFRjAxMjM0NTY3ODlBQkNERUYwMTIzND\
+    U2Nzg5QUJERUYwMTIzNDU2Nzg5QUJDREVGMDEyMw0bdg1BDq4Px/slBow06q8n/B9WBfw\
+    WYyNOB8DlUmXGGwrFmaSb9bR/eY8xgERrxmP07hFmD9uqA2p8PMHdnV5ysmgufE6oLZ5+\
+    8/mWQOW3VVTnDIlnwd8oHUYRuk8TCQ";
+pub const MESSAGE: &[u8] = b"AwgAEhAcbh6UpbByoyZxufQ+h2B+8XHMjhR69G8F4+qjMaFlnIXusJZX3r8LnRORG9T3D\
+    XFdbVuvIWrLyRfm4i8QRbe8VPwGRFG57B1CtmxanuP8bHtnnYqlwPsD";
+
+/// Encodes `bytes` as unpadded base64 with libolm's encoder.
+pub fn base64(bytes: &[u8]) -> String {
+    let mut output = vec![0; unsafe { _olm_encode_base64_length(bytes.len()) }];
+    unsafe { _olm_encode_base64(bytes.as_ptr(), bytes.len(), output.as_mut_ptr()) };
+    String::from_utf8(output).unwrap()
+}
+
+/// Re-encrypts `pickle` with `junk_length` bytes appended to its plaintext,
+/// which unpickling has to reject. Port of `add_junk_suffix_to_pickle` from
+/// `olm/tests/include/utils.hh`.
+pub fn add_junk_suffix_to_pickle(key: &[u8], pickle: &[u8], junk_length: usize) -> Vec<u8> {
+    let mut buffer = pickle.to_vec();
+    buffer.resize(
+        pickle.len() + unsafe { _olm_enc_output_length(junk_length) },
+        0,
+    );
+
+    unsafe {
+        let raw_length = _olm_enc_input(
+            key.as_ptr(),
+            key.len(),
+            buffer.as_mut_ptr(),
+            pickle.len(),
+            std::ptr::null_mut(),
+        );
+        assert_ne!(raw_length, usize::MAX, "pickle couldn't be decrypted");
+
+        let new_length = raw_length + junk_length;
+        buffer[raw_length..new_length].fill(0xff);
+
+        let destination = _olm_enc_output_pos(buffer.as_mut_ptr(), new_length);
+        std::ptr::copy(buffer.as_ptr(), destination, new_length);
+
+        let length = _olm_enc_output(key.as_ptr(), key.len(), buffer.as_mut_ptr(), new_length);
+        buffer.truncate(length);
+    }
+
+    buffer
+}
+
+/// Creates an account in `memory` from `random`.
+pub fn create_account(memory: &mut [u64], random: &mut MockRandom) -> *mut OlmAccount {
+    let account = unsafe { olm_account(self::memory(memory)) };
+    let mut bytes = random.bytes(unsafe { olm_create_account_random_length(account) });
+    let result =
+        unsafe { olm_create_account(account, bytes.as_mut_ptr() as *mut c_void, bytes.len()) };
+    assert_ne!(result, unsafe { olm_error() });
+
+    account
+}
+
+pub fn generate_one_time_keys(account: *mut OlmAccount, count: usize, random: &mut MockRandom) {
+    let mut bytes =
+        random.bytes(unsafe { olm_account_generate_one_time_keys_random_length(account, count) });
+    let result = unsafe {
+        olm_account_generate_one_time_keys(
+            account,
+            count,
+            bytes.as_mut_ptr() as *mut c_void,
+            bytes.len(),
+        )
+    };
+    assert_ne!(result, unsafe { olm_error() });
+}
+
+pub fn identity_keys(account: *mut OlmAccount) -> Vec<u8> {
+    let mut keys = vec![0; unsafe { olm_account_identity_keys_length(account) }];
+    unsafe { olm_account_identity_keys(account, keys.as_mut_ptr() as *mut c_void, keys.len()) };
+    keys
+}
+
+pub fn one_time_keys(account: *mut OlmAccount) -> Vec<u8> {
+    let mut keys = vec![0; unsafe { olm_account_one_time_keys_length(account) }];
+    unsafe { olm_account_one_time_keys(account, keys.as_mut_ptr() as *mut c_void, keys.len()) };
+    keys
+}
+
+/// The base64 Curve25519 key in a JSON key object, like the C++ tests which
+/// point 15 bytes into the identity keys or 25 into the one-time keys.
+pub fn key_at(keys: &[u8], offset: usize) -> &[u8] {
+    &keys[offset..offset + 43]
+}
+
+pub fn create_outbound_session(
+    memory: &mut [u64],
+    account: *mut OlmAccount,
+    identity_key: &[u8],
+    one_time_key: &[u8],
+    random: &mut MockRandom,
+) -> (*mut OlmSession, usize) {
+    let session = unsafe { olm_session(self::memory(memory)) };
+    let mut bytes = random.bytes(unsafe { olm_create_outbound_session_random_length(session) });
+    let result = unsafe {
+        olm_create_outbound_session(
+            session,
+            account,
+            identity_key.as_ptr() as *const c_void,
+            identity_key.len(),
+            one_time_key.as_ptr() as *const c_void,
+            one_time_key.len(),
+            bytes.as_mut_ptr() as *mut c_void,
+            bytes.len(),
+        )
+    };
+
+    (session, result)
+}
+
+/// Creates Bob's session in `memory` from Alice's pre-key `message`.
+pub fn create_inbound_session(
+    memory: &mut [u64],
+    account: *mut OlmAccount,
+    message: &[u8],
+) -> (*mut OlmSession, usize) {
+    let session = unsafe { olm_session(self::memory(memory)) };
+    let mut copy = message.to_vec();
+    let result = unsafe {
+        olm_create_inbound_session(
+            session,
+            account,
+            copy.as_mut_ptr() as *mut c_void,
+            copy.len(),
+        )
+    };
+
+    (session, result)
+}
+
+/// Encrypts `plaintext`, returning the message type and the message.
+pub fn encrypt(
+    session: *mut OlmSession,
+    plaintext: &[u8],
+    random: &mut MockRandom,
+) -> (usize, Vec<u8>) {
+    let message_type = unsafe { olm_encrypt_message_type(session) };
+    let mut message = vec![0; unsafe { olm_encrypt_message_length(session, plaintext.len()) }];
+    let mut bytes = random.bytes(unsafe { olm_encrypt_random_length(session) });
+    let result = unsafe {
+        olm_encrypt(
+            session,
+            plaintext.as_ptr() as *const c_void,
+            plaintext.len(),
+            bytes.as_mut_ptr() as *mut c_void,
+            bytes.len(),
+            message.as_mut_ptr() as *mut c_void,
+            message.len(),
+        )
+    };
+    assert_ne!(result, unsafe { olm_error() });
+
+    (message_type, message)
+}
+
+/// Decrypts `message`, which libolm destroys, so only copies are passed.
+pub fn decrypt(
+    session: *mut OlmSession,
+    message_type: usize,
+    message: &[u8],
+) -> Result<Vec<u8>, String> {
+    let mut copy = message.to_vec();
+    let max_length = unsafe {
+        olm_decrypt_max_plaintext_length(
+            session,
+            message_type,
+            copy.as_mut_ptr() as *mut c_void,
+            copy.len(),
+        )
+    };
+    if max_length == unsafe { olm_error() } {
+        return Err(session_error(session));
+    }
+
+    let mut plaintext = vec![0; max_length];
+    copy.copy_from_slice(message);
+    let length = unsafe {
+        olm_decrypt(
+            session,
+            message_type,
+            copy.as_mut_ptr() as *mut c_void,
+            copy.len(),
+            plaintext.as_mut_ptr() as *mut c_void,
+            plaintext.len(),
+        )
+    };
+    if length == unsafe { olm_error() } {
+        return Err(session_error(session));
+    }
+
+    plaintext.truncate(length);
+    Ok(plaintext)
+}
+
+pub fn session_error(session: *mut OlmSession) -> String {
+    unsafe { CStr::from_ptr(olm_session_last_error(session)) }
+        .to_string_lossy()
+        .into_owned()
+}
@@ -0,0 +1,187 @@
+// Copyright 2020 Johannes Hayeß
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Cross-signing keys and their chains of signatures.
+
+#![cfg(all(feature = "matrix", not(feature = "runtime-dlopen")))]
+
+mod common;
+
+use common::MockRandom;
+use olm_sys::account::Account;
+use olm_sys::cross_signing::{verify_device, verify_user, CrossSigningError, CrossSigningIdentity};
+use olm_sys::signing::{sign_json, SignatureError};
+use olm_sys::OlmErrorCode_OLM_BAD_ACCOUNT_KEY;
+
+use serde_json::{json, Value};
+
+const ALICE: &str = "@alice:example.org";
+const BOB: &str = "@bob:example.org";
+
+/// Device keys of `account`, signed by the device.
+fn signed_device_keys(account: &Account, user_id: &str) -> Value {
+    let identity_keys: Value = serde_json::from_str(&account.identity_keys()).unwrap();
+    let mut device_keys = json!({
+        "user_id": user_id,
+        "device_id": "DEVICE",
+        "algorithms": ["m.olm.v1.curve25519-aes-sha2", "m.megolm.v1.aes-sha2"],
+        "keys": {
+            "curve25519:DEVICE": identity_keys["curve25519"],
+            "ed25519:DEVICE": identity_keys["ed25519"],
+        },
+    });
+    sign_json(account, user_id, "DEVICE", &mut device_keys).unwrap();
+
+    device_keys
+}
+
+#[test]
+fn upload_keys() {
+    // The seed of the signing examples of the Matrix specification
+    let seed = [
+        96, 144, 193, 3, 213, 231, 175, 107, 21, 169, 112, 253, 86, 62, 215, 85, 73, 230, 21, 151,
+        25, 174, 92, 60, 49, 222, 228, 49, 111, 183, 92, 13,
+    ];
+    let identity = CrossSigningIdentity::from_seeds(ALICE, &seed, &[1; 32], &[2; 32]);
+    let master = "XGX0JRS2Af3be3knz2fBiRbApjm2Dh61gXDJA8kcJNI";
+    assert_eq!(identity.master_public_key(), master);
+
+    let upload = identity.upload_keys();
+    assert_eq!(
+        upload["master_key"],
+        json!({
+            "user_id": ALICE,
+            "usage": ["master"],
+            "keys": { format!("ed25519:{master}"): master },
+        })
+    );
+    let self_signing = identity.self_signing_public_key();
+    assert_eq!(upload["self_signing_key"]["usage"], json!(["self_signing"]));
+    assert_eq!(
+        upload["self_signing_key"]["keys"][format!("ed25519:{self_signing}")],
+        self_signing
+    );
+    assert!(
+        upload["self_signing_key"]["signatures"][ALICE][format!("ed25519:{master}")].is_string()
+    );
+    assert_eq!(upload["user_signing_key"]["usage"], json!(["user_signing"]));
+    assert_ne!(identity.user_signing_public_key(), self_signing);
+}
+
+#[test]
+fn verify_own_device() {
+    let identity = CrossSigningIdentity::new(ALICE, &mut MockRandom::new(b'C', 0x00));
+    let upload = identity.upload_keys();
+    let account = Account::new(&mut MockRandom::new(b'A', 0x00));
+    let mut device_keys = signed_device_keys(&account, ALICE);
+
+    assert!(matches!(
+        verify_device(
+            &upload["master_key"],
+            &upload["self_signing_key"],
+            &device_keys
+        ),
+        Err(CrossSigningError::Signature(
+            SignatureError::MissingSignature
+        ))
+    ));
+    identity.sign_device(&mut device_keys).unwrap();
+    assert_eq!(
+        verify_device(
+            &upload["master_key"],
+            &upload["self_signing_key"],
+            &device_keys
+        ),
+        Ok(())
+    );
+
+    // The user-signing key can't stand in for the self-signing key
+    assert_eq!(
+        verify_device(
+            &upload["master_key"],
+            &upload["user_signing_key"],
+            &device_keys
+        ),
+        Err(CrossSigningError::InvalidKey)
+    );
+
+    // Nor can another user's self-signing key
+    let other = CrossSigningIdentity::new(ALICE, &mut MockRandom::new(b'O', 0x00));
+    assert!(matches!(
+        verify_device(
+            &upload["master_key"],
+            &other.self_signing_key(),
+            &device_keys
+        ),
+        Err(CrossSigningError::Signature(_))
+    ));
+
+    let mut bob_device = signed_device_keys(&account, BOB);
+    assert_eq!(
+        identity.sign_device(&mut bob_device),
+        Err(CrossSigningError::WrongUser)
+    );
+}
+
+#[test]
+fn verify_other_user() {
+    let alice = CrossSigningIdentity::new(ALICE, &mut MockRandom::new(b'A', 0x00));
+    let bob = CrossSigningIdentity::new(BOB, &mut MockRandom::new(b'B', 0x00));
+    let mut bob_master = bob.master_key();
+
+    alice.sign_user(&mut bob_master).unwrap();
+    assert_eq!(
+        verify_user(&alice.master_key(), &alice.user_signing_key(), &bob_master),
+        Ok(())
+    );
+    assert!(matches!(
+        verify_user(&bob.master_key(), &bob.user_signing_key(), &bob_master),
+        Err(CrossSigningError::Signature(
+            SignatureError::MissingSignature
+        ))
+    ));
+    assert_eq!(
+        verify_user(&alice.master_key(), &bob.user_signing_key(), &bob_master),
+        Err(CrossSigningError::WrongUser)
+    );
+
+    assert_eq!(
+        alice.sign_user(&mut alice.master_key()),
+        Err(CrossSigningError::WrongUser)
+    );
+    assert_eq!(
+        alice.sign_user(&mut bob.self_signing_key()),
+        Err(CrossSigningError::InvalidKey)
+    );
+}
+
+#[test]
+fn pickle_round_trip() {
+    let identity = CrossSigningIdentity::new(ALICE, &mut MockRandom::new(b'C', 0x00));
+    let pickle = identity.pickle(b"secret_key");
+
+    let unpickled = CrossSigningIdentity::unpickle(b"secret_key", &pickle).unwrap();
+    assert_eq!(unpickled.user_id(), ALICE);
+    assert_eq!(unpickled.master_seed(), identity.master_seed());
+    assert_eq!(unpickled.self_signing_seed(), identity.self_signing_seed());
+    assert_eq!(unpickled.user_signing_seed(), identity.user_signing_seed());
+    assert_eq!(unpickled.upload_keys(), identity.upload_keys());
+
+    match CrossSigningIdentity::unpickle(b"wrong_key", &pickle) {
+        Err(CrossSigningError::Olm(error)) => {
+            assert_eq!(error.code(), OlmErrorCode_OLM_BAD_ACCOUNT_KEY)
+        }
+        _ => panic!("unpickled with the wrong key"),
+    }
+}
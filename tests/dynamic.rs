@@ -0,0 +1,101 @@
+// Copyright 2020 Johannes Hayeß
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Checks of the `runtime-dlopen` backend.
+//!
+//! The tests which need a library are ignored by default. They load the one
+//! the `OLM_SYS_TEST_LIBRARY` environment variable names:
+//!
+//! ```bash
+//! $ OLM_SYS_TEST_LIBRARY=/usr/lib/libolm.so.3 cargo test --features runtime-dlopen --test dynamic -- --ignored
+//! ```
+
+#![cfg(feature = "runtime-dlopen")]
+
+mod common;
+
+use common::MockRandom;
+use olm_sys::account::Account;
+use olm_sys::backend::{self, OlmLibrary};
+use olm_sys::pk::PkSigning;
+
+use std::env;
+use std::sync::Once;
+
+/// Installs the library `OLM_SYS_TEST_LIBRARY` names as the backend, once for
+/// all tests.
+fn install() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        let path = env::var_os("OLM_SYS_TEST_LIBRARY").expect("OLM_SYS_TEST_LIBRARY isn't set");
+        let library = unsafe { OlmLibrary::new(path) }.expect("failed to load libolm");
+        assert!(library.olm_account_size.is_ok());
+
+        let installed = backend::install(library).unwrap_or_else(|_| panic!("already installed"));
+        assert!(std::ptr::eq(installed, backend::installed().unwrap()));
+    });
+}
+
+#[test]
+fn missing_library_is_an_error() {
+    assert!(unsafe { OlmLibrary::new("libolm-that-does-not-exist.so") }.is_err());
+}
+
+#[test]
+#[ignore = "needs OLM_SYS_TEST_LIBRARY"]
+fn loaded_library_backs_safe_wrappers() {
+    install();
+
+    let (major, _, _) = olm_sys::version::library_version();
+    assert_eq!(major, 3);
+    assert_eq!(olm_sys::version::check_compatible(), Ok(()));
+}
+
+#[test]
+#[ignore = "needs OLM_SYS_TEST_LIBRARY"]
+fn account_round_trips_through_loaded_library() {
+    install();
+
+    let mut random = MockRandom::new(b'P', 0);
+    let mut account = Account::new(&mut random);
+    account.generate_one_time_keys(5, &mut random);
+
+    let pickle = account.pickle(b"secret_key");
+    let unpickled = Account::unpickle(b"secret_key", &pickle).unwrap();
+    assert_eq!(unpickled.identity_keys(), account.identity_keys());
+    assert_eq!(unpickled.one_time_keys(), account.one_time_keys());
+    assert_eq!(unpickled.pickle(b"secret_key"), pickle);
+}
+
+#[test]
+#[ignore = "needs OLM_SYS_TEST_LIBRARY"]
+fn pk_signing_matches_static_library() {
+    install();
+
+    // The seed of `olm/tests/test_pk.cpp`, as in tests/pk_signing.rs
+    let signing = PkSigning::from_seed(&[
+        0x77, 0x07, 0x6D, 0x0A, 0x73, 0x18, 0xA5, 0x7D, 0x3C, 0x16, 0xC1, 0x72, 0x51, 0xB2, 0x66,
+        0x45, 0xDF, 0x4C, 0x2F, 0x87, 0xEB, 0xC0, 0x99, 0x2A, 0xB1, 0x77, 0xFB, 0xA5, 0x1D, 0xB9,
+        0x2C, 0x2A,
+    ])
+    .unwrap();
+    assert_eq!(
+        signing.public_key(),
+        "0KTOwPi1C6HpNuxWFUVKqX37J4ZPXxdgivLLsQVI8bM"
+    );
+    assert_eq!(
+        signing.sign(b"Message"),
+        "E1uD5cu/VGh3xaENlxKvs+akGuOPMakmTh6rJ8uihRfWx2uGIAUq5hGR6v6CuyKrQ25tuvjRx3UBLFYuagpuBg"
+    );
+}
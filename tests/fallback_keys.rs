@@ -0,0 +1,145 @@
+// Copyright 2020 Johannes Hayeß
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The lifetimes of fallback keys.
+
+#![cfg(all(feature = "matrix", not(feature = "runtime-dlopen")))]
+
+mod common;
+
+use common::MockRandom;
+use olm_sys::account::Account;
+use olm_sys::fallback_keys::{FallbackKeySchedule, FallbackKeys};
+use olm_sys::OlmErrorCode_OLM_BAD_ACCOUNT_KEY;
+
+use std::time::Duration;
+
+const HOUR: Duration = Duration::from_secs(60 * 60);
+const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+fn unpublished_fallback_key(account: &Account) -> String {
+    let keys: serde_json::Value =
+        serde_json::from_str(&account.unpublished_fallback_key()).unwrap();
+    let key = keys["curve25519"].as_object().unwrap().values().next();
+    key.unwrap().as_str().unwrap().to_owned()
+}
+
+#[test]
+fn fallback_key_lifetime() {
+    let schedule = FallbackKeySchedule::default();
+    let mut random = MockRandom::new(b'F', 0);
+    let mut account = Account::new(&mut random);
+    let mut fallback_keys = FallbackKeys::new();
+    assert!(fallback_keys.should_rotate(DAY, &schedule));
+
+    fallback_keys.generate(&mut account, DAY, &mut random);
+    let current = fallback_keys.current().unwrap().clone();
+    assert_eq!(current.public_key(), unpublished_fallback_key(&account));
+    assert_eq!(current.generated_at(), DAY);
+    assert_eq!(current.published_at(), None);
+    assert!(!fallback_keys.should_rotate(DAY + HOUR, &schedule));
+
+    fallback_keys.mark_published(&mut account, 2 * DAY);
+    assert_eq!(
+        fallback_keys.current().unwrap().published_at(),
+        Some(2 * DAY)
+    );
+    assert_eq!(account.unpublished_fallback_key(), "{\"curve25519\":{}}");
+    // The rotation period counts from the publication
+    assert!(!fallback_keys.should_rotate(8 * DAY, &schedule));
+    assert!(fallback_keys.should_rotate(9 * DAY, &schedule));
+
+    assert!(!fallback_keys.record_use("some+other+key", 3 * DAY));
+    assert!(fallback_keys.record_use(current.public_key(), 3 * DAY));
+    assert!(fallback_keys.record_use(current.public_key(), 4 * DAY));
+    assert_eq!(
+        fallback_keys.current().unwrap().first_used_at(),
+        Some(3 * DAY)
+    );
+    // A used key is replaced soon after
+    assert!(!fallback_keys.should_rotate(3 * DAY, &schedule));
+    assert!(fallback_keys.should_rotate(3 * DAY + HOUR, &schedule));
+}
+
+#[test]
+fn previous_key_is_forgotten() {
+    let schedule = FallbackKeySchedule::default();
+    let mut random = MockRandom::new(b'P', 0);
+    let mut account = Account::new(&mut random);
+    let mut fallback_keys = FallbackKeys::new();
+
+    fallback_keys.generate(&mut account, DAY, &mut random);
+    fallback_keys.mark_published(&mut account, DAY);
+    let first = fallback_keys.current().unwrap().clone();
+    assert!(!fallback_keys.can_forget_old(10 * DAY, &schedule));
+    let one_key = account.pickle(b"key").len();
+
+    fallback_keys.generate(&mut account, 8 * DAY, &mut random);
+    assert_eq!(fallback_keys.previous(), Some(&first));
+    assert!(fallback_keys.record_use(first.public_key(), 8 * DAY));
+    fallback_keys.mark_published(&mut account, 8 * DAY + HOUR);
+
+    assert!(!fallback_keys.can_forget_old(9 * DAY, &schedule));
+    assert!(fallback_keys.can_forget_old(9 * DAY + HOUR, &schedule));
+    fallback_keys.forget_old(&mut account);
+    assert_eq!(fallback_keys.previous(), None);
+    assert!(!fallback_keys.can_forget_old(10 * DAY, &schedule));
+    assert_eq!(account.pickle(b"key").len(), one_key);
+}
+
+#[test]
+fn pickle_round_trip() {
+    let mut random = MockRandom::new(b'R', 0);
+    let mut account = Account::new(&mut random);
+    let mut fallback_keys = FallbackKeys::new();
+    let pickle = fallback_keys.pickle(b"secret_key");
+    assert_eq!(
+        FallbackKeys::unpickle(b"secret_key", &pickle),
+        Ok(FallbackKeys::new())
+    );
+
+    fallback_keys.generate(&mut account, DAY, &mut random);
+    fallback_keys.mark_published(&mut account, DAY + Duration::from_millis(1500));
+    fallback_keys.generate(&mut account, 2 * DAY, &mut random);
+    let current = fallback_keys.current().unwrap().public_key().to_owned();
+    fallback_keys.record_use(&current, 3 * DAY);
+
+    let pickle = fallback_keys.pickle(b"secret_key");
+    assert_eq!(
+        FallbackKeys::unpickle(b"secret_key", &pickle),
+        Ok(fallback_keys)
+    );
+    let error = FallbackKeys::unpickle(b"wrong_key", &pickle).unwrap_err();
+    assert_eq!(error.code(), OlmErrorCode_OLM_BAD_ACCOUNT_KEY);
+}
+
+#[test]
+fn endless_periods_never_pass() {
+    let schedule = FallbackKeySchedule {
+        rotation_period: Duration::MAX,
+        rotate_after_use: Duration::MAX,
+        forget_old_after: Duration::MAX,
+    };
+    let mut random = MockRandom::new(b'E', 0);
+    let mut account = Account::new(&mut random);
+    let mut fallback_keys = FallbackKeys::new();
+
+    fallback_keys.generate(&mut account, DAY, &mut random);
+    fallback_keys.generate(&mut account, 2 * DAY, &mut random);
+    let current = fallback_keys.current().unwrap().public_key().to_owned();
+    fallback_keys.record_use(&current, 3 * DAY);
+
+    assert!(!fallback_keys.should_rotate(Duration::MAX, &schedule));
+    assert!(!fallback_keys.can_forget_old(Duration::MAX, &schedule));
+}
@@ -0,0 +1,339 @@
+// Copyright 2020 Johannes Hayeß
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The test vectors of `olm/tests/test_group_session.cpp`: Megolm sessions,
+//! driven through the raw bindings.
+
+#![cfg(not(feature = "runtime-dlopen"))]
+
+mod common;
+
+use common::{add_junk_suffix_to_pickle, alloc, memory, MESSAGE, SESSION_KEY};
+use olm_sys::*;
+
+use std::ffi::{c_void, CStr};
+
+const PICKLE_KEY: &[u8] = b"secret_key";
+
+fn init_inbound_session(memory: &mut [u64], session_key: &[u8]) -> *mut OlmInboundGroupSession {
+    let session = unsafe { olm_inbound_group_session(common::memory(memory)) };
+    assert_eq!(unsafe { olm_inbound_group_session_is_verified(session) }, 0);
+
+    let result =
+        unsafe { olm_init_inbound_group_session(session, session_key.as_ptr(), session_key.len()) };
+    assert_eq!(result, 0);
+
+    session
+}
+
+/// Decrypts `message`, returning the plaintext and the message index.
+///
+/// Both the maximum length and the decryption destroy the message, so only
+/// copies are passed.
+fn group_decrypt(
+    session: *mut OlmInboundGroupSession,
+    message: &[u8],
+) -> Result<(Vec<u8>, u32), String> {
+    let mut copy = message.to_vec();
+    let max_length =
+        unsafe { olm_group_decrypt_max_plaintext_length(session, copy.as_mut_ptr(), copy.len()) };
+    assert_ne!(max_length, unsafe { olm_error() });
+
+    let mut plaintext = vec![0; max_length];
+    let mut message_index = u32::MAX;
+    copy.copy_from_slice(message);
+    let length = unsafe {
+        olm_group_decrypt(
+            session,
+            copy.as_mut_ptr(),
+            copy.len(),
+            plaintext.as_mut_ptr(),
+            plaintext.len(),
+            &mut message_index,
+        )
+    };
+    if length == unsafe { olm_error() } {
+        let error = unsafe { CStr::from_ptr(olm_inbound_group_session_last_error(session)) };
+        return Err(error.to_string_lossy().into_owned());
+    }
+
+    plaintext.truncate(length);
+    Ok((plaintext, message_index))
+}
+
+#[test]
+fn pickle_outbound_group_session() {
+    let size = unsafe { olm_outbound_group_session_size() };
+    let mut session_memory = alloc(size);
+    let session = unsafe { olm_outbound_group_session(memory(&mut session_memory)) };
+
+    let pickle_length = unsafe { olm_pickle_outbound_group_session_length(session) };
+    let mut pickle1 = vec![0u8; pickle_length];
+    let result = unsafe {
+        olm_pickle_outbound_group_session(
+            session,
+            PICKLE_KEY.as_ptr() as *const c_void,
+            PICKLE_KEY.len(),
+            pickle1.as_mut_ptr() as *mut c_void,
+            pickle_length,
+        )
+    };
+    assert_eq!(result, pickle_length);
+
+    let mut pickle2 = pickle1.clone();
+    let mut session_memory2 = alloc(size);
+    let session2 = unsafe { olm_outbound_group_session(memory(&mut session_memory2)) };
+    let result = unsafe {
+        olm_unpickle_outbound_group_session(
+            session2,
+            PICKLE_KEY.as_ptr() as *const c_void,
+            PICKLE_KEY.len(),
+            pickle2.as_mut_ptr() as *mut c_void,
+            pickle_length,
+        )
+    };
+    assert_ne!(result, unsafe { olm_error() });
+    assert_eq!(
+        unsafe { olm_pickle_outbound_group_session_length(session2) },
+        pickle_length
+    );
+    let result = unsafe {
+        olm_pickle_outbound_group_session(
+            session2,
+            PICKLE_KEY.as_ptr() as *const c_void,
+            PICKLE_KEY.len(),
+            pickle2.as_mut_ptr() as *mut c_void,
+            pickle_length,
+        )
+    };
+    assert_eq!(result, pickle_length);
+    assert_eq!(pickle1, pickle2);
+
+    let mut junk_pickle = add_junk_suffix_to_pickle(PICKLE_KEY, &pickle1, 1);
+    let result = unsafe {
+        olm_unpickle_outbound_group_session(
+            session,
+            PICKLE_KEY.as_ptr() as *const c_void,
+            PICKLE_KEY.len(),
+            junk_pickle.as_mut_ptr() as *mut c_void,
+            junk_pickle.len(),
+        )
+    };
+    assert_eq!(result, unsafe { olm_error() });
+    assert_eq!(
+        unsafe { olm_outbound_group_session_last_error_code(session) },
+        OlmErrorCode_OLM_PICKLE_EXTRA_DATA
+    );
+}
+
+#[test]
+fn pickle_inbound_group_session() {
+    let size = unsafe { olm_inbound_group_session_size() };
+    let mut session_memory = alloc(size);
+    let session = unsafe { olm_inbound_group_session(memory(&mut session_memory)) };
+
+    let pickle_length = unsafe { olm_pickle_inbound_group_session_length(session) };
+    let mut pickle1 = vec![0u8; pickle_length];
+    let result = unsafe {
+        olm_pickle_inbound_group_session(
+            session,
+            PICKLE_KEY.as_ptr() as *const c_void,
+            PICKLE_KEY.len(),
+            pickle1.as_mut_ptr() as *mut c_void,
+            pickle_length,
+        )
+    };
+    assert_eq!(result, pickle_length);
+
+    let mut pickle2 = pickle1.clone();
+    let mut session_memory2 = alloc(size);
+    let session2 = unsafe { olm_inbound_group_session(memory(&mut session_memory2)) };
+    let result = unsafe {
+        olm_unpickle_inbound_group_session(
+            session2,
+            PICKLE_KEY.as_ptr() as *const c_void,
+            PICKLE_KEY.len(),
+            pickle2.as_mut_ptr() as *mut c_void,
+            pickle_length,
+        )
+    };
+    assert_ne!(result, unsafe { olm_error() });
+    assert_eq!(
+        unsafe { olm_pickle_inbound_group_session_length(session2) },
+        pickle_length
+    );
+    let result = unsafe {
+        olm_pickle_inbound_group_session(
+            session2,
+            PICKLE_KEY.as_ptr() as *const c_void,
+            PICKLE_KEY.len(),
+            pickle2.as_mut_ptr() as *mut c_void,
+            pickle_length,
+        )
+    };
+    assert_eq!(result, pickle_length);
+    assert_eq!(pickle1, pickle2);
+
+    let mut junk_pickle = add_junk_suffix_to_pickle(PICKLE_KEY, &pickle1, 1);
+    let result = unsafe {
+        olm_unpickle_inbound_group_session(
+            session,
+            PICKLE_KEY.as_ptr() as *const c_void,
+            PICKLE_KEY.len(),
+            junk_pickle.as_mut_ptr() as *mut c_void,
+            junk_pickle.len(),
+        )
+    };
+    assert_eq!(result, unsafe { olm_error() });
+    assert_eq!(
+        unsafe { olm_inbound_group_session_last_error_code(session) },
+        OlmErrorCode_OLM_PICKLE_EXTRA_DATA
+    );
+}
+
+#[test]
+fn group_message_send_receive() {
+    // Like the C++ string literal, including its terminating null byte
+    let mut random_bytes = b"0123456789ABDEF0123456789ABCDEF\
+        0123456789ABDEF0123456789ABCDEF\
+        0123456789ABDEF0123456789ABCDEF\
+        0123456789ABDEF0123456789ABCDEF\
+        0123456789ABDEF0123456789ABCDEF\
+        0123456789ABDEF0123456789ABCDEF\0"
+        .to_vec();
+
+    let mut outbound_memory = alloc(unsafe { olm_outbound_group_session_size() });
+    let outbound = unsafe { olm_outbound_group_session(memory(&mut outbound_memory)) };
+    assert_eq!(
+        unsafe { olm_init_outbound_group_session_random_length(outbound) },
+        160
+    );
+    let result = unsafe {
+        olm_init_outbound_group_session(outbound, random_bytes.as_mut_ptr(), random_bytes.len())
+    };
+    assert_eq!(result, 0);
+    assert_eq!(
+        unsafe { olm_outbound_group_session_message_index(outbound) },
+        0
+    );
+
+    let mut session_key = vec![0; unsafe { olm_outbound_group_session_key_length(outbound) }];
+    unsafe {
+        olm_outbound_group_session_key(outbound, session_key.as_mut_ptr(), session_key.len())
+    };
+
+    let plaintext = b"Message";
+    let mut message =
+        vec![0; unsafe { olm_group_encrypt_message_length(outbound, plaintext.len()) }];
+    let result = unsafe {
+        olm_group_encrypt(
+            outbound,
+            plaintext.as_ptr(),
+            plaintext.len(),
+            message.as_mut_ptr(),
+            message.len(),
+        )
+    };
+    assert_eq!(result, message.len());
+    assert_eq!(
+        unsafe { olm_outbound_group_session_message_index(outbound) },
+        1
+    );
+
+    let mut inbound_memory = alloc(unsafe { olm_inbound_group_session_size() });
+    let inbound = init_inbound_session(&mut inbound_memory, &session_key);
+    assert_eq!(unsafe { olm_inbound_group_session_is_verified(inbound) }, 1);
+
+    let mut outbound_id = vec![0; unsafe { olm_outbound_group_session_id_length(outbound) }];
+    let length = unsafe {
+        olm_outbound_group_session_id(outbound, outbound_id.as_mut_ptr(), outbound_id.len())
+    };
+    assert_eq!(length, outbound_id.len());
+    let mut inbound_id = vec![0; unsafe { olm_inbound_group_session_id_length(inbound) }];
+    let length =
+        unsafe { olm_inbound_group_session_id(inbound, inbound_id.as_mut_ptr(), inbound_id.len()) };
+    assert_eq!(length, inbound_id.len());
+    assert_eq!(outbound_id, inbound_id);
+
+    let (decrypted, message_index) = group_decrypt(inbound, &message).unwrap();
+    assert_eq!(decrypted, plaintext);
+    assert_eq!(message_index, 0);
+}
+
+#[test]
+fn inbound_group_session_export_import() {
+    let size = unsafe { olm_inbound_group_session_size() };
+    let mut session_memory1 = alloc(size);
+    let session1 = init_inbound_session(&mut session_memory1, SESSION_KEY);
+    assert_eq!(
+        unsafe { olm_inbound_group_session_is_verified(session1) },
+        1
+    );
+
+    let (plaintext, message_index) = group_decrypt(session1, MESSAGE).unwrap();
+    assert_eq!(plaintext, b"Message");
+    assert_eq!(message_index, 0);
+
+    let mut export = vec![0; unsafe { olm_export_inbound_group_session_length(session1) }];
+    let length =
+        unsafe { olm_export_inbound_group_session(session1, export.as_mut_ptr(), export.len(), 0) };
+    assert_eq!(length, export.len());
+
+    // Clear the old session to check that there is no shared data
+    unsafe { olm_clear_inbound_group_session(session1) };
+
+    let mut session_memory2 = alloc(size);
+    let session2 = unsafe { olm_inbound_group_session(memory(&mut session_memory2)) };
+    let result =
+        unsafe { olm_import_inbound_group_session(session2, export.as_ptr(), export.len()) };
+    assert_eq!(result, 0);
+    assert_eq!(
+        unsafe { olm_inbound_group_session_is_verified(session2) },
+        0
+    );
+
+    let (plaintext, message_index) = group_decrypt(session2, MESSAGE).unwrap();
+    assert_eq!(plaintext, b"Message");
+    assert_eq!(message_index, 0);
+    assert_eq!(
+        unsafe { olm_inbound_group_session_is_verified(session2) },
+        1
+    );
+}
+
+#[test]
+fn invalid_signature_group_message() {
+    let session_key = b"AgAAAAAwMTIzNDU2Nzg5QUJERUYwMTIzNDU2Nzg5QUJDREVGMDEyMzQ1Njc4OUFCREVGM\
+        DEyMzQ1Njc4OUFCQ0RFRjAxMjM0NTY3ODlBQkRFRjAxMjM0NTY3ODlBQkNERUYwMTIzND\
+        U2Nzg5QUJERUYwMTIzNDU2Nzg5QUJDREVGMDEyMztqJ7zOtqQtYqOo0CpvDXNlMhV3HeJ\
+        DpjrASKGLWdop4lx1cSN3Xv1TgfLPW8rhGiW+hHiMxd36nRuxscNv9k4oJA/KP+o0mi1w\
+        v44StrEJ1wwx9WZHBUIWkQbaBSuBDw";
+    let mut message = b"AwgAEhAcbh6UpbByoyZxufQ+h2B+8XHMjhR69G8nP4pNZGl/3QMgrzCZPmP+F2aPLyKPz\
+        xRPBMUkeXRJ6Iqm5NeOdx2eERgTW7P20CM+lL3Xpk+ZUOOPvsSQNaAL"
+        .to_vec();
+
+    let mut session_memory = alloc(unsafe { olm_inbound_group_session_size() });
+    let session = init_inbound_session(&mut session_memory, session_key);
+
+    let (plaintext, message_index) = group_decrypt(session, &message).unwrap();
+    assert_eq!(plaintext, b"Message");
+    assert_eq!(message_index, 0);
+
+    // Twiddle the signature
+    *message.last_mut().unwrap() = b'E';
+    assert_eq!(
+        group_decrypt(session, &message).unwrap_err(),
+        "BAD_SIGNATURE"
+    );
+}
@@ -0,0 +1,72 @@
+// Copyright 2020 Johannes Hayeß
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The safe `InboundGroupSession` wrapper.
+
+#![cfg(not(feature = "runtime-dlopen"))]
+
+mod common;
+
+use common::{MESSAGE, SESSION_KEY};
+use olm_sys::group_session::InboundGroupSession;
+use olm_sys::*;
+
+use std::str::from_utf8;
+
+const PICKLE_KEY: &[u8] = b"secret_key";
+
+#[test]
+fn session_decrypts_and_round_trips_through_pickle() {
+    let mut session = InboundGroupSession::new(from_utf8(SESSION_KEY).unwrap()).unwrap();
+    assert!(session.is_verified());
+    assert_eq!(session.first_known_index(), 0);
+
+    let (plaintext, message_index) = session.decrypt(from_utf8(MESSAGE).unwrap()).unwrap();
+    assert_eq!(plaintext, b"Message");
+    assert_eq!(message_index, 0);
+
+    let pickle = session.pickle(PICKLE_KEY);
+    let unpickled = InboundGroupSession::unpickle(PICKLE_KEY, &pickle).unwrap();
+    assert_eq!(unpickled.session_id(), session.session_id());
+    assert_eq!(unpickled.pickle(PICKLE_KEY), pickle);
+}
+
+#[test]
+fn exported_sessions_import() {
+    let session = InboundGroupSession::new(from_utf8(SESSION_KEY).unwrap()).unwrap();
+    let exported = session.export_at(0).unwrap();
+
+    let mut imported = InboundGroupSession::import(&exported).unwrap();
+    assert!(!imported.is_verified());
+    assert_eq!(imported.session_id(), session.session_id());
+    assert_eq!(
+        imported.decrypt(from_utf8(MESSAGE).unwrap()).unwrap().0,
+        b"Message"
+    );
+    assert!(imported.is_verified());
+
+    // A session exported from a later index can't go back
+    let later = InboundGroupSession::import(&session.export_at(1).unwrap()).unwrap();
+    assert_eq!(later.first_known_index(), 1);
+    assert_eq!(
+        later.export_at(0).unwrap_err().code(),
+        OlmErrorCode_OLM_UNKNOWN_MESSAGE_INDEX
+    );
+}
+
+#[test]
+fn invalid_session_keys_are_rejected() {
+    let error = InboundGroupSession::new("not a session key").err().unwrap();
+    assert_eq!(error.code(), OlmErrorCode_OLM_INVALID_BASE64);
+}
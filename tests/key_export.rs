@@ -0,0 +1,154 @@
+// Copyright 2020 Johannes Hayeß
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The Megolm session export file format.
+
+#![cfg(all(feature = "matrix", not(feature = "runtime-dlopen")))]
+
+mod common;
+
+use common::{MockRandom, MESSAGE, SESSION_KEY};
+use olm_sys::group_session::InboundGroupSession;
+use olm_sys::key_export::{export_room_keys, import_room_keys, ExportedRoomKey, KeyExportError};
+
+use std::str::from_utf8;
+
+const ROUNDS: u32 = 1000;
+
+fn room_key(room_id: &str) -> ExportedRoomKey {
+    let session = InboundGroupSession::new(from_utf8(SESSION_KEY).unwrap()).unwrap();
+    let mut key = ExportedRoomKey::new(room_id, "sender+curve25519+key", &session);
+    key.sender_claimed_keys
+        .insert("ed25519".to_string(), "sender+ed25519+key".to_string());
+
+    key
+}
+
+#[test]
+fn exported_keys_import() {
+    let keys = vec![room_key("!a:example.org"), room_key("!b:example.org")];
+    let file = export_room_keys(&keys, "passphrase", ROUNDS, &mut MockRandom::new(b'E', 0));
+
+    assert!(file.starts_with("-----BEGIN MEGOLM SESSION DATA-----\n"));
+    assert!(file.ends_with("\n-----END MEGOLM SESSION DATA-----\n"));
+    assert!(file.lines().all(|line| line.len() <= 96));
+
+    let imported = import_room_keys(&file, "passphrase").unwrap();
+    assert_eq!(imported, keys);
+
+    let mut session = imported[0].session().unwrap();
+    assert_eq!(
+        session.decrypt(from_utf8(MESSAGE).unwrap()).unwrap().0,
+        b"Message"
+    );
+}
+
+#[test]
+fn wrong_passphrase_fails_the_mac() {
+    let file = export_room_keys(
+        &[room_key("!a:example.org")],
+        "passphrase",
+        ROUNDS,
+        &mut MockRandom::new(b'E', 0),
+    );
+
+    assert_eq!(
+        import_room_keys(&file, "wrong passphrase"),
+        Err(KeyExportError::BadMac)
+    );
+}
+
+#[test]
+fn malformed_files_are_rejected() {
+    let armour = |body: &str| {
+        format!(
+            "-----BEGIN MEGOLM SESSION DATA-----\n{}\n-----END MEGOLM SESSION DATA-----",
+            body
+        )
+    };
+
+    assert_eq!(
+        import_room_keys("AQID", "passphrase"),
+        Err(KeyExportError::InvalidFormat)
+    );
+    assert_eq!(
+        import_room_keys(&armour("AQID"), "passphrase"),
+        Err(KeyExportError::InvalidFormat)
+    );
+    assert_eq!(
+        import_room_keys(&armour("not base64!"), "passphrase"),
+        Err(KeyExportError::InvalidFormat)
+    );
+
+    let mut data = vec![0; 100];
+    data[0] = 2;
+    assert_eq!(
+        import_room_keys(&armour(&common::base64(&data)), "passphrase"),
+        Err(KeyExportError::UnsupportedVersion(2))
+    );
+}
+
+#[test]
+fn element_exports_decrypt() {
+    // The test vectors of matrix-react-sdk's MegolmExportEncryption. Their
+    // plaintexts aren't room keys, so only getting past the MAC shows that
+    // the keys are derived like Element does.
+    for (file, passphrase) in [
+        (
+            "-----BEGIN MEGOLM SESSION DATA-----
+            AXNhbHRzYWx0c2FsdHNhbHSIiIiIiIiIiIiIiIiIiIiIAAAACmIRUW2OjZ3L2l6j9h0lHlV3M2dx
+            cissyYBxjsfsAndErh065A8=
+            -----END MEGOLM SESSION DATA-----",
+            "password",
+        ),
+        (
+            "-----BEGIN MEGOLM SESSION DATA-----
+            AW1vcmVzYWx0bW9yZXNhbHT//////////wAAAAAAAAAAAAAD6KyBpe1Niv5M5NPm4ZATsJo5nghk
+            KYu63a0YQ5DRhUWEKk7CcMkrKnAUiZny
+            -----END MEGOLM SESSION DATA-----",
+            "betterpassword",
+        ),
+    ] {
+        assert_eq!(
+            import_room_keys(file, passphrase),
+            Err(KeyExportError::InvalidJson)
+        );
+        assert_eq!(
+            import_room_keys(file, "wrong passphrase"),
+            Err(KeyExportError::BadMac)
+        );
+    }
+}
+
+#[test]
+fn keys_of_unknown_algorithms_are_skipped() {
+    // Made with Python's hashlib and cryptography from the passphrase
+    // "password", with 10 rounds and a key of an unknown algorithm
+    let file = "-----BEGIN MEGOLM SESSION DATA-----
+        AXNhbHRzYWx0c2FsdHNhbHSIiIiIiIiIiAAAAAAAAAAAAAAACt1wXRA2QjMw6C5qNlfe7/FKM4OR
+        C+b4rIEi9r7fUpgKXc6pXJ9ddR0fhzhTKnnSXpwZ54T9qCzF1v7/HfrBYT1+Zk2w3MlGqd9I7DuD
+        QQWrhkAfdBUAt9G9Ie52Yo37kYQ3hPe9u1sORHvUUAjmwT8yiYHURrpSMA1iplfCl6XTSm41bvAw
+        iEYf89sfIczqnqg4R2OVc90n2pjnE7Fh68yAJ8nmVxq1QILjEBIWqqldk03wFj7fYy1gcpDUiy2k
+        Xtvv3tPJqFjPZtYGMcRlyK+amkhLuwSv0A==
+        -----END MEGOLM SESSION DATA-----";
+
+    let keys = import_room_keys(file, "password").unwrap();
+    assert_eq!(keys.len(), 1);
+    assert_eq!(keys[0].room_id, "!room:example.org");
+    assert_eq!(keys[0].sender_key, "sender");
+    assert_eq!(keys[0].session_id, "id");
+    assert_eq!(keys[0].session_key, "key");
+    assert!(keys[0].sender_claimed_keys.is_empty());
+    assert!(keys[0].forwarding_curve25519_key_chain.is_empty());
+}
@@ -0,0 +1,94 @@
+// Copyright 2020 Johannes Hayeß
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Runs the test suite of the vendored `libolm`, built by build.rs together
+//! with the library this crate links, so that a toolchain or flags which
+//! miscompile `libolm` get noticed.
+//!
+//! Only built with the `libolm-tests` feature:
+//!
+//! ```bash
+//! $ cargo test --features libolm-tests --test libolm
+//! ```
+
+#![cfg(all(
+    feature = "libolm-tests",
+    not(feature = "runtime-dlopen"),
+    not(target_arch = "wasm32")
+))]
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Names of the test executables, as listed in `olm/tests/CMakeLists.txt`.
+fn libolm_tests() -> Vec<&'static str> {
+    let cmake_lists = include_str!("../olm/tests/CMakeLists.txt");
+    let list = cmake_lists
+        .split("set(TEST_LIST")
+        .nth(1)
+        .and_then(|rest| rest.split(')').next())
+        .expect("olm/tests/CMakeLists.txt doesn't declare a TEST_LIST");
+
+    let mut tests: Vec<&str> = list.split_whitespace().collect();
+    // Appended to the list on all platforms but Windows DLL builds
+    tests.push("test_ratchet");
+    tests
+}
+
+/// Finds the executable of `test`, which multi-config generators like Xcode
+/// put into a directory named after the build profile.
+fn executable(tests_dir: &Path, test: &str) -> Option<PathBuf> {
+    let name = format!("{}{}", test, std::env::consts::EXE_SUFFIX);
+    let direct = tests_dir.join(&name);
+    if direct.is_file() {
+        return Some(direct);
+    }
+
+    std::fs::read_dir(tests_dir)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path().join(&name))
+        .find(|path| path.is_file())
+}
+
+#[test]
+fn libolm_test_suite_passes() {
+    let tests_dir = Path::new(env!("OLM_SYS_LIBOLM_TESTS_DIR"));
+    let mut failures = Vec::new();
+
+    for test in libolm_tests() {
+        let executable = executable(tests_dir, test)
+            .unwrap_or_else(|| panic!("{} wasn't built in {}", test, tests_dir.display()));
+        let output = Command::new(&executable)
+            .current_dir(tests_dir)
+            .output()
+            .unwrap_or_else(|error| panic!("Failed to run {}: {}", test, error));
+
+        if !output.status.success() {
+            failures.push(format!(
+                "{} ({}):\n{}{}",
+                test,
+                output.status,
+                String::from_utf8_lossy(&output.stdout),
+                String::from_utf8_lossy(&output.stderr)
+            ));
+        }
+    }
+
+    assert!(
+        failures.is_empty(),
+        "libolm tests failed:\n{}",
+        failures.join("\n")
+    );
+}
@@ -0,0 +1,162 @@
+// Copyright 2020 Johannes Hayeß
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Sanity checks against the `libolm` that this crate has been linked to.
+
+#![cfg(not(feature = "runtime-dlopen"))]
+
+use olm_sys::*;
+
+use std::ffi::c_void;
+
+/// Version of the vendored `libolm`, as declared in its CMake project.
+fn vendored_version() -> (u8, u8, u8) {
+    let cmake_lists = include_str!("../olm/CMakeLists.txt");
+    let version = cmake_lists
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("project(olm VERSION "))
+        .and_then(|rest| rest.split_whitespace().next())
+        .expect("olm/CMakeLists.txt doesn't declare a project version");
+
+    let mut parts = version.split('.').map(|part| part.parse().unwrap());
+    (
+        parts.next().unwrap(),
+        parts.next().unwrap(),
+        parts.next().unwrap(),
+    )
+}
+
+/// Allocates zeroed, 8-byte aligned memory of at least `size` bytes.
+fn alloc(size: usize) -> Vec<u64> {
+    vec![0; size.div_ceil(8)]
+}
+
+#[test]
+fn library_version_matches_vendored_sources() {
+    let (mut major, mut minor, mut patch) = (0, 0, 0);
+    unsafe { olm_get_library_version(&mut major, &mut minor, &mut patch) };
+
+    assert_eq!((major, minor, patch), vendored_version());
+}
+
+#[test]
+fn library_version_ignores_null_arguments() {
+    let mut major = 0;
+    unsafe { olm_get_library_version(&mut major, std::ptr::null_mut(), std::ptr::null_mut()) };
+
+    assert_eq!(major, vendored_version().0);
+}
+
+/// Allocates an object of `$size()` bytes, initialises it with `$init`,
+/// checks that its error code is `OLM_SUCCESS` and clears it again.
+macro_rules! check_object {
+    ($size:ident, $init:ident, $error_code:ident, $clear:ident) => {{
+        let size = unsafe { $size() };
+        assert!(size > 0, "{} returned 0", stringify!($size));
+
+        let mut memory = alloc(size);
+        let object = unsafe { $init(memory.as_mut_ptr() as *mut c_void) };
+        assert!(!object.is_null(), "{} returned null", stringify!($init));
+        assert_eq!(
+            unsafe { $error_code(object) },
+            OlmErrorCode_OLM_SUCCESS,
+            "{} left an error behind",
+            stringify!($init)
+        );
+        assert_eq!(unsafe { $clear(object) }, size);
+    }};
+}
+
+#[test]
+fn objects_allocate_and_initialise() {
+    check_object!(
+        olm_account_size,
+        olm_account,
+        olm_account_last_error_code,
+        olm_clear_account
+    );
+    check_object!(
+        olm_session_size,
+        olm_session,
+        olm_session_last_error_code,
+        olm_clear_session
+    );
+    check_object!(
+        olm_utility_size,
+        olm_utility,
+        olm_utility_last_error_code,
+        olm_clear_utility
+    );
+    check_object!(
+        olm_inbound_group_session_size,
+        olm_inbound_group_session,
+        olm_inbound_group_session_last_error_code,
+        olm_clear_inbound_group_session
+    );
+    check_object!(
+        olm_outbound_group_session_size,
+        olm_outbound_group_session,
+        olm_outbound_group_session_last_error_code,
+        olm_clear_outbound_group_session
+    );
+    check_object!(
+        olm_sas_size,
+        olm_sas,
+        olm_sas_last_error_code,
+        olm_clear_sas
+    );
+    check_object!(
+        olm_pk_encryption_size,
+        olm_pk_encryption,
+        olm_pk_encryption_last_error_code,
+        olm_clear_pk_encryption
+    );
+    check_object!(
+        olm_pk_decryption_size,
+        olm_pk_decryption,
+        olm_pk_decryption_last_error_code,
+        olm_clear_pk_decryption
+    );
+    check_object!(
+        olm_pk_signing_size,
+        olm_pk_signing,
+        olm_pk_signing_last_error_code,
+        olm_clear_pk_signing
+    );
+}
+
+#[test]
+fn account_can_be_created() {
+    let mut memory = alloc(unsafe { olm_account_size() });
+    let account = unsafe { olm_account(memory.as_mut_ptr() as *mut c_void) };
+
+    let mut random = vec![0x42; unsafe { olm_create_account_random_length(account) }];
+    let result =
+        unsafe { olm_create_account(account, random.as_mut_ptr() as *mut c_void, random.len()) };
+    assert_ne!(result, unsafe { olm_error() });
+
+    let mut keys = vec![0; unsafe { olm_account_identity_keys_length(account) }];
+    let written =
+        unsafe { olm_account_identity_keys(account, keys.as_mut_ptr() as *mut c_void, keys.len()) };
+    assert_eq!(written, keys.len());
+    assert!(keys.starts_with(b"{\"curve25519\":\""));
+
+    unsafe { olm_clear_account(account) };
+}
+
+#[test]
+fn linked_library_is_compatible() {
+    assert_eq!(olm_sys::version::library_version(), vendored_version());
+    assert_eq!(olm_sys::version::check_compatible(), Ok(()));
+}
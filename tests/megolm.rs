@@ -0,0 +1,88 @@
+// Copyright 2020 Johannes Hayeß
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Parsing Megolm messages with `MegolmMessage`.
+
+#![cfg(not(feature = "runtime-dlopen"))]
+
+mod common;
+
+use olm_sys::megolm::MegolmMessage;
+use olm_sys::*;
+
+/// The message of the export/import test in `olm/tests/test_group_session.cpp`.
+const MESSAGE: &str = "AwgAEhAcbh6UpbByoyZxufQ+h2B+8XHMjhR69G8F4+qjMaFlnIXusJZX3r8LnRORG9T3D\
+    XFdbVuvIWrLyRfm4i8QRbe8VPwGRFG57B1CtmxanuP8bHtnnYqlwPsD";
+
+#[test]
+fn parses_libolm_message() {
+    let message = MegolmMessage::parse(MESSAGE).unwrap();
+
+    assert_eq!(message.version(), 3);
+    assert_eq!(message.message_index(), Some(0));
+    assert_eq!(message.ciphertext().len(), 16);
+    assert_eq!(message.ciphertext(), &message.as_bytes()[5..21]);
+    assert_eq!(message.mac_offset(), 21);
+    assert_eq!(message.mac().len(), MegolmMessage::MAC_LENGTH);
+    assert_eq!(message.signature_offset(), 29);
+    assert_eq!(message.signature().len(), MegolmMessage::SIGNATURE_LENGTH);
+}
+
+#[test]
+fn parses_encoded_headers() {
+    let ciphertext = [0x42; 200];
+    let length = unsafe {
+        _olm_encode_group_message_length(
+            300,
+            ciphertext.len(),
+            MegolmMessage::MAC_LENGTH,
+            MegolmMessage::SIGNATURE_LENGTH,
+        )
+    };
+    let mut message = vec![0u8; length];
+    let mut ciphertext_ptr = std::ptr::null_mut();
+    let header_length = unsafe {
+        _olm_encode_group_message(
+            3,
+            300,
+            ciphertext.len(),
+            message.as_mut_ptr(),
+            &mut ciphertext_ptr,
+        )
+    };
+    unsafe { std::ptr::copy_nonoverlapping(ciphertext.as_ptr(), ciphertext_ptr, ciphertext.len()) };
+    assert_eq!(
+        header_length + MegolmMessage::MAC_LENGTH + MegolmMessage::SIGNATURE_LENGTH,
+        length
+    );
+
+    let parsed = MegolmMessage::parse(&common::base64(&message)).unwrap();
+    assert_eq!(parsed.version(), 3);
+    assert_eq!(parsed.message_index(), Some(300));
+    assert_eq!(parsed.ciphertext(), ciphertext);
+    assert_eq!(
+        parsed.signature_offset(),
+        header_length + MegolmMessage::MAC_LENGTH
+    );
+}
+
+#[test]
+fn rejects_malformed_messages() {
+    let error = MegolmMessage::parse("not base64!").unwrap_err();
+    assert_eq!(error.code(), OlmErrorCode_OLM_INVALID_BASE64);
+
+    // Too short to hold the MAC and signature
+    let error = MegolmMessage::parse("AwgAEhAc").unwrap_err();
+    assert_eq!(error.code(), OlmErrorCode_OLM_BAD_MESSAGE_FORMAT);
+}
@@ -0,0 +1,172 @@
+// Copyright 2020 Johannes Hayeß
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Inspecting Olm messages with `PreKeyMessage` and `OlmMessage`.
+
+#![cfg(not(feature = "runtime-dlopen"))]
+
+mod common;
+
+use common::{
+    alloc, create_inbound_session, create_outbound_session, decrypt, encrypt, key_at, MockRandom,
+};
+use olm_sys::account::Account;
+use olm_sys::message::{OlmMessage, PreKeyMessage};
+use olm_sys::*;
+
+use std::str;
+
+const PLAINTEXT: &[u8] = b"Hello, World";
+
+#[test]
+fn pre_key_message_names_the_keys_used() {
+    let mut random = MockRandom::new(b'A', 0);
+    let alice = Account::new(&mut random);
+    let mut bob = Account::new(&mut random);
+    bob.generate_one_time_keys(1, &mut random);
+    let alice_identity_keys = alice.identity_keys();
+    let bob_identity_keys = bob.identity_keys();
+    let bob_one_time_keys = bob.one_time_keys();
+
+    let mut session_memory = alloc(unsafe { olm_session_size() });
+    let (session, result) = create_outbound_session(
+        &mut session_memory,
+        alice.as_ptr(),
+        key_at(bob_identity_keys.as_bytes(), 15),
+        key_at(bob_one_time_keys.as_bytes(), 25),
+        &mut random,
+    );
+    assert_ne!(result, unsafe { olm_error() });
+
+    let (message_type, first) = encrypt(session, PLAINTEXT, &mut random);
+    assert_eq!(message_type, OLM_MESSAGE_TYPE_PRE_KEY);
+    let first = PreKeyMessage::parse(str::from_utf8(&first).unwrap()).unwrap();
+    assert_eq!(first.version(), 3);
+    assert_eq!(
+        first.one_time_key().as_bytes(),
+        key_at(bob_one_time_keys.as_bytes(), 25)
+    );
+    assert_eq!(
+        first.identity_key().as_bytes(),
+        key_at(alice_identity_keys.as_bytes(), 15)
+    );
+    assert_eq!(first.base_key().len(), 43);
+    assert_eq!(first.message().version(), 3);
+    assert_eq!(first.message().chain_index(), 0);
+    assert_eq!(first.message().mac().len(), OlmMessage::MAC_LENGTH);
+
+    // Until Bob replies, Alice keeps sending pre-key messages on one chain
+    let (message_type, second) = encrypt(session, PLAINTEXT, &mut random);
+    assert_eq!(message_type, OLM_MESSAGE_TYPE_PRE_KEY);
+    let second = PreKeyMessage::parse(str::from_utf8(&second).unwrap()).unwrap();
+    assert_eq!(second.base_key(), first.base_key());
+    assert_eq!(
+        second.message().ratchet_key(),
+        first.message().ratchet_key()
+    );
+    assert_eq!(second.message().chain_index(), 1);
+
+    unsafe { olm_clear_session(session) };
+}
+
+#[test]
+fn normal_message_names_the_ratchet_key() {
+    let mut random = MockRandom::new(b'B', 0);
+    let alice = Account::new(&mut random);
+    let mut bob = Account::new(&mut random);
+    bob.generate_one_time_keys(1, &mut random);
+    let bob_identity_keys = bob.identity_keys();
+    let bob_one_time_keys = bob.one_time_keys();
+
+    let mut alice_memory = alloc(unsafe { olm_session_size() });
+    let (alice_session, result) = create_outbound_session(
+        &mut alice_memory,
+        alice.as_ptr(),
+        key_at(bob_identity_keys.as_bytes(), 15),
+        key_at(bob_one_time_keys.as_bytes(), 25),
+        &mut random,
+    );
+    assert_ne!(result, unsafe { olm_error() });
+    let (_, pre_key_message) = encrypt(alice_session, PLAINTEXT, &mut random);
+
+    let mut bob_memory = alloc(unsafe { olm_session_size() });
+    let (bob_session, result) =
+        create_inbound_session(&mut bob_memory, bob.as_ptr(), &pre_key_message);
+    assert_ne!(result, unsafe { olm_error() });
+
+    // Bob only sends normal messages once he decrypted one of Alice's
+    assert_eq!(
+        decrypt(bob_session, OLM_MESSAGE_TYPE_PRE_KEY, &pre_key_message),
+        Ok(PLAINTEXT.to_vec())
+    );
+
+    let (message_type, reply) = encrypt(bob_session, PLAINTEXT, &mut random);
+    assert_eq!(message_type, OLM_MESSAGE_TYPE_MESSAGE);
+    let reply = OlmMessage::parse(str::from_utf8(&reply).unwrap()).unwrap();
+    let pre_key_message = PreKeyMessage::parse(str::from_utf8(&pre_key_message).unwrap()).unwrap();
+    assert_eq!(reply.version(), 3);
+    assert_eq!(reply.chain_index(), 0);
+    assert_ne!(reply.ratchet_key(), pre_key_message.message().ratchet_key());
+    assert!(!reply.ciphertext().is_empty());
+
+    unsafe {
+        olm_clear_session(alice_session);
+        olm_clear_session(bob_session);
+    }
+}
+
+#[test]
+fn unknown_fields_are_skipped() {
+    let key = [0x11; 32];
+    let mut inner = vec![3, 0o12, 32];
+    inner.extend_from_slice(&key);
+    inner.extend_from_slice(&[0o20, 0x96, 0x01]); // chain index 150
+    inner.extend_from_slice(&[0o50, 0xff, 0x7f]); // unknown varint field
+    inner.extend_from_slice(&[0o42, 2, 0xaa, 0xbb]);
+    inner.extend_from_slice(&[0; OlmMessage::MAC_LENGTH]);
+
+    let mut message = vec![3];
+    for tag in [0o12, 0o22, 0o32] {
+        message.extend_from_slice(&[tag, 32]);
+        message.extend_from_slice(&key);
+    }
+    message.extend_from_slice(&[0o62, 1, 0]); // unknown length delimited field
+    message.extend_from_slice(&[0o42, inner.len() as u8]);
+    message.extend_from_slice(&inner);
+
+    let message = PreKeyMessage::parse(&common::base64(&message)).unwrap();
+    assert_eq!(message.one_time_key(), common::base64(&key));
+    assert_eq!(message.message().chain_index(), 150);
+    assert_eq!(message.message().ciphertext(), [0xaa, 0xbb]);
+}
+
+#[test]
+fn malformed_messages_are_rejected() {
+    let error = PreKeyMessage::parse("not base64!").unwrap_err();
+    assert_eq!(error.code(), OlmErrorCode_OLM_INVALID_BASE64);
+
+    // Only a version byte
+    let error = PreKeyMessage::parse("Aw").unwrap_err();
+    assert_eq!(error.code(), OlmErrorCode_OLM_BAD_MESSAGE_FORMAT);
+    let error = OlmMessage::parse("AwAAAAAAAAAA").unwrap_err();
+    assert_eq!(error.code(), OlmErrorCode_OLM_BAD_MESSAGE_FORMAT);
+
+    // A ratchet key and a ciphertext, but no chain index
+    let mut message = vec![3, 0o12, 32];
+    message.extend_from_slice(&[0x11; 32]);
+    message.extend_from_slice(&[0o42, 2, 0xaa, 0xbb]);
+    message.extend_from_slice(&[0; OlmMessage::MAC_LENGTH]);
+    let error = OlmMessage::parse(&common::base64(&message)).unwrap_err();
+    assert_eq!(error.code(), OlmErrorCode_OLM_BAD_MESSAGE_FORMAT);
+}
@@ -0,0 +1,499 @@
+// Copyright 2020 Johannes Hayeß
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The test vectors of `olm/tests/test_olm.cpp`: accounts and sessions,
+//! driven through the raw bindings.
+
+#![cfg(not(feature = "runtime-dlopen"))]
+
+mod common;
+
+use common::{
+    add_junk_suffix_to_pickle, alloc, create_account, create_inbound_session,
+    create_outbound_session, decrypt, encrypt, generate_one_time_keys, identity_keys, key_at,
+    memory, one_time_keys, session_error, MockRandom,
+};
+use olm_sys::*;
+
+use std::ffi::{c_void, CStr};
+
+const PICKLE_KEY: &[u8] = b"secret_key";
+const PLAINTEXT: &[u8] = b"Hello, World";
+
+fn session_id(session: *mut OlmSession) -> Vec<u8> {
+    let mut id = vec![0; unsafe { olm_session_id_length(session) }];
+    let result = unsafe { olm_session_id(session, id.as_mut_ptr() as *mut c_void, id.len()) };
+    assert_ne!(result, unsafe { olm_error() });
+    id
+}
+
+fn matches_inbound_session(session: *mut OlmSession, message: &[u8]) -> usize {
+    let mut copy = message.to_vec();
+    unsafe { olm_matches_inbound_session(session, copy.as_mut_ptr() as *mut c_void, copy.len()) }
+}
+
+fn matches_inbound_session_from(
+    session: *mut OlmSession,
+    identity_key: &[u8],
+    message: &[u8],
+) -> usize {
+    let mut copy = message.to_vec();
+    unsafe {
+        olm_matches_inbound_session_from(
+            session,
+            identity_key.as_ptr() as *const c_void,
+            identity_key.len(),
+            copy.as_mut_ptr() as *mut c_void,
+            copy.len(),
+        )
+    }
+}
+
+#[test]
+fn pickle_account() {
+    let mut random = MockRandom::new(b'P', 0);
+    let mut account_memory = alloc(unsafe { olm_account_size() });
+    let account = create_account(&mut account_memory, &mut random);
+    generate_one_time_keys(account, 42, &mut random);
+
+    let pickle_length = unsafe { olm_pickle_account_length(account) };
+    let mut pickle1 = vec![0u8; pickle_length];
+    let result = unsafe {
+        olm_pickle_account(
+            account,
+            PICKLE_KEY.as_ptr() as *const c_void,
+            PICKLE_KEY.len(),
+            pickle1.as_mut_ptr() as *mut c_void,
+            pickle_length,
+        )
+    };
+    assert_eq!(result, pickle_length);
+
+    // Unpickling destroys the input
+    let mut pickle2 = pickle1.clone();
+    let mut account_memory2 = alloc(unsafe { olm_account_size() });
+    let account2 = unsafe { olm_account(memory(&mut account_memory2)) };
+    let result = unsafe {
+        olm_unpickle_account(
+            account2,
+            PICKLE_KEY.as_ptr() as *const c_void,
+            PICKLE_KEY.len(),
+            pickle2.as_mut_ptr() as *mut c_void,
+            pickle_length,
+        )
+    };
+    assert_ne!(result, unsafe { olm_error() });
+    assert_eq!(
+        unsafe { olm_pickle_account_length(account2) },
+        pickle_length
+    );
+    let result = unsafe {
+        olm_pickle_account(
+            account2,
+            PICKLE_KEY.as_ptr() as *const c_void,
+            PICKLE_KEY.len(),
+            pickle2.as_mut_ptr() as *mut c_void,
+            pickle_length,
+        )
+    };
+    assert_eq!(result, pickle_length);
+    assert_eq!(pickle1, pickle2);
+
+    let mut junk_pickle = add_junk_suffix_to_pickle(PICKLE_KEY, &pickle1, 1);
+    let result = unsafe {
+        olm_unpickle_account(
+            account,
+            PICKLE_KEY.as_ptr() as *const c_void,
+            PICKLE_KEY.len(),
+            junk_pickle.as_mut_ptr() as *mut c_void,
+            junk_pickle.len(),
+        )
+    };
+    assert_eq!(result, unsafe { olm_error() });
+    assert_eq!(
+        unsafe { olm_account_last_error_code(account) },
+        OlmErrorCode_OLM_PICKLE_EXTRA_DATA
+    );
+}
+
+#[test]
+fn old_account_pickle_is_rejected() {
+    // The old pickle format, which didn't use enough space for the Ed25519 key
+    let mut pickle = b"x3h9er86ygvq56pM1yesdAxZou4ResPQC9Rszk/fhEL9JY/umtZ2N/foL/SUgVXS\
+        v0IxHHZTafYjDdzJU9xr8dQeBoOTGfV9E/lCqDGBnIlu7SZndqjEKXtzGyQr4sP4\
+        K/A/8TOu9iK2hDFszy6xETiousHnHgh2ZGbRUh4pQx+YMm8ZdNZeRnwFGLnrWyf9\
+        O5TmXua1FcU"
+        .to_vec();
+
+    let mut account_memory = alloc(unsafe { olm_account_size() });
+    let account = unsafe { olm_account(memory(&mut account_memory)) };
+    let result = unsafe {
+        olm_unpickle_account(
+            account,
+            std::ptr::null(),
+            0,
+            pickle.as_mut_ptr() as *mut c_void,
+            pickle.len(),
+        )
+    };
+    assert_eq!(result, unsafe { olm_error() });
+    assert_eq!(
+        unsafe { CStr::from_ptr(olm_account_last_error(account)) }.to_bytes(),
+        b"BAD_LEGACY_ACCOUNT_PICKLE"
+    );
+}
+
+#[test]
+fn pickle_session() {
+    let mut random = MockRandom::new(b'P', 0);
+    let mut account_memory = alloc(unsafe { olm_account_size() });
+    let account = create_account(&mut account_memory, &mut random);
+
+    let identity_key = random.bytes(32);
+    let one_time_key = random.bytes(32);
+    let mut session_memory = alloc(unsafe { olm_session_size() });
+    // As in libolm's test the keys are raw bytes instead of base64, so this
+    // fails and a blank session gets pickled
+    let (session, _) = create_outbound_session(
+        &mut session_memory,
+        account,
+        &identity_key,
+        &one_time_key,
+        &mut random,
+    );
+
+    let pickle_length = unsafe { olm_pickle_session_length(session) };
+    let mut pickle1 = vec![0u8; pickle_length];
+    let result = unsafe {
+        olm_pickle_session(
+            session,
+            PICKLE_KEY.as_ptr() as *const c_void,
+            PICKLE_KEY.len(),
+            pickle1.as_mut_ptr() as *mut c_void,
+            pickle_length,
+        )
+    };
+    assert_eq!(result, pickle_length);
+
+    let mut pickle2 = pickle1.clone();
+    let mut session_memory2 = alloc(unsafe { olm_session_size() });
+    let session2 = unsafe { olm_session(memory(&mut session_memory2)) };
+    let result = unsafe {
+        olm_unpickle_session(
+            session2,
+            PICKLE_KEY.as_ptr() as *const c_void,
+            PICKLE_KEY.len(),
+            pickle2.as_mut_ptr() as *mut c_void,
+            pickle_length,
+        )
+    };
+    assert_ne!(result, unsafe { olm_error() });
+    assert_eq!(
+        unsafe { olm_pickle_session_length(session2) },
+        pickle_length
+    );
+    let result = unsafe {
+        olm_pickle_session(
+            session2,
+            PICKLE_KEY.as_ptr() as *const c_void,
+            PICKLE_KEY.len(),
+            pickle2.as_mut_ptr() as *mut c_void,
+            pickle_length,
+        )
+    };
+    assert_eq!(result, pickle_length);
+    assert_eq!(pickle1, pickle2);
+
+    let mut junk_pickle = add_junk_suffix_to_pickle(PICKLE_KEY, &pickle1, 1);
+    let result = unsafe {
+        olm_unpickle_session(
+            session,
+            PICKLE_KEY.as_ptr() as *const c_void,
+            PICKLE_KEY.len(),
+            junk_pickle.as_mut_ptr() as *mut c_void,
+            junk_pickle.len(),
+        )
+    };
+    assert_eq!(result, unsafe { olm_error() });
+    assert_eq!(
+        unsafe { olm_session_last_error_code(session) },
+        OlmErrorCode_OLM_PICKLE_EXTRA_DATA
+    );
+}
+
+#[test]
+fn loopback() {
+    let mut random_a = MockRandom::new(b'A', 0x00);
+    let mut random_b = MockRandom::new(b'B', 0x80);
+
+    let mut a_account_memory = alloc(unsafe { olm_account_size() });
+    let a_account = create_account(&mut a_account_memory, &mut random_a);
+    let mut b_account_memory = alloc(unsafe { olm_account_size() });
+    let b_account = create_account(&mut b_account_memory, &mut random_b);
+    generate_one_time_keys(b_account, 42, &mut random_b);
+
+    let a_id_keys = identity_keys(a_account);
+    let b_id_keys = identity_keys(b_account);
+    let b_ot_keys = one_time_keys(b_account);
+
+    let mut a_session_memory = alloc(unsafe { olm_session_size() });
+    let (a_session, result) = create_outbound_session(
+        &mut a_session_memory,
+        a_account,
+        key_at(&b_id_keys, 15),
+        key_at(&b_ot_keys, 25),
+        &mut random_a,
+    );
+    assert_ne!(result, unsafe { olm_error() });
+
+    let (message_type, message_1) = encrypt(a_session, PLAINTEXT, &mut random_a);
+    assert_eq!(message_type, 0);
+
+    let mut b_session_memory = alloc(unsafe { olm_session_size() });
+    let (b_session, result) = create_inbound_session(&mut b_session_memory, b_account, &message_1);
+    assert_ne!(result, unsafe { olm_error() });
+
+    // The inbound session matches the message it was created from, and the
+    // key the message is supposed to be from, but not a different one
+    assert_eq!(matches_inbound_session(b_session, &message_1), 1);
+    assert_eq!(
+        matches_inbound_session_from(b_session, key_at(&a_id_keys, 15), &message_1),
+        1
+    );
+    assert_eq!(
+        matches_inbound_session_from(b_session, key_at(&b_id_keys, 15), &message_1),
+        0
+    );
+
+    assert_eq!(decrypt(b_session, 0, &message_1).unwrap(), PLAINTEXT);
+
+    let (message_type, message_2) = encrypt(b_session, PLAINTEXT, &mut random_b);
+    assert_eq!(message_type, 1);
+    assert_eq!(decrypt(a_session, 1, &message_2).unwrap(), PLAINTEXT);
+
+    // A message can only be decrypted once
+    assert!(decrypt(a_session, 1, &message_2).is_err());
+
+    assert_eq!(session_id(a_session), session_id(b_session));
+}
+
+#[test]
+fn more_messages() {
+    let mut random_a = MockRandom::new(b'A', 0x00);
+    let mut random_b = MockRandom::new(b'B', 0x80);
+
+    let mut a_account_memory = alloc(unsafe { olm_account_size() });
+    let a_account = create_account(&mut a_account_memory, &mut random_a);
+    let mut b_account_memory = alloc(unsafe { olm_account_size() });
+    let b_account = create_account(&mut b_account_memory, &mut random_b);
+    generate_one_time_keys(b_account, 42, &mut random_b);
+
+    let b_id_keys = identity_keys(b_account);
+    let b_ot_keys = one_time_keys(b_account);
+
+    let mut a_session_memory = alloc(unsafe { olm_session_size() });
+    let (a_session, result) = create_outbound_session(
+        &mut a_session_memory,
+        a_account,
+        key_at(&b_id_keys, 15),
+        key_at(&b_ot_keys, 25),
+        &mut random_a,
+    );
+    assert_ne!(result, unsafe { olm_error() });
+
+    let (message_type, message_1) = encrypt(a_session, PLAINTEXT, &mut random_a);
+    assert_eq!(message_type, 0);
+
+    let mut b_session_memory = alloc(unsafe { olm_session_size() });
+    let (b_session, _) = create_inbound_session(&mut b_session_memory, b_account, &message_1);
+    assert_eq!(decrypt(b_session, 0, &message_1).unwrap(), PLAINTEXT);
+
+    for _ in 0..8 {
+        let (message_type, message) = encrypt(a_session, PLAINTEXT, &mut random_a);
+        assert_eq!(
+            decrypt(b_session, message_type, &message).unwrap(),
+            PLAINTEXT
+        );
+
+        let (message_type, message) = encrypt(b_session, PLAINTEXT, &mut random_b);
+        assert_eq!(
+            decrypt(a_session, message_type, &message).unwrap(),
+            PLAINTEXT
+        );
+    }
+}
+
+#[test]
+fn fallback_key() {
+    let mut random_a = MockRandom::new(b'A', 0x00);
+    let mut random_b = MockRandom::new(b'B', 0x80);
+
+    let mut a_account_memory = alloc(unsafe { olm_account_size() });
+    let a_account = create_account(&mut a_account_memory, &mut random_a);
+    let mut b_account_memory = alloc(unsafe { olm_account_size() });
+    let b_account = create_account(&mut b_account_memory, &mut random_b);
+
+    let a_id_keys = identity_keys(a_account);
+    let b_id_keys = identity_keys(b_account);
+
+    let mut fallback_random =
+        random_b.bytes(unsafe { olm_account_generate_fallback_key_random_length(b_account) });
+    unsafe {
+        olm_account_generate_fallback_key(
+            b_account,
+            fallback_random.as_mut_ptr() as *mut c_void,
+            fallback_random.len(),
+        )
+    };
+    let mut b_fallback_key =
+        vec![0; unsafe { olm_account_unpublished_fallback_key_length(b_account) }];
+    unsafe {
+        olm_account_unpublished_fallback_key(
+            b_account,
+            b_fallback_key.as_mut_ptr() as *mut c_void,
+            b_fallback_key.len(),
+        )
+    };
+
+    // A session with the fallback key works like one with a one-time key
+    let mut a_session1_memory = alloc(unsafe { olm_session_size() });
+    let (a_session1, result) = create_outbound_session(
+        &mut a_session1_memory,
+        a_account,
+        key_at(&b_id_keys, 15),
+        key_at(&b_fallback_key, 25),
+        &mut random_a,
+    );
+    assert_ne!(result, unsafe { olm_error() });
+    let (message_type, message_1) = encrypt(a_session1, PLAINTEXT, &mut random_a);
+    assert_eq!(message_type, 0);
+
+    let mut b_session1_memory = alloc(unsafe { olm_session_size() });
+    let (b_session1, _) = create_inbound_session(&mut b_session1_memory, b_account, &message_1);
+    assert_eq!(matches_inbound_session(b_session1, &message_1), 1);
+    assert_eq!(
+        matches_inbound_session_from(b_session1, key_at(&a_id_keys, 15), &message_1),
+        1
+    );
+    assert_eq!(
+        matches_inbound_session_from(b_session1, key_at(&b_id_keys, 15), &message_1),
+        0
+    );
+    assert_eq!(decrypt(b_session1, 0, &message_1).unwrap(), PLAINTEXT);
+
+    // After generating a new fallback key, the old one is still usable
+    random_b.fill(&mut fallback_random);
+    unsafe {
+        olm_account_generate_fallback_key(
+            b_account,
+            fallback_random.as_mut_ptr() as *mut c_void,
+            fallback_random.len(),
+        )
+    };
+
+    let mut a_session2_memory = alloc(unsafe { olm_session_size() });
+    let (a_session2, result) = create_outbound_session(
+        &mut a_session2_memory,
+        a_account,
+        key_at(&b_id_keys, 15),
+        key_at(&b_fallback_key, 25),
+        &mut random_a,
+    );
+    assert_ne!(result, unsafe { olm_error() });
+    let (message_type, message_2) = encrypt(a_session2, PLAINTEXT, &mut random_a);
+    assert_eq!(message_type, 0);
+
+    let mut b_session2_memory = alloc(unsafe { olm_session_size() });
+    let (b_session2, result) =
+        create_inbound_session(&mut b_session2_memory, b_account, &message_2);
+    assert_ne!(result, unsafe { olm_error() });
+    assert_eq!(matches_inbound_session(b_session2, &message_2), 1);
+    assert_eq!(
+        matches_inbound_session_from(b_session2, key_at(&a_id_keys, 15), &message_2),
+        1
+    );
+    assert_eq!(
+        matches_inbound_session_from(b_session2, key_at(&b_id_keys, 15), &message_2),
+        0
+    );
+    assert_eq!(decrypt(b_session2, 0, &message_2).unwrap(), PLAINTEXT);
+
+    // Once the old fallback key is forgotten, sessions with it fail
+    unsafe { olm_account_forget_old_fallback_key(b_account) };
+
+    let mut a_session3_memory = alloc(unsafe { olm_session_size() });
+    let (a_session3, result) = create_outbound_session(
+        &mut a_session3_memory,
+        a_account,
+        key_at(&b_id_keys, 15),
+        key_at(&b_fallback_key, 25),
+        &mut random_a,
+    );
+    assert_ne!(result, unsafe { olm_error() });
+    let (message_type, message_3) = encrypt(a_session3, PLAINTEXT, &mut random_a);
+    assert_eq!(message_type, 0);
+
+    let mut b_session3_memory = alloc(unsafe { olm_session_size() });
+    let (b_session3, result) =
+        create_inbound_session(&mut b_session3_memory, b_account, &message_3);
+    assert_eq!(result, unsafe { olm_error() });
+    assert_eq!(session_error(b_session3), "BAD_MESSAGE_KEY_ID");
+}
+
+#[test]
+fn old_account_v3_unpickles() {
+    let mut pickle = b"0mSqVn3duHffbhaTbFgW+4JPlcRoqT7z0x4mQ72N+g+eSAk5sgcWSoDzKpMazgcB\
+        46ItEpChthVHTGRA6PD3dly0dUs4ji7VtWTa+1tUv1UbxP92uYf1Ae3fomX0yAoH\
+        OjSrz1+RmuXr+At8jsmsf260sKvhB6LnI3qYsrw6AAtpgk5d5xZd66sLxvvYUuai\
+        +SmmcmT0bHosLTuDiiB9amBvPKkUKtKZmaEAl5ULrgnJygp1/FnwzVfSrw6PBSX6\
+        ZaUEZHZGX1iI6/WjbHqlTQeOQjtaSsPaL5XXpteS9dFsuaANAj+8ks7Ut2Hwg/JP\
+        Ih/ERYBwiMh9Mt3zSAG0NkvgUkcdipKxoSNZ6t+TkqZrN6jG6VCbx+4YpJO24iJb\
+        ShZy8n79aePIgIsxX94ycsTq1ic38sCRSkWGVbCSRkPloHW7ZssLHA"
+        .to_vec();
+
+    let mut account_memory = alloc(unsafe { olm_account_size() });
+    let account = unsafe { olm_account(memory(&mut account_memory)) };
+    let result = unsafe {
+        olm_unpickle_account(
+            account,
+            std::ptr::null(),
+            0,
+            pickle.as_mut_ptr() as *mut c_void,
+            pickle.len(),
+        )
+    };
+    assert_ne!(result, unsafe { olm_error() });
+
+    let mut fallback = vec![0u8; unsafe { olm_account_fallback_key_length(account) }];
+    let length = unsafe {
+        olm_account_fallback_key(
+            account,
+            fallback.as_mut_ptr() as *mut c_void,
+            fallback.len(),
+        )
+    };
+    assert_eq!(
+        &fallback[..length],
+        b"{\"curve25519\":{\"AAAAAQ\":\"dr98y6VOWt6lJaQgFVZeWY2ky76mga9MEMbdItJTdng\"}}"
+    );
+
+    let length = unsafe {
+        olm_account_unpublished_fallback_key(
+            account,
+            fallback.as_mut_ptr() as *mut c_void,
+            fallback.len(),
+        )
+    };
+    assert_eq!(&fallback[..length], b"{\"curve25519\":{}}");
+}
@@ -0,0 +1,83 @@
+// Copyright 2020 Johannes Hayeß
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The test vector of `olm/tests/test_olm_signature.cpp`: account signatures,
+//! verified through the raw bindings.
+
+#![cfg(not(feature = "runtime-dlopen"))]
+
+mod common;
+
+use common::{alloc, memory, MockRandom};
+use olm_sys::*;
+
+use std::ffi::c_void;
+
+#[test]
+fn account_signature_verifies() {
+    let mut random = MockRandom::new(b'A', 0x00);
+
+    let mut account_memory = alloc(unsafe { olm_account_size() });
+    let account = unsafe { olm_account(memory(&mut account_memory)) };
+    let mut bytes = random.bytes(unsafe { olm_create_account_random_length(account) });
+    let result =
+        unsafe { olm_create_account(account, bytes.as_mut_ptr() as *mut c_void, bytes.len()) };
+    assert_ne!(result, unsafe { olm_error() });
+
+    let message = b"Hello, World";
+    let mut signature = vec![0u8; unsafe { olm_account_signature_length(account) }];
+    let result = unsafe {
+        olm_account_sign(
+            account,
+            message.as_ptr() as *const c_void,
+            message.len(),
+            signature.as_mut_ptr() as *mut c_void,
+            signature.len(),
+        )
+    };
+    assert_ne!(result, unsafe { olm_error() });
+
+    let mut identity_keys = vec![0u8; unsafe { olm_account_identity_keys_length(account) }];
+    let result = unsafe {
+        olm_account_identity_keys(
+            account,
+            identity_keys.as_mut_ptr() as *mut c_void,
+            identity_keys.len(),
+        )
+    };
+    assert_ne!(result, unsafe { olm_error() });
+
+    // Check that nothing is shared with the account
+    unsafe { olm_clear_account(account) };
+    drop(account_memory);
+
+    let mut utility_memory = alloc(unsafe { olm_utility_size() });
+    let utility = unsafe { olm_utility(memory(&mut utility_memory)) };
+    // The Ed25519 key starts 71 bytes into the identity keys' JSON
+    let ed25519_key = &identity_keys[71..71 + 43];
+    let result = unsafe {
+        olm_ed25519_verify(
+            utility,
+            ed25519_key.as_ptr() as *const c_void,
+            ed25519_key.len(),
+            message.as_ptr() as *const c_void,
+            message.len(),
+            signature.as_mut_ptr() as *mut c_void,
+            signature.len(),
+        )
+    };
+    assert_ne!(result, unsafe { olm_error() });
+
+    unsafe { olm_clear_utility(utility) };
+}
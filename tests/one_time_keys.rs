@@ -0,0 +1,118 @@
+// Copyright 2020 Johannes Hayeß
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Topping up one-time keys and rotating the fallback key.
+
+#![cfg(all(feature = "matrix", not(feature = "runtime-dlopen")))]
+
+mod common;
+
+use common::MockRandom;
+use olm_sys::account::Account;
+use olm_sys::one_time_keys::{FallbackKeySchedule, OneTimeKeyManager};
+
+use std::time::Duration;
+
+const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+fn manager() -> OneTimeKeyManager {
+    OneTimeKeyManager::new(
+        "@alice:example.org",
+        "DEVICE",
+        FallbackKeySchedule {
+            rotation_period: 7 * DAY,
+            rotate_after_use: DAY / 24,
+            forget_old_after: DAY,
+        },
+    )
+}
+
+fn count(upload: &serde_json::Value, keys: &str) -> usize {
+    upload[keys].as_object().unwrap().len()
+}
+
+#[test]
+fn server_is_topped_up_to_half_of_the_maximum() {
+    let mut random = MockRandom::new(b'O', 0);
+    let mut account = Account::new(&mut random);
+    let mut manager = manager();
+    let half = account.max_number_of_one_time_keys() / 2;
+
+    let upload = manager.keys_to_upload(&mut account, 0, DAY, &mut random);
+    assert_eq!(count(&upload, "one_time_keys"), half);
+
+    // A failed upload is retried without generating more keys
+    let retry = manager.keys_to_upload(&mut account, 0, DAY, &mut random);
+    assert_eq!(retry, upload);
+    manager.mark_published(&mut account, DAY);
+
+    let upload = manager.keys_to_upload(&mut account, half, DAY, &mut random);
+    assert_eq!(count(&upload, "one_time_keys"), 0);
+    let upload = manager.keys_to_upload(&mut account, half - 5, DAY, &mut random);
+    assert_eq!(count(&upload, "one_time_keys"), 5);
+    manager.mark_published(&mut account, DAY);
+
+    // A server with more keys than that gets none
+    assert_eq!(OneTimeKeyManager::keys_to_generate(&account, 2 * half), 0);
+}
+
+#[test]
+fn fallback_key_rotates_on_schedule() {
+    let mut random = MockRandom::new(b'F', 0);
+    let mut account = Account::new(&mut random);
+    let mut manager = manager();
+
+    let upload = manager.keys_to_upload(&mut account, 50, DAY, &mut random);
+    assert_eq!(count(&upload, "fallback_keys"), 1);
+    manager.mark_published(&mut account, DAY);
+    let one_key = account.pickle(b"key").len();
+
+    let upload = manager.keys_to_upload(&mut account, 50, 7 * DAY, &mut random);
+    assert_eq!(count(&upload, "fallback_keys"), 0);
+
+    let upload = manager.keys_to_upload(&mut account, 50, 8 * DAY, &mut random);
+    assert_eq!(count(&upload, "fallback_keys"), 1);
+    manager.mark_published(&mut account, 8 * DAY);
+    // The previous fallback key is pickled too while it's kept
+    let two_keys = account.pickle(b"key").len();
+    assert!(two_keys > one_key);
+
+    manager.keys_to_upload(&mut account, 50, 8 * DAY + DAY / 2, &mut random);
+    assert_eq!(account.pickle(b"key").len(), two_keys);
+    let upload = manager.keys_to_upload(&mut account, 50, 9 * DAY, &mut random);
+    assert_eq!(count(&upload, "fallback_keys"), 0);
+    assert_eq!(account.pickle(b"key").len(), one_key);
+}
+
+#[test]
+fn endless_periods_never_expire() {
+    let mut random = MockRandom::new(b'N', 0);
+    let mut account = Account::new(&mut random);
+    let mut manager = OneTimeKeyManager::new(
+        "@alice:example.org",
+        "DEVICE",
+        FallbackKeySchedule {
+            rotation_period: Duration::MAX,
+            rotate_after_use: Duration::MAX,
+            forget_old_after: Duration::MAX,
+        },
+    );
+
+    let upload = manager.keys_to_upload(&mut account, 50, DAY, &mut random);
+    assert_eq!(count(&upload, "fallback_keys"), 1);
+    manager.mark_published(&mut account, DAY);
+
+    let upload = manager.keys_to_upload(&mut account, 50, Duration::MAX, &mut random);
+    assert_eq!(count(&upload, "fallback_keys"), 0);
+}
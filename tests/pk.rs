@@ -0,0 +1,268 @@
+// Copyright 2020 Johannes Hayeß
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The test vectors of `olm/tests/test_pk.cpp`: public key encryption and
+//! signing, driven through the raw bindings.
+
+#![cfg(not(feature = "runtime-dlopen"))]
+
+mod common;
+
+use common::{add_junk_suffix_to_pickle, alloc, memory};
+use olm_sys::*;
+
+use std::ffi::c_void;
+
+const ALICE_PRIVATE: [u8; 32] = [
+    0x77, 0x07, 0x6D, 0x0A, 0x73, 0x18, 0xA5, 0x7D, 0x3C, 0x16, 0xC1, 0x72, 0x51, 0xB2, 0x66, 0x45,
+    0xDF, 0x4C, 0x2F, 0x87, 0xEB, 0xC0, 0x99, 0x2A, 0xB1, 0x77, 0xFB, 0xA5, 0x1D, 0xB9, 0x2C, 0x2A,
+];
+const ALICE_PUBLIC: &[u8] = b"hSDwCYkwp1R0i33ctD73Wg2/Og0mOBr066SpjqqbTmo";
+
+const BOB_PRIVATE: [u8; 32] = [
+    0x5D, 0xAB, 0x08, 0x7E, 0x62, 0x4A, 0x8A, 0x4B, 0x79, 0xE1, 0x7F, 0x8B, 0x83, 0x80, 0x0E, 0xE6,
+    0x6F, 0x3B, 0xB1, 0x29, 0x26, 0x18, 0xB6, 0xFD, 0x1C, 0x2F, 0x8B, 0x27, 0xFF, 0x88, 0xE0, 0xEB,
+];
+const BOB_PUBLIC: &[u8] = b"3p7bfXt9wbTTW2HC7OQ1Nz+DQ8hbeGdNrfx+FG+IK08";
+
+const PICKLE_KEY: &[u8] = b"secret_key";
+const PLAINTEXT: &[u8] = b"This is a test";
+
+/// Sets up a decryption object with Alice's key, returning it and the
+/// public key.
+fn alice_decryption(memory: &mut [u64]) -> (*mut OlmPkDecryption, Vec<u8>) {
+    let decryption = unsafe { olm_pk_decryption(common::memory(memory)) };
+    let mut public_key = vec![0; unsafe { olm_pk_key_length() }];
+    let result = unsafe {
+        olm_pk_key_from_private(
+            decryption,
+            public_key.as_mut_ptr() as *mut c_void,
+            public_key.len(),
+            ALICE_PRIVATE.as_ptr() as *const c_void,
+            ALICE_PRIVATE.len(),
+        )
+    };
+    assert_ne!(result, unsafe { olm_error() });
+
+    (decryption, public_key)
+}
+
+/// Decrypts `ciphertext`, which libolm destroys, so only a copy is passed.
+fn decrypt(
+    decryption: *mut OlmPkDecryption,
+    ephemeral_key: &[u8],
+    mac: &[u8],
+    ciphertext: &[u8],
+) -> Vec<u8> {
+    let mut ciphertext = ciphertext.to_vec();
+    let mut plaintext =
+        vec![0; unsafe { olm_pk_max_plaintext_length(decryption, ciphertext.len()) }];
+    let length = unsafe {
+        olm_pk_decrypt(
+            decryption,
+            ephemeral_key.as_ptr() as *const c_void,
+            ephemeral_key.len(),
+            mac.as_ptr() as *const c_void,
+            mac.len(),
+            ciphertext.as_mut_ptr() as *mut c_void,
+            ciphertext.len(),
+            plaintext.as_mut_ptr() as *mut c_void,
+            plaintext.len(),
+        )
+    };
+    assert_ne!(length, unsafe { olm_error() });
+
+    plaintext.truncate(length);
+    plaintext
+}
+
+#[test]
+fn encryption_decryption() {
+    let mut decryption_memory = alloc(unsafe { olm_pk_decryption_size() });
+    let (decryption, public_key) = alice_decryption(&mut decryption_memory);
+    assert_eq!(public_key, ALICE_PUBLIC);
+
+    let mut private_key = vec![0; unsafe { olm_pk_private_key_length() }];
+    unsafe {
+        olm_pk_get_private_key(
+            decryption,
+            private_key.as_mut_ptr() as *mut c_void,
+            private_key.len(),
+        )
+    };
+    assert_eq!(private_key, ALICE_PRIVATE);
+
+    let mut encryption_memory = alloc(unsafe { olm_pk_encryption_size() });
+    let encryption = unsafe { olm_pk_encryption(memory(&mut encryption_memory)) };
+    unsafe {
+        olm_pk_encryption_set_recipient_key(
+            encryption,
+            public_key.as_ptr() as *const c_void,
+            public_key.len(),
+        )
+    };
+
+    let mut ciphertext = vec![0; unsafe { olm_pk_ciphertext_length(encryption, PLAINTEXT.len()) }];
+    let mut mac = vec![0; unsafe { olm_pk_mac_length(encryption) }];
+    let mut ephemeral_key = vec![0; unsafe { olm_pk_key_length() }];
+    let result = unsafe {
+        olm_pk_encrypt(
+            encryption,
+            PLAINTEXT.as_ptr() as *const c_void,
+            PLAINTEXT.len(),
+            ciphertext.as_mut_ptr() as *mut c_void,
+            ciphertext.len(),
+            mac.as_mut_ptr() as *mut c_void,
+            mac.len(),
+            ephemeral_key.as_mut_ptr() as *mut c_void,
+            ephemeral_key.len(),
+            BOB_PRIVATE.as_ptr() as *const c_void,
+            BOB_PRIVATE.len(),
+        )
+    };
+    assert_ne!(result, unsafe { olm_error() });
+    assert_eq!(ephemeral_key, BOB_PUBLIC);
+
+    assert_eq!(
+        decrypt(decryption, &ephemeral_key, &mac, &ciphertext),
+        PLAINTEXT
+    );
+}
+
+#[test]
+fn decryption_pickling() {
+    let mut decryption_memory = alloc(unsafe { olm_pk_decryption_size() });
+    let (decryption, mut public_key) = alice_decryption(&mut decryption_memory);
+
+    let pickle_length = unsafe { olm_pickle_pk_decryption_length(decryption) };
+    let mut pickle = vec![0u8; pickle_length];
+    let result = unsafe {
+        olm_pickle_pk_decryption(
+            decryption,
+            PICKLE_KEY.as_ptr() as *const c_void,
+            PICKLE_KEY.len(),
+            pickle.as_mut_ptr() as *mut c_void,
+            pickle.len(),
+        )
+    };
+    assert_eq!(result, pickle_length);
+    assert_eq!(
+        pickle,
+        b"qx37WTQrjZLz5tId/uBX9B3/okqAbV1ofl9UnHKno1eipByCpXleAAlAZoJgYnCDOQZDQWzo3luTSfkF9pU1mOIL\
+          Cbbouubs6TVeDyPfgGD9i86J8irHjA"
+    );
+
+    unsafe { olm_clear_pk_decryption(decryption) };
+    public_key.fill(0);
+
+    // Unpickling destroys the pickle
+    let mut copy = pickle.clone();
+    let result = unsafe {
+        olm_unpickle_pk_decryption(
+            decryption,
+            PICKLE_KEY.as_ptr() as *const c_void,
+            PICKLE_KEY.len(),
+            copy.as_mut_ptr() as *mut c_void,
+            copy.len(),
+            public_key.as_mut_ptr() as *mut c_void,
+            public_key.len(),
+        )
+    };
+    assert_ne!(result, unsafe { olm_error() });
+    assert_eq!(public_key, ALICE_PUBLIC);
+
+    let mut junk_pickle = add_junk_suffix_to_pickle(PICKLE_KEY, &pickle, 1);
+    let result = unsafe {
+        olm_unpickle_pk_decryption(
+            decryption,
+            PICKLE_KEY.as_ptr() as *const c_void,
+            PICKLE_KEY.len(),
+            junk_pickle.as_mut_ptr() as *mut c_void,
+            junk_pickle.len(),
+            public_key.as_mut_ptr() as *mut c_void,
+            public_key.len(),
+        )
+    };
+    assert_eq!(result, unsafe { olm_error() });
+    assert_eq!(
+        unsafe { olm_pk_decryption_last_error_code(decryption) },
+        OlmErrorCode_OLM_PICKLE_EXTRA_DATA
+    );
+
+    // A message encrypted to Alice's key by libolm's test
+    assert_eq!(
+        decrypt(
+            decryption,
+            BOB_PUBLIC,
+            b"zpzU6BkZcNI",
+            b"ntk49j/KozVFtSqJXhCejg"
+        ),
+        PLAINTEXT
+    );
+}
+
+#[test]
+fn signing() {
+    let mut signing_memory = alloc(unsafe { olm_pk_signing_size() });
+    let signing = unsafe { olm_pk_signing(memory(&mut signing_memory)) };
+
+    let mut public_key = vec![0u8; unsafe { olm_pk_signing_public_key_length() }];
+    let result = unsafe {
+        olm_pk_signing_key_from_seed(
+            signing,
+            public_key.as_mut_ptr() as *mut c_void,
+            public_key.len(),
+            ALICE_PRIVATE.as_ptr() as *const c_void,
+            ALICE_PRIVATE.len(),
+        )
+    };
+    assert_ne!(result, unsafe { olm_error() });
+
+    let message: &[u8] = b"We hold these truths to be self-evident, that all men are created \
+        equal, that they are endowed by their Creator with certain unalienable Rights, that \
+        among these are Life, Liberty and the pursuit of Happiness.";
+    let mut signature = vec![0u8; unsafe { olm_pk_signature_length() }];
+    let result = unsafe {
+        olm_pk_sign(
+            signing,
+            message.as_ptr(),
+            message.len(),
+            signature.as_mut_ptr(),
+            signature.len(),
+        )
+    };
+    assert_ne!(result, unsafe { olm_error() });
+
+    let mut utility_memory = alloc(unsafe { olm_utility_size() });
+    let utility = unsafe { olm_utility(memory(&mut utility_memory)) };
+    let verify = |signature: &mut [u8]| unsafe {
+        olm_ed25519_verify(
+            utility,
+            public_key.as_ptr() as *const c_void,
+            public_key.len(),
+            message.as_ptr() as *const c_void,
+            message.len(),
+            signature.as_mut_ptr() as *mut c_void,
+            signature.len(),
+        )
+    };
+
+    assert_eq!(verify(&mut signature.clone()), 0);
+
+    signature[5] = b'm';
+    assert_eq!(verify(&mut signature), unsafe { olm_error() });
+
+    unsafe { olm_clear_utility(utility) };
+    unsafe { olm_clear_pk_signing(signing) };
+}
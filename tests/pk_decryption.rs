@@ -0,0 +1,71 @@
+// Copyright 2020 Johannes Hayeß
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The safe `PkEncryption` and `PkDecryption` wrappers.
+
+#![cfg(not(feature = "runtime-dlopen"))]
+
+mod common;
+
+use common::MockRandom;
+use olm_sys::pk::{PkDecryption, PkEncryption};
+use olm_sys::*;
+
+/// Alice's key pair of `olm/tests/test_pk.cpp`.
+const ALICE_PRIVATE: [u8; 32] = [
+    0x77, 0x07, 0x6D, 0x0A, 0x73, 0x18, 0xA5, 0x7D, 0x3C, 0x16, 0xC1, 0x72, 0x51, 0xB2, 0x66, 0x45,
+    0xDF, 0x4C, 0x2F, 0x87, 0xEB, 0xC0, 0x99, 0x2A, 0xB1, 0x77, 0xFB, 0xA5, 0x1D, 0xB9, 0x2C, 0x2A,
+];
+const ALICE_PUBLIC: &str = "hSDwCYkwp1R0i33ctD73Wg2/Og0mOBr066SpjqqbTmo";
+
+#[test]
+fn private_keys_restore_the_key_pair() {
+    let decryption = PkDecryption::from_private_key(&ALICE_PRIVATE).unwrap();
+    assert_eq!(decryption.public_key(), ALICE_PUBLIC);
+    assert_eq!(decryption.private_key().as_slice(), ALICE_PRIVATE);
+
+    let error = PkDecryption::from_private_key(&ALICE_PRIVATE[..31])
+        .err()
+        .unwrap();
+    assert_eq!(error.code(), OlmErrorCode_OLM_INPUT_BUFFER_TOO_SMALL);
+}
+
+#[test]
+fn messages_decrypt_only_with_the_right_key() {
+    let mut random = MockRandom::new(b'K', 0);
+    let mut alice = PkDecryption::from_private_key(&ALICE_PRIVATE).unwrap();
+    let mut eve = PkDecryption::new(&mut random);
+    assert_ne!(eve.public_key(), ALICE_PUBLIC);
+
+    let mut encryption = PkEncryption::new(ALICE_PUBLIC).unwrap();
+    let message = encryption.encrypt(b"This is a test", &mut random);
+
+    assert_eq!(*alice.decrypt(&message).unwrap(), b"This is a test");
+    assert_eq!(
+        eve.decrypt(&message).unwrap_err().code(),
+        OlmErrorCode_OLM_BAD_MESSAGE_MAC
+    );
+
+    let mut corrupted = message.clone();
+    corrupted.ciphertext.push('A');
+    assert_eq!(
+        alice.decrypt(&corrupted).unwrap_err().code(),
+        OlmErrorCode_OLM_BAD_MESSAGE_FORMAT
+    );
+    corrupted.ciphertext.push_str("AA");
+    assert_eq!(
+        alice.decrypt(&corrupted).unwrap_err().code(),
+        OlmErrorCode_OLM_INVALID_BASE64
+    );
+}
@@ -0,0 +1,40 @@
+// Copyright 2020 Johannes Hayeß
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The safe `PkSigning` wrapper.
+
+#![cfg(not(feature = "runtime-dlopen"))]
+
+use olm_sys::pk::PkSigning;
+use olm_sys::*;
+
+/// The seed of `olm/tests/test_pk.cpp`, with its public key and signature
+/// of "Message" made with Python.
+const SEED: [u8; 32] = [
+    0x77, 0x07, 0x6D, 0x0A, 0x73, 0x18, 0xA5, 0x7D, 0x3C, 0x16, 0xC1, 0x72, 0x51, 0xB2, 0x66, 0x45,
+    0xDF, 0x4C, 0x2F, 0x87, 0xEB, 0xC0, 0x99, 0x2A, 0xB1, 0x77, 0xFB, 0xA5, 0x1D, 0xB9, 0x2C, 0x2A,
+];
+const PUBLIC_KEY: &str = "0KTOwPi1C6HpNuxWFUVKqX37J4ZPXxdgivLLsQVI8bM";
+const SIGNATURE: &str =
+    "E1uD5cu/VGh3xaENlxKvs+akGuOPMakmTh6rJ8uihRfWx2uGIAUq5hGR6v6CuyKrQ25tuvjRx3UBLFYuagpuBg";
+
+#[test]
+fn sign_with_seed() {
+    let signing = PkSigning::from_seed(&SEED).unwrap();
+    assert_eq!(signing.public_key(), PUBLIC_KEY);
+    assert_eq!(signing.sign(b"Message"), SIGNATURE);
+
+    let error = PkSigning::from_seed(&SEED[..16]).err().unwrap();
+    assert_eq!(error.code(), OlmErrorCode_OLM_INPUT_BUFFER_TOO_SMALL);
+}
@@ -0,0 +1,129 @@
+// Copyright 2020 Johannes Hayeß
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The test vectors of `olm/tests/test_sas.cpp`: short authentication
+//! strings, driven through the raw bindings.
+
+#![cfg(not(feature = "runtime-dlopen"))]
+
+mod common;
+
+use common::alloc;
+use olm_sys::*;
+
+use std::ffi::c_void;
+
+const ALICE_PRIVATE: [u8; 32] = [
+    0x77, 0x07, 0x6D, 0x0A, 0x73, 0x18, 0xA5, 0x7D, 0x3C, 0x16, 0xC1, 0x72, 0x51, 0xB2, 0x66, 0x45,
+    0xDF, 0x4C, 0x2F, 0x87, 0xEB, 0xC0, 0x99, 0x2A, 0xB1, 0x77, 0xFB, 0xA5, 0x1D, 0xB9, 0x2C, 0x2A,
+];
+const ALICE_PUBLIC: &[u8] = b"hSDwCYkwp1R0i33ctD73Wg2/Og0mOBr066SpjqqbTmo";
+
+const BOB_PRIVATE: [u8; 32] = [
+    0x5D, 0xAB, 0x08, 0x7E, 0x62, 0x4A, 0x8A, 0x4B, 0x79, 0xE1, 0x7F, 0x8B, 0x83, 0x80, 0x0E, 0xE6,
+    0x6F, 0x3B, 0xB1, 0x29, 0x26, 0x18, 0xB6, 0xFD, 0x1C, 0x2F, 0x8B, 0x27, 0xFF, 0x88, 0xE0, 0xEB,
+];
+const BOB_PUBLIC: &[u8] = b"3p7bfXt9wbTTW2HC7OQ1Nz+DQ8hbeGdNrfx+FG+IK08";
+
+fn create_sas(memory: &mut [u64], private_key: &[u8; 32]) -> *mut OlmSAS {
+    let sas = unsafe { olm_sas(common::memory(memory)) };
+    // The key is used as the randomness, which olm_create_sas wipes
+    let mut random = *private_key;
+    let result = unsafe { olm_create_sas(sas, random.as_mut_ptr() as *mut c_void, random.len()) };
+    assert_ne!(result, unsafe { olm_error() });
+
+    sas
+}
+
+fn pubkey(sas: *mut OlmSAS) -> Vec<u8> {
+    let mut pubkey = vec![0; unsafe { olm_sas_pubkey_length(sas) }];
+    unsafe { olm_sas_get_pubkey(sas, pubkey.as_mut_ptr() as *mut c_void, pubkey.len()) };
+    pubkey
+}
+
+fn set_their_key(sas: *mut OlmSAS, key: &[u8]) {
+    // libolm decodes the key in place
+    let mut key = key.to_vec();
+    let result = unsafe { olm_sas_set_their_key(sas, key.as_mut_ptr() as *mut c_void, key.len()) };
+    assert_ne!(result, unsafe { olm_error() });
+}
+
+/// Alice's and Bob's SAS objects, which know each other's keys.
+fn alice_and_bob(alice_memory: &mut [u64], bob_memory: &mut [u64]) -> (*mut OlmSAS, *mut OlmSAS) {
+    let alice = create_sas(alice_memory, &ALICE_PRIVATE);
+    let bob = create_sas(bob_memory, &BOB_PRIVATE);
+
+    let alice_public = pubkey(alice);
+    assert_eq!(alice_public, ALICE_PUBLIC);
+    set_their_key(bob, &alice_public);
+
+    let bob_public = pubkey(bob);
+    assert_eq!(bob_public, BOB_PUBLIC);
+    set_their_key(alice, &bob_public);
+
+    (alice, bob)
+}
+
+#[test]
+fn generate_bytes() {
+    let mut alice_memory = alloc(unsafe { olm_sas_size() });
+    let mut bob_memory = alloc(unsafe { olm_sas_size() });
+    let (alice, bob) = alice_and_bob(&mut alice_memory, &mut bob_memory);
+
+    let generate = |sas: *mut OlmSAS| {
+        let mut bytes = [0u8; 6];
+        let info = b"SAS";
+        let result = unsafe {
+            olm_sas_generate_bytes(
+                sas,
+                info.as_ptr() as *const c_void,
+                info.len(),
+                bytes.as_mut_ptr() as *mut c_void,
+                bytes.len(),
+            )
+        };
+        assert_ne!(result, unsafe { olm_error() });
+        bytes
+    };
+
+    assert_eq!(generate(alice), generate(bob));
+}
+
+#[test]
+fn calculate_mac() {
+    let mut alice_memory = alloc(unsafe { olm_sas_size() });
+    let mut bob_memory = alloc(unsafe { olm_sas_size() });
+    let (alice, bob) = alice_and_bob(&mut alice_memory, &mut bob_memory);
+
+    let calculate = |sas: *mut OlmSAS| {
+        let input = b"Hello world!";
+        let info = b"MAC";
+        let mut mac = vec![0u8; unsafe { olm_sas_mac_length(sas) }];
+        let result = unsafe {
+            olm_sas_calculate_mac(
+                sas,
+                input.as_ptr() as *const c_void,
+                input.len(),
+                info.as_ptr() as *const c_void,
+                info.len(),
+                mac.as_mut_ptr() as *mut c_void,
+                mac.len(),
+            )
+        };
+        assert_ne!(result, unsafe { olm_error() });
+        mac
+    };
+
+    assert_eq!(calculate(alice), calculate(bob));
+}
@@ -0,0 +1,141 @@
+// Copyright 2020 Johannes Hayeß
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Secret storage with `m.secret_storage.v1.aes-hmac-sha2`.
+
+#![cfg(all(feature = "matrix", not(feature = "runtime-dlopen")))]
+
+mod common;
+
+use common::MockRandom;
+use olm_sys::secret_storage::{EncryptedSecret, KeyCheck, SecretStorageError, SecretStorageKey};
+
+use serde_json::json;
+use zeroize::Zeroizing;
+
+/// The key 0, 1, …, 31; the vectors below were made with Python.
+fn key() -> SecretStorageKey {
+    SecretStorageKey::from_bytes(&std::array::from_fn(|i| i as u8))
+}
+
+fn secret() -> EncryptedSecret {
+    EncryptedSecret {
+        iv: "QkJCQkJCQkJ/QkJCQkJCQg==".to_string(),
+        ciphertext: "A+CG51ivB9nA".to_string(),
+        mac: "XHuQuc+D9g0XnBu8VmUhL1TVF0+ghs2NzkqRWyQNdpY=".to_string(),
+    }
+}
+
+#[test]
+fn decrypt_known_secret() {
+    let secret = EncryptedSecret::from_json(&json!({
+        "iv": "QkJCQkJCQkJ/QkJCQkJCQg==",
+        "ciphertext": "A+CG51ivB9nA",
+        "mac": "XHuQuc+D9g0XnBu8VmUhL1TVF0+ghs2NzkqRWyQNdpY=",
+    }))
+    .unwrap();
+
+    assert_eq!(
+        key().decrypt_secret("m.cross_signing.master", &secret),
+        Ok(Zeroizing::new(b"my secret".to_vec()))
+    );
+    assert_eq!(
+        key().decrypt_secret("m.cross_signing.self_signing", &secret),
+        Err(SecretStorageError::BadMac)
+    );
+}
+
+#[test]
+fn encrypt_decrypt_round_trip() {
+    let key = key();
+    let mut random = MockRandom::new(b'I', 0x00);
+    let encrypted = key.encrypt_secret("m.megolm_backup.v1", b"backup key", &mut random);
+
+    assert_eq!(
+        EncryptedSecret::from_json(&encrypted.to_json()),
+        Ok(encrypted.clone())
+    );
+    assert_eq!(
+        key.decrypt_secret("m.megolm_backup.v1", &encrypted),
+        Ok(Zeroizing::new(b"backup key".to_vec()))
+    );
+
+    let mut tampered = encrypted;
+    tampered.ciphertext = secret().ciphertext;
+    assert_eq!(
+        key.decrypt_secret("m.megolm_backup.v1", &tampered),
+        Err(SecretStorageError::BadMac)
+    );
+}
+
+#[test]
+fn key_check() {
+    let known = KeyCheck {
+        iv: "AAAAAAAAAAAAAAAAAAAAAA==".to_string(),
+        mac: "Gv+0yDqPNdj9zSgvL1FUew0ODALBHY/PO5cLnkGX55w=".to_string(),
+    };
+    assert_eq!(key().check_key(&known), Ok(()));
+
+    let other = SecretStorageKey::from_bytes(&[0; 32]);
+    assert_eq!(other.check_key(&known), Err(SecretStorageError::BadMac));
+
+    let check = other.key_check(&mut MockRandom::new(b'K', 0x00));
+    assert_eq!(other.check_key(&check), Ok(()));
+    assert_eq!(key().check_key(&check), Err(SecretStorageError::BadMac));
+}
+
+#[test]
+fn passphrase_and_recovery_key() {
+    let from_passphrase = SecretStorageKey::from_passphrase(
+        "correct horse battery staple",
+        "MmA8eOxQ8kVTjb0ExVbmJqkQ0MLWxM5V",
+        10,
+    )
+    .unwrap();
+    let expected = [
+        0xa8, 0xd8, 0x3b, 0xcb, 0xb8, 0x00, 0xcd, 0x94, 0x38, 0x5b, 0xb4, 0x16, 0xf8, 0x3a, 0x47,
+        0xc7, 0xaa, 0x53, 0x20, 0x18, 0x54, 0x15, 0xea, 0x2e, 0xc4, 0x39, 0x71, 0x31, 0xe7, 0x19,
+        0x58, 0xbd,
+    ];
+    assert_eq!(
+        from_passphrase.to_base58(),
+        SecretStorageKey::from_bytes(&expected).to_base58()
+    );
+
+    let recovery_key = "EsSz ykH7 LCZx 7Cae cmKD wcmY JRXi Ybtu 8iQ3 t8Ez nRwK pUY1";
+    assert_eq!(key().to_base58(), recovery_key);
+    let decoded = SecretStorageKey::from_base58(recovery_key).unwrap();
+    assert_eq!(
+        decoded.decrypt_secret("m.cross_signing.master", &secret()),
+        Ok(Zeroizing::new(b"my secret".to_vec()))
+    );
+    assert!(matches!(
+        SecretStorageKey::from_base58("EsSz ykH7"),
+        Err(SecretStorageError::InvalidRecoveryKey)
+    ));
+    assert!(matches!(
+        SecretStorageKey::from_passphrase("correct horse battery staple", "salt", 0),
+        Err(SecretStorageError::InvalidIterations)
+    ));
+    assert!(matches!(
+        key().decrypt_secret(
+            "m.cross_signing.master",
+            &EncryptedSecret {
+                iv: "QkJC".to_string(),
+                ..secret()
+            }
+        ),
+        Err(SecretStorageError::InvalidFormat)
+    ));
+}
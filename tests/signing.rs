@@ -0,0 +1,143 @@
+// Copyright 2020 Johannes Hayeß
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Signing JSON over its canonical form.
+
+#![cfg(all(feature = "matrix", not(feature = "runtime-dlopen")))]
+
+mod common;
+
+use common::MockRandom;
+use olm_sys::account::Account;
+use olm_sys::pk::PkSigning;
+use olm_sys::signing::{canonical_json, sign_json, verify_json, SignatureError};
+use olm_sys::OlmErrorCode_OLM_BAD_MESSAGE_MAC;
+
+use serde_json::{json, Value};
+
+/// The signing key of the examples in the appendix of the Matrix
+/// specification, "YJDBA9Xnr2sVqXD9Vj7XVUnmFZcZrlw8Md7kMW+3XA1", and its public
+/// key as derived by Python's `cryptography`.
+const SEED: [u8; 32] = [
+    96, 144, 193, 3, 213, 231, 175, 107, 21, 169, 112, 253, 86, 62, 215, 85, 73, 230, 21, 151, 25,
+    174, 92, 60, 49, 222, 228, 49, 111, 183, 92, 13,
+];
+const PUBLIC_KEY: &str = "XGX0JRS2Af3be3knz2fBiRbApjm2Dh61gXDJA8kcJNI";
+
+#[test]
+fn canonical_json_matches_specification() {
+    let examples = [
+        (json!({}), "{}"),
+        (json!({"one": 1, "two": "Two"}), r#"{"one":1,"two":"Two"}"#),
+        (json!({"b": "2", "a": "1"}), r#"{"a":"1","b":"2"}"#),
+        (
+            json!({"auth": {
+                "success": true,
+                "mxid": "@john.doe:example.com",
+                "profile": {
+                    "display_name": "John Doe",
+                    "three_pids": [
+                        {"medium": "email", "address": "john.doe@example.org"},
+                        {"medium": "msisdn", "address": "123456789"},
+                    ],
+                },
+            }}),
+            concat!(
+                r#"{"auth":{"mxid":"@john.doe:example.com","profile":{"display_name":"John Doe","#,
+                r#""three_pids":[{"address":"john.doe@example.org","medium":"email"},"#,
+                r#"{"address":"123456789","medium":"msisdn"}]},"success":true}}"#,
+            ),
+        ),
+        (json!({"a": "日本語"}), r#"{"a":"日本語"}"#),
+        (json!({"本": 2, "日": 1}), r#"{"日":1,"本":2}"#),
+        (json!({"a": "\u{65E5}"}), r#"{"a":"日"}"#),
+        (json!({"a": null}), r#"{"a":null}"#),
+        (json!({"a": "\u{1}\n"}), r#"{"a":"\u0001\n"}"#),
+    ];
+
+    for (value, canonical) in examples {
+        assert_eq!(canonical_json(&value).as_deref(), Ok(canonical));
+    }
+
+    for value in [json!({"a": 1.5}), json!([9007199254740992_u64])] {
+        assert_eq!(canonical_json(&value), Err(SignatureError::NotCanonical));
+    }
+    assert!(canonical_json(&json!([9007199254740991_u64, -9007199254740991_i64])).is_ok());
+}
+
+#[test]
+fn sign_matches_specification() {
+    let signing = PkSigning::from_seed(&SEED).unwrap();
+    assert_eq!(signing.public_key(), PUBLIC_KEY);
+
+    let mut value = json!({});
+    sign_json(&signing, "domain", "1", &mut value).unwrap();
+    assert_eq!(
+        value,
+        json!({"signatures": {"domain": {"ed25519:1": "K8280/U9SSy9IVtjBuVeLr+HpOB4BQFWbg+UZaADMtTdGYI7Geitb76LTrr5QV/7Xg4ahLwYGYZzuHGZKM5ZAQ"}}})
+    );
+
+    let mut value = json!({"two": "Two", "one": 1, "unsigned": {"age_ts": 922834800000_u64}});
+    sign_json(&signing, "domain", "1", &mut value).unwrap();
+    assert_eq!(
+        value["signatures"]["domain"]["ed25519:1"],
+        "KqmLSbO39/Bzb0QIYE82zqLwsA+PDzYIpIRA2sRQ4sL53+sN6/fpNSoqE7BP7vBZhG6kYdD13EIMJpvhJI+6Bw"
+    );
+    assert_eq!(value["unsigned"], json!({"age_ts": 922834800000_u64}));
+    assert_eq!(verify_json(PUBLIC_KEY, "domain", "1", &value), Ok(()));
+}
+
+#[test]
+fn signatures_accumulate_and_verify() {
+    let account = Account::new(&mut MockRandom::new(b'A', 0x00));
+    let identity_keys: Value = serde_json::from_str(&account.identity_keys()).unwrap();
+    let ed25519_key = identity_keys["ed25519"].as_str().unwrap();
+    let signing = PkSigning::from_seed(&SEED).unwrap();
+
+    let mut value = json!({"user_id": "@alice:example.org", "device_id": "DEVICE"});
+    sign_json(&account, "@alice:example.org", "DEVICE", &mut value).unwrap();
+    sign_json(&signing, "@alice:example.org", PUBLIC_KEY, &mut value).unwrap();
+
+    assert_eq!(
+        verify_json(ed25519_key, "@alice:example.org", "DEVICE", &value),
+        Ok(())
+    );
+    assert_eq!(
+        verify_json(PUBLIC_KEY, "@alice:example.org", PUBLIC_KEY, &value),
+        Ok(())
+    );
+    assert_eq!(
+        verify_json(ed25519_key, "@bob:example.org", "DEVICE", &value),
+        Err(SignatureError::MissingSignature)
+    );
+
+    // Unsigned data can change, anything else breaks the signature
+    value["unsigned"] = json!({"device_display_name": "Phone"});
+    assert_eq!(
+        verify_json(ed25519_key, "@alice:example.org", "DEVICE", &value),
+        Ok(())
+    );
+    value["device_id"] = json!("OTHER");
+    match verify_json(ed25519_key, "@alice:example.org", "DEVICE", &value) {
+        Err(SignatureError::Olm(error)) => {
+            assert_eq!(error.code(), OlmErrorCode_OLM_BAD_MESSAGE_MAC)
+        }
+        result => panic!("{result:?}"),
+    }
+
+    assert_eq!(
+        sign_json(&account, "@alice:example.org", "DEVICE", &mut json!([])),
+        Err(SignatureError::InvalidJson)
+    );
+}
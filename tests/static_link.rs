@@ -0,0 +1,68 @@
+// Copyright 2020 Johannes Hayeß
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Checks that musl builds come out fully static, C++ runtime included.
+//!
+//! Run with e.g. `cargo test --target x86_64-unknown-linux-musl`.
+
+#![cfg(all(
+    target_os = "linux",
+    target_env = "musl",
+    not(feature = "runtime-dlopen")
+))]
+
+use olm_sys::*;
+
+use std::ffi::c_void;
+
+const PT_INTERP: u32 = 3;
+
+/// Whether the ELF file has a program header requesting a dynamic loader.
+fn has_interpreter(elf: &[u8]) -> bool {
+    let u16_at = |offset: usize| u16::from_ne_bytes(elf[offset..offset + 2].try_into().unwrap());
+    let u32_at = |offset: usize| u32::from_ne_bytes(elf[offset..offset + 4].try_into().unwrap());
+    let u64_at = |offset: usize| u64::from_ne_bytes(elf[offset..offset + 8].try_into().unwrap());
+
+    assert_eq!(&elf[..4], b"\x7fELF");
+    let (header_offset, entry_size, entries) = match elf[4] {
+        // ELFCLASS32
+        1 => (u32_at(0x1c) as usize, u16_at(0x2a), u16_at(0x2c)),
+        // ELFCLASS64
+        2 => (u64_at(0x20) as usize, u16_at(0x36), u16_at(0x38)),
+        class => panic!("unknown ELF class {}", class),
+    };
+
+    (0..entries as usize)
+        .map(|i| u32_at(header_offset + i * entry_size as usize))
+        .any(|kind| kind == PT_INTERP)
+}
+
+#[test]
+fn binary_is_statically_linked() {
+    let elf = std::fs::read("/proc/self/exe").unwrap();
+    assert!(!has_interpreter(&elf), "test binary needs a dynamic loader");
+}
+
+#[test]
+fn cxx_parts_of_libolm_work() {
+    // olm_account() runs C++ code, including a constructor.
+    let size = unsafe { olm_account_size() };
+    let mut memory = vec![0u64; size.div_ceil(8)];
+    let account = unsafe { olm_account(memory.as_mut_ptr() as *mut c_void) };
+
+    assert_eq!(
+        unsafe { olm_account_last_error_code(account) },
+        OlmErrorCode_OLM_SUCCESS
+    );
+}
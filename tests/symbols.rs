@@ -0,0 +1,284 @@
+// Copyright 2020 Johannes Hayeß
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Link-time check that every function declared in `src/lib.rs` is provided
+//! by the `libolm` this crate is linked against.
+//!
+//! Taking the address of each function forces the linker to resolve it, so
+//! linking against an older `libolm` which lacks some of them fails here
+//! instead of at the first call in production.
+
+#![cfg(not(feature = "runtime-dlopen"))]
+
+use olm_sys::*;
+
+macro_rules! symbols {
+    ($($name:ident,)*) => {
+        /// Names and addresses of all bound functions.
+        fn symbols() -> Vec<(&'static str, usize)> {
+            vec![$((stringify!($name), $name as *const () as usize),)*]
+        }
+    };
+}
+
+symbols! {
+    _olm_error_to_string,
+    olm_inbound_group_session_size,
+    olm_inbound_group_session,
+    olm_inbound_group_session_last_error,
+    olm_inbound_group_session_last_error_code,
+    olm_clear_inbound_group_session,
+    olm_pickle_inbound_group_session_length,
+    olm_pickle_inbound_group_session,
+    olm_unpickle_inbound_group_session,
+    olm_init_inbound_group_session,
+    olm_import_inbound_group_session,
+    olm_group_decrypt_max_plaintext_length,
+    olm_group_decrypt,
+    olm_inbound_group_session_id_length,
+    olm_inbound_group_session_id,
+    olm_inbound_group_session_first_known_index,
+    olm_inbound_group_session_is_verified,
+    olm_export_inbound_group_session_length,
+    olm_export_inbound_group_session,
+    olm_outbound_group_session_size,
+    olm_outbound_group_session,
+    olm_outbound_group_session_last_error,
+    olm_outbound_group_session_last_error_code,
+    olm_clear_outbound_group_session,
+    olm_pickle_outbound_group_session_length,
+    olm_pickle_outbound_group_session,
+    olm_unpickle_outbound_group_session,
+    olm_init_outbound_group_session_random_length,
+    olm_init_outbound_group_session,
+    olm_group_encrypt_message_length,
+    olm_group_encrypt,
+    olm_outbound_group_session_id_length,
+    olm_outbound_group_session_id,
+    olm_outbound_group_session_message_index,
+    olm_outbound_group_session_key_length,
+    olm_outbound_group_session_key,
+    olm_get_library_version,
+    olm_account_size,
+    olm_session_size,
+    olm_utility_size,
+    olm_account,
+    olm_session,
+    olm_utility,
+    olm_error,
+    olm_account_last_error,
+    olm_account_last_error_code,
+    olm_session_last_error,
+    olm_session_last_error_code,
+    olm_utility_last_error,
+    olm_utility_last_error_code,
+    olm_clear_account,
+    olm_clear_session,
+    olm_clear_utility,
+    olm_pickle_account_length,
+    olm_pickle_session_length,
+    olm_pickle_account,
+    olm_pickle_session,
+    olm_unpickle_account,
+    olm_unpickle_session,
+    olm_create_account_random_length,
+    olm_create_account,
+    olm_account_identity_keys_length,
+    olm_account_identity_keys,
+    olm_account_signature_length,
+    olm_account_sign,
+    olm_account_one_time_keys_length,
+    olm_account_one_time_keys,
+    olm_account_mark_keys_as_published,
+    olm_account_max_number_of_one_time_keys,
+    olm_account_generate_one_time_keys_random_length,
+    olm_account_generate_one_time_keys,
+    olm_account_generate_fallback_key_random_length,
+    olm_account_generate_fallback_key,
+    olm_account_fallback_key_length,
+    olm_account_fallback_key,
+    olm_account_unpublished_fallback_key_length,
+    olm_account_unpublished_fallback_key,
+    olm_account_forget_old_fallback_key,
+    olm_create_outbound_session_random_length,
+    olm_create_outbound_session,
+    olm_create_inbound_session,
+    olm_create_inbound_session_from,
+    olm_session_id_length,
+    olm_session_id,
+    olm_session_has_received_message,
+    olm_session_describe,
+    olm_matches_inbound_session,
+    olm_matches_inbound_session_from,
+    olm_remove_one_time_keys,
+    olm_encrypt_message_type,
+    olm_encrypt_random_length,
+    olm_encrypt_message_length,
+    olm_encrypt,
+    olm_decrypt_max_plaintext_length,
+    olm_decrypt,
+    olm_sha256_length,
+    olm_sha256,
+    olm_ed25519_verify,
+    olm_sas_last_error,
+    olm_sas_last_error_code,
+    olm_sas_size,
+    olm_sas,
+    olm_clear_sas,
+    olm_create_sas_random_length,
+    olm_create_sas,
+    olm_sas_pubkey_length,
+    olm_sas_get_pubkey,
+    olm_sas_set_their_key,
+    olm_sas_is_their_key_set,
+    olm_sas_generate_bytes,
+    olm_sas_mac_length,
+    olm_sas_calculate_mac,
+    olm_sas_calculate_mac_fixed_base64,
+    olm_sas_calculate_mac_long_kdf,
+    olm_pk_encryption_size,
+    olm_pk_encryption,
+    olm_pk_encryption_last_error,
+    olm_pk_encryption_last_error_code,
+    olm_clear_pk_encryption,
+    olm_pk_encryption_set_recipient_key,
+    olm_pk_ciphertext_length,
+    olm_pk_mac_length,
+    olm_pk_key_length,
+    olm_pk_encrypt_random_length,
+    olm_pk_encrypt,
+    olm_pk_decryption_size,
+    olm_pk_decryption,
+    olm_pk_decryption_last_error,
+    olm_pk_decryption_last_error_code,
+    olm_clear_pk_decryption,
+    olm_pk_private_key_length,
+    olm_pk_generate_key_random_length,
+    olm_pk_key_from_private,
+    olm_pk_generate_key,
+    olm_pickle_pk_decryption_length,
+    olm_pickle_pk_decryption,
+    olm_unpickle_pk_decryption,
+    olm_pk_max_plaintext_length,
+    olm_pk_decrypt,
+    olm_pk_get_private_key,
+    olm_pk_signing_size,
+    olm_pk_signing,
+    olm_pk_signing_last_error,
+    olm_pk_signing_last_error_code,
+    olm_clear_pk_signing,
+    olm_pk_signing_key_from_seed,
+    olm_pk_signing_seed_length,
+    olm_pk_signing_public_key_length,
+    olm_pk_signature_length,
+    olm_pk_sign,
+    _olm_encode_group_message_length,
+    _olm_encode_group_message,
+    _olm_decode_group_message,
+    _olm_crypto_aes_encrypt_cbc_length,
+    _olm_crypto_aes_encrypt_cbc,
+    _olm_crypto_aes_decrypt_cbc,
+    _olm_crypto_sha256,
+    _olm_crypto_hmac_sha256,
+    _olm_crypto_hkdf_sha256,
+    _olm_crypto_curve25519_generate_key,
+    _olm_crypto_curve25519_shared_secret,
+    _olm_crypto_ed25519_generate_key,
+    _olm_crypto_ed25519_sign,
+    _olm_crypto_ed25519_verify,
+    _olm_enc_output_length,
+    _olm_enc_output_pos,
+    _olm_enc_output,
+    _olm_enc_input,
+    _olm_encode_base64_length,
+    _olm_encode_base64,
+    _olm_decode_base64_length,
+    _olm_decode_base64,
+}
+
+#[test]
+fn all_symbols_resolve() {
+    for (name, address) in symbols() {
+        assert_ne!(
+            std::hint::black_box(address),
+            0,
+            "{} resolved to null",
+            name
+        );
+    }
+}
+
+#[test]
+fn symbol_list_is_complete() {
+    let bindings = include_str!("../src/lib.rs");
+    let declared: Vec<&str> = bindings
+        .split("pub fn ")
+        .skip(1)
+        .map(|rest| rest.split('(').next().unwrap())
+        .collect();
+    let checked: Vec<&str> = symbols().into_iter().map(|(name, _)| name).collect();
+
+    for name in &declared {
+        assert!(
+            checked.contains(name),
+            "{} is missing from tests/symbols.rs",
+            name
+        );
+    }
+    assert_eq!(declared.len(), checked.len());
+}
+
+/// With `prefix-symbols` only the renamed symbols may end up in the binary,
+/// both for the bound functions and for the generic names of `libolm`'s
+/// bundled crypto code, which other C libraries define too.
+#[cfg(feature = "prefix-symbols")]
+#[test]
+fn only_prefixed_symbols_are_linked() {
+    let output = std::process::Command::new(std::env::var("NM").unwrap_or_else(|_| "nm".into()))
+        .arg("--defined-only")
+        .arg(std::env::current_exe().unwrap())
+        .output()
+        .expect("Failed to run nm");
+    assert!(output.status.success(), "nm failed");
+
+    let listing = String::from_utf8_lossy(&output.stdout);
+    let defined: std::collections::HashSet<&str> = listing
+        .lines()
+        .filter_map(|line| line.split_whitespace().nth(2))
+        // Symbols of C functions carry a leading underscore on Apple targets
+        .map(|symbol| match symbol.strip_prefix('_') {
+            Some(name) if cfg!(target_vendor = "apple") => name,
+            _ => symbol,
+        })
+        .collect();
+
+    let bundled = [
+        "aes_key_setup",
+        "curve25519_donna",
+        "ed25519_sign",
+        "fe_add",
+        "ge_add",
+        "sha256_init",
+        "sha512",
+    ];
+    let bound = symbols().into_iter().map(|(name, _)| name);
+    for name in bound.chain(bundled) {
+        assert!(
+            defined.contains(format!("olmsys_{}", name).as_str()),
+            "olmsys_{} isn't linked",
+            name
+        );
+        assert!(!defined.contains(name), "{} isn't prefixed", name);
+    }
+}
@@ -3,3 +3,11 @@
 #include "olm/sas.h"
 // Used for secret storage and device cross signing
 #include "olm/pk.h"
+// Message headers, for inspecting messages without decrypting them
+#include "olm/message.h"
+// Crypto primitives, for the Matrix key formats built on top of libolm
+#include "olm/crypto.h"
+// Encrypted pickles, for pickling objects built on top of libolm
+#include "olm/pickle_encoding.h"
+// Unpadded base64, as libolm encodes keys and messages
+#include "olm/base64.h"